@@ -130,6 +130,7 @@ impl From<LegacyManifest> for Manifest {
             // The JS implementation infers `adult`, but it's such a rare case, it's not worth it:
             // https://github.com/Stremio/stremio-addon-client/blob/4f4dbbf55498d7fdc6bd41bf49cb2f05915b3f8e/lib/transports/legacy/mapper.js#L70
             behavior_hints: Default::default(),
+            config: vec![],
         }
     }
 }
@@ -14,6 +14,11 @@ pub const STREAMS_STORAGE_KEY: &str = "streams";
 pub const SEARCH_HISTORY_STORAGE_KEY: &str = "search_history";
 pub const NOTIFICATIONS_STORAGE_KEY: &str = "notifications";
 pub const DISMISSED_EVENTS_STORAGE_KEY: &str = "dismissed_events";
+pub const LANGUAGE_PREFERENCES_STORAGE_KEY: &str = "language_preferences";
+pub const INTROS_STORAGE_KEY: &str = "intros";
+pub const PLAY_QUEUE_STORAGE_KEY: &str = "play_queue";
+pub const WATCH_HISTORY_STORAGE_KEY: &str = "watch_history";
+pub const ADDONS_HISTORY_STORAGE_KEY: &str = "addons_history";
 pub const LIBRARY_COLLECTION_NAME: &str = "libraryItem";
 pub const SEARCH_EXTRA_NAME: &str = "search";
 /// `https://{ADDON_UR}/meta/...` resource
@@ -28,6 +33,15 @@ pub const CATALOG_PAGE_SIZE: usize = 100;
 pub const CATALOG_PREVIEW_SIZE: usize = 100;
 pub const LIBRARY_RECENT_COUNT: usize = 200;
 pub const NOTIFICATION_ITEMS_COUNT: usize = 100;
+/// The most ids requested at once from an addon when pulling notifications
+pub const NOTIFICATIONS_BATCH_SIZE: usize = 25;
+/// The most notifications requests which are loading at the same time
+pub const NOTIFICATIONS_CONCURRENT_REQUESTS: usize = 2;
+/// The most library items checked for new videos on a single pull,
+/// the rest of them are checked on the next pulls
+pub const NOTIFICATIONS_PULL_LIMIT: usize = 100;
+/// The least time between two pulls which send requests to the addons
+pub const NOTIFICATIONS_PULL_INTERVAL_MINUTES: i64 = 10;
 
 /// A `LibraryItem` is considered watched once we've watched more than the `duration * threshold`:
 ///
@@ -35,7 +49,7 @@ pub const NOTIFICATION_ITEMS_COUNT: usize = 100;
 pub const WATCHED_THRESHOLD_COEF: f64 = 0.7;
 pub const CREDITS_THRESHOLD_COEF: f64 = 0.9;
 /// The latest migration scheme version
-pub const SCHEMA_VERSION: u32 = 14;
+pub const SCHEMA_VERSION: u32 = 24;
 pub const IMDB_LINK_CATEGORY: &str = "imdb";
 pub const GENRES_LINK_CATEGORY: &str = "Genres";
 pub const CINEMETA_TOP_CATALOG_ID: &str = "top";
@@ -57,6 +71,10 @@ pub const URI_COMPONENT_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
 /// In milliseconds
 pub const PLAYER_IGNORE_SEEK_AFTER: u64 = 600_000;
 
+/// The number of consecutive videos played by binge watching after which
+/// the user is asked if they're still watching.
+pub const STILL_WATCHING_AFTER_EPISODES: u32 = 3;
+
 pub static BASE64: base64::engine::general_purpose::GeneralPurpose =
     base64::engine::general_purpose::STANDARD;
 
@@ -103,6 +121,13 @@ pub static VIDEO_FILENAME_EXTRA_PROP: Lazy<ExtraProp> = Lazy::new(|| ExtraProp {
     options: vec![],
     options_limit: OptionsLimit::default(),
 });
+/// The interface language of the user, passed to the addons which declare it
+pub static LANGUAGE_EXTRA_PROP: Lazy<ExtraProp> = Lazy::new(|| ExtraProp {
+    name: "language".to_owned(),
+    is_required: false,
+    options: vec![],
+    options_limit: OptionsLimit(1),
+});
 pub static LAST_VIDEOS_IDS_EXTRA_PROP: Lazy<ExtraProp> = Lazy::new(|| ExtraProp {
     name: "lastVideosIds".to_owned(),
     is_required: false,
@@ -0,0 +1,213 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+use percent_encoding::percent_decode_str;
+use serde::Serialize;
+use url::{form_urlencoded, Url};
+
+use crate::{
+    constants::{
+        META_RESOURCE_NAME, SEARCH_EXTRA_NAME, STREAM_RESOURCE_NAME, SUBTITLES_RESOURCE_NAME,
+    },
+    models::{
+        addon_configure::Selected as AddonConfigureSelected,
+        catalog_with_filters::{CatalogResourceAdapter, Selected as CatalogWithFiltersSelected},
+        catalogs_with_extra::Selected as CatalogsWithExtraSelected,
+        installed_addons_with_filters::{
+            InstalledAddonsRequest, Selected as InstalledAddonsWithFiltersSelected,
+        },
+        library_with_filters::{LibraryRequest, Selected as LibraryWithFiltersSelected, Sort},
+        meta_details::Selected as MetaDetailsSelected,
+        player::Selected as PlayerSelected,
+    },
+    runtime::msg::ActionLoad,
+    types::{
+        addon::{DescriptorPreview, ExtraValue, ResourcePath, ResourceRequest},
+        resource::{MetaItemPreview, Stream},
+    },
+};
+
+/// A route parsed from a deep link, the inverse of the deep links generators.
+///
+/// Both the `stremio:///` and the web `#/` links are supported, e.g.
+/// `stremio:///detail/series/tt1` and `#/detail/series/tt1`.
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(tag = "route", content = "args")]
+pub enum DeepLinkRoute {
+    /// `/detail/{type}/{id}` and `/detail/{type}/{id}/{video_id}`
+    MetaDetails(MetaDetailsSelected),
+    /// `/player/{stream}` and
+    /// `/player/{stream}/{stream_base}/{meta_base}/{type}/{id}/{video_id}`
+    Player(Box<PlayerSelected>),
+    /// `/discover/{base}/{type}/{id}?{extra}`
+    Discover(CatalogWithFiltersSelected),
+    /// `/addons/{type}/{base}/{id}`
+    Addons(CatalogWithFiltersSelected),
+    /// `/addons/configure/{transport_url}`
+    AddonConfigure(AddonConfigureSelected),
+    /// `/addons` and `/addons/{type}`
+    InstalledAddons(InstalledAddonsWithFiltersSelected),
+    /// `/library?sort={sort}` and `/library/{type}?sort={sort}`
+    Library(LibraryWithFiltersSelected),
+    /// `/continuewatching?sort={sort}` and `/continuewatching/{type}?sort={sort}`
+    ContinueWatching(LibraryWithFiltersSelected),
+    /// `/search?query={query}`
+    Search(CatalogsWithExtraSelected),
+}
+
+impl FromStr for DeepLinkRoute {
+    type Err = anyhow::Error;
+
+    fn from_str(deep_link: &str) -> Result<Self, Self::Err> {
+        let route = deep_link
+            .strip_prefix("stremio:///")
+            .or_else(|| deep_link.strip_prefix("#/"))
+            .ok_or_else(|| anyhow!("Not a deep link: {deep_link}"))?;
+        let (path, query) = route.split_once('?').unwrap_or((route, ""));
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                percent_decode_str(segment)
+                    .decode_utf8()
+                    .map(|segment| segment.into_owned())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let query = form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect::<Vec<_>>();
+        let query_value = |name: &str| {
+            query
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.to_owned())
+        };
+
+        match segments.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+            ["detail", r#type, id] => Ok(DeepLinkRoute::MetaDetails(MetaDetailsSelected {
+                meta_path: ResourcePath::without_extra(META_RESOURCE_NAME, r#type, id),
+                stream_path: None,
+                guess_stream: false,
+            })),
+            ["detail", r#type, id, video_id] => {
+                Ok(DeepLinkRoute::MetaDetails(MetaDetailsSelected {
+                    meta_path: ResourcePath::without_extra(META_RESOURCE_NAME, r#type, id),
+                    stream_path: Some(ResourcePath::without_extra(
+                        STREAM_RESOURCE_NAME,
+                        r#type,
+                        video_id,
+                    )),
+                    guess_stream: false,
+                }))
+            }
+            ["player", stream] => Ok(DeepLinkRoute::Player(Box::new(PlayerSelected {
+                stream: Stream::decode(stream.to_owned()).context("Invalid stream")?,
+                stream_request: None,
+                meta_request: None,
+                subtitles_path: None,
+            }))),
+            ["player", stream, stream_base, meta_base, r#type, id, video_id] => {
+                Ok(DeepLinkRoute::Player(Box::new(PlayerSelected {
+                    stream: Stream::decode(stream.to_owned()).context("Invalid stream")?,
+                    stream_request: Some(ResourceRequest::new(
+                        Url::parse(stream_base)?,
+                        ResourcePath::without_extra(STREAM_RESOURCE_NAME, r#type, video_id),
+                    )),
+                    meta_request: Some(ResourceRequest::new(
+                        Url::parse(meta_base)?,
+                        ResourcePath::without_extra(META_RESOURCE_NAME, r#type, id),
+                    )),
+                    subtitles_path: Some(ResourcePath::without_extra(
+                        SUBTITLES_RESOURCE_NAME,
+                        r#type,
+                        video_id,
+                    )),
+                })))
+            }
+            ["discover", base, r#type, id] => {
+                Ok(DeepLinkRoute::Discover(CatalogWithFiltersSelected {
+                    request: ResourceRequest::new(
+                        Url::parse(base)?,
+                        ResourcePath {
+                            resource: MetaItemPreview::resource().to_owned(),
+                            r#type: r#type.to_owned(),
+                            id: id.to_owned(),
+                            extra: query
+                                .iter()
+                                .map(|(name, value)| ExtraValue {
+                                    name: name.to_owned(),
+                                    value: value.to_owned(),
+                                })
+                                .collect(),
+                        },
+                    ),
+                }))
+            }
+            ["addons", "configure", transport_url] => {
+                Ok(DeepLinkRoute::AddonConfigure(AddonConfigureSelected {
+                    transport_url: Url::parse(transport_url)?,
+                }))
+            }
+            ["addons", r#type, base, id] => Ok(DeepLinkRoute::Addons(CatalogWithFiltersSelected {
+                request: ResourceRequest::new(
+                    Url::parse(base)?,
+                    ResourcePath::without_extra(DescriptorPreview::resource(), r#type, id),
+                ),
+            })),
+            ["addons"] | ["addons", _] => Ok(DeepLinkRoute::InstalledAddons(
+                InstalledAddonsWithFiltersSelected {
+                    request: InstalledAddonsRequest {
+                        r#type: segments.get(1).cloned(),
+                    },
+                },
+            )),
+            [root @ ("library" | "continuewatching")]
+            | [root @ ("library" | "continuewatching"), _] => {
+                let sort = match query_value("sort") {
+                    Some(sort) => serde_json::from_value::<Sort>(serde_json::Value::String(sort))
+                        .context("Invalid sort")?,
+                    None => Sort::default(),
+                };
+                let selected = LibraryWithFiltersSelected {
+                    request: LibraryRequest {
+                        r#type: segments.get(1).cloned(),
+                        sort,
+                        page: Default::default(),
+                    },
+                };
+                match root {
+                    "library" => Ok(DeepLinkRoute::Library(selected)),
+                    _ => Ok(DeepLinkRoute::ContinueWatching(selected)),
+                }
+            }
+            ["search"] => Ok(DeepLinkRoute::Search(CatalogsWithExtraSelected {
+                r#type: None,
+                extra: vec![ExtraValue {
+                    name: SEARCH_EXTRA_NAME.to_owned(),
+                    value: query_value("query").context("Missing search query")?,
+                }],
+            })),
+            _ => bail!("Unknown deep link route: {path}"),
+        }
+    }
+}
+
+impl From<DeepLinkRoute> for ActionLoad {
+    fn from(route: DeepLinkRoute) -> Self {
+        match route {
+            DeepLinkRoute::MetaDetails(selected) => ActionLoad::MetaDetails(selected),
+            DeepLinkRoute::Player(selected) => ActionLoad::Player(selected),
+            DeepLinkRoute::Discover(selected) | DeepLinkRoute::Addons(selected) => {
+                ActionLoad::CatalogWithFilters(Some(selected))
+            }
+            DeepLinkRoute::AddonConfigure(selected) => ActionLoad::AddonConfigure(selected),
+            DeepLinkRoute::InstalledAddons(selected) => {
+                ActionLoad::InstalledAddonsWithFilters(selected)
+            }
+            DeepLinkRoute::Library(selected) | DeepLinkRoute::ContinueWatching(selected) => {
+                ActionLoad::LibraryWithFilters(selected)
+            }
+            DeepLinkRoute::Search(selected) => ActionLoad::CatalogsWithExtra(selected),
+        }
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
         installed_addons_with_filters::InstalledAddonsRequest, library_with_filters::LibraryRequest,
     },
     types::{
-        addon::{ExtraValue, ResourcePath, ResourceRequest},
+        addon::{Descriptor, ExtraValue, ResourcePath, ResourceRequest},
         library::LibraryItem,
         profile::Settings,
         query_params_encode,
@@ -18,8 +18,10 @@ use crate::{
     },
 };
 
+pub use deep_link_route::DeepLinkRoute;
 pub use error_link::ErrorLink;
 
+mod deep_link_route;
 mod error_link;
 
 #[derive(Default, Serialize, Debug, PartialEq, Eq)]
@@ -556,6 +558,20 @@ impl From<&ResourceRequest> for AddonsDeepLinks {
     }
 }
 
+impl From<&Descriptor> for AddonsDeepLinks {
+    /// The page for configuring the addon, see [`ManifestBehaviorHints::configurable`].
+    ///
+    /// [`ManifestBehaviorHints::configurable`]: crate::types::addon::ManifestBehaviorHints::configurable
+    fn from(addon: &Descriptor) -> Self {
+        AddonsDeepLinks {
+            addons: format!(
+                "stremio:///addons/configure/{}",
+                utf8_percent_encode(addon.transport_url.as_str(), URI_COMPONENT_ENCODE_SET),
+            ),
+        }
+    }
+}
+
 impl From<&InstalledAddonsRequest> for AddonsDeepLinks {
     fn from(request: &InstalledAddonsRequest) -> Self {
         AddonsDeepLinks {
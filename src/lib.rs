@@ -17,6 +17,7 @@ pub mod deep_links;
 pub mod models;
 pub mod runtime;
 pub mod types;
+pub mod watch_party_transport;
 
 pub mod constants;
 
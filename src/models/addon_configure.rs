@@ -0,0 +1,297 @@
+use std::collections::{BTreeMap, HashMap};
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::constants::URI_COMPONENT_ENCODE_SET;
+use crate::models::common::{
+    descriptor_update, eq_update, DescriptorAction, DescriptorLoadable, Loadable,
+};
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionAddonConfigure, ActionLoad, Internal, Msg};
+use crate::runtime::{Effects, Env, UpdateWithCtx};
+use crate::types::addon::{Descriptor, ManifestConfig, ManifestConfigType};
+use crate::types::profile::Profile;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    /// The transport url of the addon, if it's already configured
+    /// the current configuration is used for the initial values.
+    pub transport_url: Url,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(tag = "type")]
+pub enum ConfigValueError {
+    Required,
+    InvalidNumber,
+    InvalidOption,
+}
+
+/// Configures an addon with the settings of its [`Manifest::config`]
+/// and installs the configured addon.
+///
+/// [`Manifest::config`]: crate::types::addon::Manifest::config
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonConfigure {
+    pub selected: Option<Selected>,
+    /// The unconfigured addon which settings are rendered
+    pub addon: Option<DescriptorLoadable>,
+    /// The values of the settings, the current configuration or the defaults
+    pub values: HashMap<String, String>,
+    pub errors: HashMap<String, ConfigValueError>,
+    /// The configured addon, installed once it's loaded
+    pub configured_addon: Option<DescriptorLoadable>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for AddonConfigure {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::AddonConfigure(selected))) => {
+                let (transport_url, values) = unconfigured_transport_url(&selected.transport_url);
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let values_effects = eq_update(&mut self.values, values);
+                let errors_effects = eq_update(&mut self.errors, HashMap::new());
+                let configured_addon_effects = eq_update(&mut self.configured_addon, None);
+                let addon_effects = descriptor_update::<E>(
+                    &mut self.addon,
+                    DescriptorAction::DescriptorRequested {
+                        transport_url: &transport_url,
+                    },
+                );
+                let default_values_effects = default_values_update(&mut self.values, &self.addon);
+                selected_effects
+                    .join(values_effects)
+                    .join(errors_effects)
+                    .join(configured_addon_effects)
+                    .join(addon_effects)
+                    .join(default_values_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let addon_effects = eq_update(&mut self.addon, None);
+                let values_effects = eq_update(&mut self.values, HashMap::new());
+                let errors_effects = eq_update(&mut self.errors, HashMap::new());
+                let configured_addon_effects = eq_update(&mut self.configured_addon, None);
+                selected_effects
+                    .join(addon_effects)
+                    .join(values_effects)
+                    .join(errors_effects)
+                    .join(configured_addon_effects)
+            }
+            Msg::Action(Action::AddonConfigure(ActionAddonConfigure::Configure(values))) => {
+                let addon = match &self.addon {
+                    Some(DescriptorLoadable {
+                        content: Loadable::Ready(addon),
+                        ..
+                    }) => addon,
+                    _ => return Effects::none().unchanged(),
+                };
+                let errors = validate_values(&addon.manifest.config, values);
+                let configured_addon_effects = if errors.is_empty() {
+                    let transport_url = configured_transport_url(
+                        &addon.transport_url,
+                        &addon.manifest.config,
+                        values,
+                    );
+                    // the same configuration can be submitted again after a failure
+                    self.configured_addon = None;
+                    descriptor_update::<E>(
+                        &mut self.configured_addon,
+                        DescriptorAction::DescriptorRequested {
+                            transport_url: &transport_url,
+                        },
+                    )
+                } else {
+                    eq_update(&mut self.configured_addon, None)
+                };
+                let values_effects = eq_update(&mut self.values, values.to_owned());
+                let errors_effects = eq_update(&mut self.errors, errors);
+                values_effects
+                    .join(errors_effects)
+                    .join(configured_addon_effects)
+            }
+            Msg::Internal(Internal::ManifestRequestResult(transport_url, result)) => {
+                let addon_effects = descriptor_update::<E>(
+                    &mut self.addon,
+                    DescriptorAction::ManifestRequestResult {
+                        transport_url,
+                        result,
+                    },
+                );
+                let default_values_effects = if addon_effects.has_changed {
+                    default_values_update(&mut self.values, &self.addon)
+                } else {
+                    Effects::none().unchanged()
+                };
+                let configured_addon_effects = descriptor_update::<E>(
+                    &mut self.configured_addon,
+                    DescriptorAction::ManifestRequestResult {
+                        transport_url,
+                        result,
+                    },
+                );
+                let install_effects = match &self.configured_addon {
+                    Some(DescriptorLoadable {
+                        content: Loadable::Ready(configured_addon),
+                        ..
+                    }) if configured_addon_effects.has_changed => {
+                        install_effects(configured_addon, &ctx.profile)
+                    }
+                    _ => Effects::none().unchanged(),
+                };
+                addon_effects
+                    .join(default_values_effects)
+                    .join(configured_addon_effects)
+                    .join(install_effects)
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+/// Splits an addon transport url into the unconfigured one and its configuration,
+/// e.g. `https://addon.com/%7B%22key%22%3A%22value%22%7D/manifest.json`
+/// into `https://addon.com/manifest.json` and `key: value`.
+fn unconfigured_transport_url(transport_url: &Url) -> (Url, HashMap<String, String>) {
+    let segments = transport_url
+        .path_segments()
+        .map(|segments| segments.collect::<Vec<_>>())
+        .unwrap_or_default();
+    let config =
+        match segments[..] {
+            [.., config, "manifest.json"] => percent_decode_str(config)
+                .decode_utf8()
+                .ok()
+                .and_then(|config| {
+                    serde_json::from_str::<HashMap<String, serde_json::Value>>(&config).ok()
+                }),
+            _ => None,
+        };
+    match config {
+        Some(config) => {
+            let mut unconfigured_transport_url = transport_url.to_owned();
+            if let Ok(mut segments) = unconfigured_transport_url.path_segments_mut() {
+                segments.pop().pop().push("manifest.json");
+            }
+            let values = config
+                .into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(value) => (key, value),
+                    value => (key, value.to_string()),
+                })
+                .collect();
+            (unconfigured_transport_url, values)
+        }
+        _ => (transport_url.to_owned(), HashMap::new()),
+    }
+}
+
+/// The transport url of the addon configured with the given values,
+/// the configuration is a JSON object in the path before the `manifest.json`.
+fn configured_transport_url(
+    transport_url: &Url,
+    config: &[ManifestConfig],
+    values: &HashMap<String, String>,
+) -> Url {
+    let config = config
+        .iter()
+        .filter_map(|config| {
+            values
+                .get(&config.key)
+                .filter(|value| !value.is_empty())
+                .map(|value| (&config.key, value))
+        })
+        .collect::<BTreeMap<_, _>>();
+    let config = serde_json::to_string(&config).expect("Addon config to JSON");
+    transport_url
+        .join(&format!(
+            "{}/manifest.json",
+            utf8_percent_encode(&config, URI_COMPONENT_ENCODE_SET)
+        ))
+        .expect("Configured transport url")
+}
+
+fn validate_values(
+    config: &[ManifestConfig],
+    values: &HashMap<String, String>,
+) -> HashMap<String, ConfigValueError> {
+    config
+        .iter()
+        .filter_map(|config| {
+            let value = values
+                .get(&config.key)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty());
+            let error = match (value, config.r#type) {
+                (None, _) if config.required => Some(ConfigValueError::Required),
+                (None, _) => None,
+                (Some(value), ManifestConfigType::Number) if value.parse::<f64>().is_err() => {
+                    Some(ConfigValueError::InvalidNumber)
+                }
+                (Some(value), ManifestConfigType::Select)
+                    if !config.options.iter().any(|option| option == value) =>
+                {
+                    Some(ConfigValueError::InvalidOption)
+                }
+                (Some(value), ManifestConfigType::Checkbox)
+                    if value != ManifestConfig::CHECKBOX_CHECKED =>
+                {
+                    Some(ConfigValueError::InvalidOption)
+                }
+                _ => None,
+            };
+            error.map(|error| (config.key.to_owned(), error))
+        })
+        .collect()
+}
+
+/// Fills the values of the settings which are not set with their defaults.
+fn default_values_update(
+    values: &mut HashMap<String, String>,
+    addon: &Option<DescriptorLoadable>,
+) -> Effects {
+    let config = match addon {
+        Some(DescriptorLoadable {
+            content: Loadable::Ready(addon),
+            ..
+        }) => &addon.manifest.config,
+        _ => return Effects::none().unchanged(),
+    };
+    let mut next_values = values.to_owned();
+    for config in config {
+        if let Some(default) = &config.default {
+            next_values
+                .entry(config.key.to_owned())
+                .or_insert_with(|| default.to_owned());
+        }
+    }
+    eq_update(values, next_values)
+}
+
+/// Installs the configured addon, replacing the same addon with another configuration.
+fn install_effects(configured_addon: &Descriptor, profile: &Profile) -> Effects {
+    let mut configured_addon = configured_addon.to_owned();
+    // the addon is configured, even if its manifest does not say so
+    configured_addon
+        .manifest
+        .behavior_hints
+        .configuration_required = false;
+    let uninstall_effects = profile
+        .addons
+        .iter()
+        .filter(|addon| {
+            addon.manifest.id == configured_addon.manifest.id
+                && addon.transport_url != configured_addon.transport_url
+        })
+        .fold(Effects::none().unchanged(), |effects, addon| {
+            effects.join(
+                Effects::msg(Msg::Internal(Internal::UninstallAddon(addon.to_owned()))).unchanged(),
+            )
+        });
+    uninstall_effects
+        .join(Effects::msg(Msg::Internal(Internal::InstallAddon(configured_addon))).unchanged())
+}
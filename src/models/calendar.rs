@@ -0,0 +1,280 @@
+use std::collections::HashSet;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::META_RESOURCE_NAME,
+    models::{
+        common::{
+            eq_update, resources_update_with_vector_content, Loadable, ResourceLoadable,
+            ResourcesAction,
+        },
+        ctx::{last_videos_request, Ctx},
+    },
+    runtime::{
+        msg::{Action, ActionLoad, Internal, Msg},
+        Effects, Env, UpdateWithCtx,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest},
+        library::{LibraryBucket, LibraryItem},
+        profile::Profile,
+        resource::{MetaItem, MetaItemPreview, Video},
+    },
+};
+
+/// The period of time which the calendar shows
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum CalendarRange {
+    Month {
+        year: i32,
+        month: u32,
+    },
+    /// An ISO 8601 week, starting on Monday
+    Week {
+        year: i32,
+        week: u32,
+    },
+}
+
+impl CalendarRange {
+    /// The first day of the range and the first day after it.
+    ///
+    /// Returns `None` if the range is not a valid date.
+    pub fn dates(&self) -> Option<(NaiveDate, NaiveDate)> {
+        match self {
+            CalendarRange::Month { year, month } => {
+                let start = NaiveDate::from_ymd_opt(*year, *month, 1)?;
+                let end = match month {
+                    12 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+                    _ => NaiveDate::from_ymd_opt(*year, month + 1, 1)?,
+                };
+                Some((start, end))
+            }
+            CalendarRange::Week { year, week } => {
+                let start = NaiveDate::from_isoywd_opt(*year, *week, Weekday::Mon)?;
+                Some((start, start + Duration::days(7)))
+            }
+        }
+    }
+    /// The range of the same kind which is just before this one
+    pub fn previous(&self) -> Option<CalendarRange> {
+        let (start, _) = self.dates()?;
+        Some(self.with_date(start - Duration::days(1)))
+    }
+    /// The range of the same kind which is just after this one
+    pub fn next(&self) -> Option<CalendarRange> {
+        let (_, end) = self.dates()?;
+        Some(self.with_date(end))
+    }
+    fn with_date(&self, date: NaiveDate) -> CalendarRange {
+        match self {
+            CalendarRange::Month { .. } => CalendarRange::Month {
+                year: date.year(),
+                month: date.month(),
+            },
+            CalendarRange::Week { .. } => CalendarRange::Week {
+                year: date.iso_week().year(),
+                week: date.iso_week().week(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct Selected {
+    pub range: CalendarRange,
+}
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Debug)]
+pub struct Selectable {
+    pub previous: Option<Selected>,
+    pub next: Option<Selected>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub meta_item: MetaItemPreview,
+    pub video: Video,
+    /// The meta request of the item, used for the deep links of the video
+    pub request: ResourceRequest,
+}
+
+#[derive(Clone, PartialEq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Day {
+    pub date: NaiveDate,
+    /// The videos released on this day, ordered by release time
+    pub items: Vec<Item>,
+}
+
+/// The upcoming episodes of the series in the library, by day of release.
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Calendar {
+    pub selected: Option<Selected>,
+    pub selectable: Selectable,
+    /// Every day of the selected range
+    pub days: Vec<Day>,
+    /// The last videos catalogs of the series which have scheduled videos
+    #[serde(skip_serializing)]
+    pub catalogs: Vec<ResourceLoadable<Vec<MetaItem>>>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for Calendar {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::Calendar(selected))) => {
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let selectable_effects = selectable_update(&mut self.selectable, &self.selected);
+                let catalogs_effects =
+                    catalogs_update::<E>(&mut self.catalogs, &ctx.library, &ctx.profile);
+                let days_effects = days_update(&mut self.days, &self.selected, &self.catalogs);
+                selected_effects
+                    .join(selectable_effects)
+                    .join(catalogs_effects)
+                    .join(days_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let selectable_effects = selectable_update(&mut self.selectable, &self.selected);
+                let catalogs_effects = eq_update(&mut self.catalogs, vec![]);
+                let days_effects = days_update(&mut self.days, &self.selected, &self.catalogs);
+                selected_effects
+                    .join(selectable_effects)
+                    .join(catalogs_effects)
+                    .join(days_effects)
+            }
+            Msg::Internal(Internal::LibraryChanged(_)) if self.selected.is_some() => {
+                let catalogs_effects =
+                    catalogs_update::<E>(&mut self.catalogs, &ctx.library, &ctx.profile);
+                let days_effects = days_update(&mut self.days, &self.selected, &self.catalogs);
+                catalogs_effects.join(days_effects)
+            }
+            Msg::Internal(Internal::ResourceRequestResult(request, result)) => {
+                let catalogs_effects = resources_update_with_vector_content::<E, _>(
+                    &mut self.catalogs,
+                    ResourcesAction::ResourceRequestResult { request, result },
+                );
+                let days_effects = if catalogs_effects.has_changed {
+                    days_update(&mut self.days, &self.selected, &self.catalogs)
+                } else {
+                    Effects::none().unchanged()
+                };
+                catalogs_effects.join(days_effects)
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+/// Only the series which have scheduled videos are shown in the calendar
+fn should_show_in_calendar(library_item: &LibraryItem) -> bool {
+    library_item.r#type == "series"
+        && library_item.behavior_hints.has_scheduled_videos
+        && !library_item.removed
+        && !library_item.temp
+}
+
+fn selectable_update(selectable: &mut Selectable, selected: &Option<Selected>) -> Effects {
+    let next_selectable = match selected {
+        Some(selected) => Selectable {
+            previous: selected.range.previous().map(|range| Selected { range }),
+            next: selected.range.next().map(|range| Selected { range }),
+        },
+        _ => Selectable::default(),
+    };
+    eq_update(selectable, next_selectable)
+}
+
+fn catalogs_update<E: Env + 'static>(
+    catalogs: &mut Vec<ResourceLoadable<Vec<MetaItem>>>,
+    library: &LibraryBucket,
+    profile: &Profile,
+) -> Effects {
+    match last_videos_request(
+        library
+            .items
+            .values()
+            .filter(|library_item| should_show_in_calendar(library_item)),
+    ) {
+        Some(request) => resources_update_with_vector_content::<E, _>(
+            catalogs,
+            ResourcesAction::request(&request, &profile.addons),
+        ),
+        _ => eq_update(catalogs, vec![]),
+    }
+}
+
+fn days_update(
+    days: &mut Vec<Day>,
+    selected: &Option<Selected>,
+    catalogs: &[ResourceLoadable<Vec<MetaItem>>],
+) -> Effects {
+    let next_days = match selected
+        .as_ref()
+        .and_then(|selected| selected.range.dates())
+    {
+        Some((start, end)) => {
+            let mut items = catalogs
+                .iter()
+                .filter_map(|catalog| match &catalog.content {
+                    Some(Loadable::Ready(meta_items)) => Some((&catalog.request, meta_items)),
+                    _ => None,
+                })
+                .flat_map(|(catalog_request, meta_items)| {
+                    meta_items.iter().map(move |meta_item| {
+                        let request = ResourceRequest {
+                            base: catalog_request.base.to_owned(),
+                            path: ResourcePath::without_extra(
+                                META_RESOURCE_NAME,
+                                &meta_item.preview.r#type,
+                                &meta_item.preview.id,
+                            ),
+                        };
+                        (meta_item, request)
+                    })
+                })
+                .flat_map(|(meta_item, request)| {
+                    meta_item.videos_iter().filter_map(move |video| {
+                        let released = video.released?;
+                        let date = released.date_naive();
+                        (start <= date && date < end).then(|| {
+                            (
+                                released,
+                                Item {
+                                    meta_item: meta_item.preview.to_owned(),
+                                    video: video.to_owned(),
+                                    request: request.to_owned(),
+                                },
+                            )
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+            // the same video may be returned by more than one addon, the first one is kept
+            let mut seen = HashSet::new();
+            items.retain(|(_, item)| {
+                seen.insert((item.meta_item.id.to_owned(), item.video.id.to_owned()))
+            });
+            items.sort_by_key(|(released, _)| *released);
+            start
+                .iter_days()
+                .take_while(|date| *date < end)
+                .map(|date| Day {
+                    date,
+                    items: items
+                        .iter()
+                        .filter(|(released, _)| released.date_naive() == date)
+                        .map(|(_, item)| item.to_owned())
+                        .collect(),
+                })
+                .collect()
+        }
+        _ => vec![],
+    };
+    eq_update(days, next_days)
+}
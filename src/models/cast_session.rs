@@ -0,0 +1,298 @@
+use chrono::{DateTime, TimeZone, Utc};
+use derivative::Derivative;
+use enclose::enclose;
+use futures::{FutureExt, TryFutureExt};
+use http::request::Request;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::models::common::{eq_update, Loadable};
+use crate::models::ctx::Ctx;
+use crate::models::player::push_to_library;
+use crate::runtime::msg::{Action, ActionCastSession, ActionLoad, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvError, EnvFutureExt, UpdateWithCtx};
+use crate::types::library::{LibraryItem, LibraryItemId};
+use crate::types::streaming_server::CastingStatus;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    /// The id of the [`PlaybackDevice`](crate::models::streaming_server::PlaybackDevice)
+    pub device: String,
+    /// The url which the device will play
+    pub source: Url,
+    /// The library item which progress is tracked during the session
+    pub library_item_id: Option<LibraryItemId>,
+    pub video_id: Option<String>,
+}
+
+/// Playback on a casting device, controlled through the streaming server.
+#[derive(Clone, Derivative, Serialize, Debug)]
+#[derivative(Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CastSession {
+    pub selected: Option<Selected>,
+    pub status: Option<Loadable<CastingStatus, EnvError>>,
+    pub library_item: Option<LibraryItem>,
+    #[serde(skip_serializing)]
+    pub status_time: Option<DateTime<Utc>>,
+    #[serde(skip_serializing)]
+    #[derivative(Default(value = "Utc.timestamp_opt(0, 0).unwrap()"))]
+    pub push_library_item_time: DateTime<Utc>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for CastSession {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        let streaming_server_url = &ctx.profile.settings.streaming_server_url;
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::CastSession(selected))) => {
+                let push_to_library_effects = match &self.library_item {
+                    Some(library_item) => Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(
+                        library_item.to_owned(),
+                    )))
+                    .unchanged(),
+                    _ => Effects::none().unchanged(),
+                };
+                let library_item = selected
+                    .library_item_id
+                    .as_ref()
+                    .and_then(|id| ctx.library.items.get(id))
+                    .cloned();
+                // resume from the last position only if it's the same video
+                let time = library_item
+                    .as_ref()
+                    .filter(|library_item| library_item.state.video_id == selected.video_id)
+                    .map(|library_item| library_item.state.time_offset)
+                    .unwrap_or_default();
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let status_effects = eq_update(&mut self.status, Some(Loadable::Loading));
+                let library_item_effects = eq_update(&mut self.library_item, library_item);
+                self.status_time = None;
+                Effects::one(send_command::<E>(
+                    streaming_server_url,
+                    &selected.device,
+                    CastingCommand {
+                        source: Some(selected.source.to_owned()),
+                        time: Some(time),
+                        ..Default::default()
+                    },
+                ))
+                .unchanged()
+                .join(push_to_library_effects)
+                .join(selected_effects)
+                .join(status_effects)
+                .join(library_item_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let push_to_library_effects = match &self.library_item {
+                    Some(library_item) => Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(
+                        library_item.to_owned(),
+                    )))
+                    .unchanged(),
+                    _ => Effects::none().unchanged(),
+                };
+                let selected_effects = eq_update(&mut self.selected, None);
+                let status_effects = eq_update(&mut self.status, None);
+                let library_item_effects = eq_update(&mut self.library_item, None);
+                self.status_time = None;
+                push_to_library_effects
+                    .join(selected_effects)
+                    .join(status_effects)
+                    .join(library_item_effects)
+            }
+            Msg::Action(Action::CastSession(action)) => match &self.selected {
+                Some(selected) => {
+                    let command = match action {
+                        ActionCastSession::Play => CastingCommand {
+                            paused: Some(false),
+                            ..Default::default()
+                        },
+                        ActionCastSession::Pause => CastingCommand {
+                            paused: Some(true),
+                            ..Default::default()
+                        },
+                        ActionCastSession::Seek { time } => CastingCommand {
+                            time: Some(*time),
+                            ..Default::default()
+                        },
+                        ActionCastSession::SetVolume { volume } => CastingCommand {
+                            volume: Some(volume.clamp(0.0, 1.0)),
+                            ..Default::default()
+                        },
+                        ActionCastSession::Stop => CastingCommand {
+                            stop: Some(true),
+                            ..Default::default()
+                        },
+                        ActionCastSession::GetStatus => {
+                            return Effects::one(get_status::<E>(
+                                streaming_server_url,
+                                &selected.device,
+                            ))
+                            .unchanged()
+                        }
+                    };
+                    let command_effects = Effects::one(send_command::<E>(
+                        streaming_server_url,
+                        &selected.device,
+                        command,
+                    ))
+                    .unchanged();
+                    match action {
+                        ActionCastSession::Stop => {
+                            let push_to_library_effects = match &self.library_item {
+                                Some(library_item) => Effects::msg(Msg::Internal(
+                                    Internal::UpdateLibraryItem(library_item.to_owned()),
+                                ))
+                                .unchanged(),
+                                _ => Effects::none().unchanged(),
+                            };
+                            let selected_effects = eq_update(&mut self.selected, None);
+                            let status_effects = eq_update(&mut self.status, None);
+                            let library_item_effects = eq_update(&mut self.library_item, None);
+                            self.status_time = None;
+                            command_effects
+                                .join(push_to_library_effects)
+                                .join(selected_effects)
+                                .join(status_effects)
+                                .join(library_item_effects)
+                        }
+                        _ => command_effects,
+                    }
+                }
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Internal(Internal::CastSessionCommandResult(device, result)) => {
+                match &self.selected {
+                    Some(selected) if selected.device == *device => match result {
+                        Ok(_) => {
+                            let playing_effects = match self.status {
+                                Some(Loadable::Loading) => {
+                                    Effects::msg(Msg::Event(Event::PlayingOnDevice {
+                                        device: device.to_owned(),
+                                    }))
+                                    .unchanged()
+                                }
+                                _ => Effects::none().unchanged(),
+                            };
+                            Effects::one(get_status::<E>(streaming_server_url, device))
+                                .unchanged()
+                                .join(playing_effects)
+                        }
+                        Err(error) => {
+                            eq_update(&mut self.status, Some(Loadable::Err(error.to_owned())))
+                        }
+                    },
+                    _ => Effects::none().unchanged(),
+                }
+            }
+            Msg::Internal(Internal::CastSessionStatusResult(device, result)) => {
+                match &self.selected {
+                    Some(selected) if selected.device == *device => match result {
+                        Ok(status) => {
+                            let library_item_effects = library_item_update::<E>(
+                                &mut self.library_item,
+                                &mut self.status_time,
+                                &mut self.push_library_item_time,
+                                selected.video_id.as_ref(),
+                                status,
+                            );
+                            let status_effects = eq_update(
+                                &mut self.status,
+                                Some(Loadable::Ready(status.to_owned())),
+                            );
+                            library_item_effects.join(status_effects)
+                        }
+                        Err(error) => {
+                            eq_update(&mut self.status, Some(Loadable::Err(error.to_owned())))
+                        }
+                    },
+                    _ => Effects::none().unchanged(),
+                }
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+/// Updates the progress of the library item the same way the [`Player`](crate::models::player::Player)
+/// does on `TimeChanged`, using the elapsed time between two status polls as the watched time.
+fn library_item_update<E: Env + 'static>(
+    library_item: &mut Option<LibraryItem>,
+    status_time: &mut Option<DateTime<Utc>>,
+    push_library_item_time: &mut DateTime<Utc>,
+    video_id: Option<&String>,
+    status: &CastingStatus,
+) -> Effects {
+    let now = E::now();
+    let last_status_time = status_time.replace(now);
+    let library_item = match library_item {
+        Some(library_item) => library_item,
+        _ => return Effects::none().unchanged(),
+    };
+    // the watched time is the one elapsed between the status polls
+    let max_time_watched = last_status_time
+        .and_then(|last_status_time| {
+            u64::try_from((now - last_status_time).num_milliseconds()).ok()
+        })
+        .unwrap_or(0);
+    library_item.update_progress::<E>(
+        video_id.map(String::as_str),
+        status.time,
+        status.duration,
+        max_time_watched,
+    );
+    push_to_library::<E>(push_library_item_time, library_item).join(Effects::none())
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CastingCommand {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<Url>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    paused: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    volume: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<bool>,
+}
+
+fn send_command<E: Env + 'static>(url: &Url, device: &str, command: CastingCommand) -> Effect {
+    let device = device.to_owned();
+    let endpoint = url
+        .join(&format!("casting/{device}/player"))
+        .expect("url builder failed");
+    let request = Request::post(endpoint.as_str())
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(command)
+        .expect("request builder failed");
+    EffectFuture::Concurrent(
+        E::fetch::<_, serde_json::Value>(request)
+            .map_ok(|_| ())
+            .map(enclose!(() move |result|
+                Msg::Internal(Internal::CastSessionCommandResult(device, result))
+            ))
+            .boxed_env(),
+    )
+    .into()
+}
+
+fn get_status<E: Env + 'static>(url: &Url, device: &str) -> Effect {
+    let device = device.to_owned();
+    let endpoint = url
+        .join(&format!("casting/{device}/player"))
+        .expect("url builder failed");
+    let request = Request::get(endpoint.as_str())
+        .body(())
+        .expect("request builder failed");
+    EffectFuture::Concurrent(
+        E::fetch::<_, CastingStatus>(request)
+            .map(enclose!(() move |result|
+                Msg::Internal(Internal::CastSessionStatusResult(device, result))
+            ))
+            .boxed_env(),
+    )
+    .into()
+}
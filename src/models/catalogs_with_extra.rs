@@ -6,7 +6,7 @@ use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCatalogsWithExtra, ActionLoad, Internal, Msg};
 use crate::runtime::{EffectFuture, Effects, Env, EnvFutureExt, UpdateWithCtx};
 use crate::types::addon::{AggrRequest, ExtraExt, ExtraValue, ResourcePath, ResourceRequest};
-use crate::types::profile::Profile;
+use crate::types::profile::{BoardCatalog, Profile};
 use crate::types::resource::MetaItemPreview;
 use futures::FutureExt;
 use serde::{Deserialize, Serialize};
@@ -173,11 +173,24 @@ fn catalogs_update<E: Env + 'static>(
             let request = AggrRequest::AllCatalogs {
                 extra: &selected.extra,
                 r#type: &selected.r#type,
+                language: Some(&profile.settings.interface_language),
             };
-            request
+            let mut requests = request
                 .plan(&profile.addons)
                 .into_iter()
                 .map(|(_, request)| request)
+                .collect::<Vec<_>>();
+            // the Board catalogs are ordered and hidden by the user's board layout
+            if selected.extra.is_empty() {
+                requests.retain(|request| {
+                    !profile.board_layout.is_hidden(&BoardCatalog::from(request))
+                });
+                profile
+                    .board_layout
+                    .sort_by_catalog(&mut requests, |request| BoardCatalog::from(request));
+            }
+            requests
+                .into_iter()
                 .enumerate()
                 .map(|(index, request)| {
                     catalogs
@@ -10,7 +10,7 @@ use crate::{
     },
     types::{
         library::{LibraryBucket, LibraryItem},
-        notifications::NotificationsBucket,
+        notifications::{NotificationKind, NotificationsBucket},
     },
 };
 
@@ -21,6 +21,8 @@ pub struct Item {
     pub library_item: LibraryItem,
     /// a count of the total notifications we have for this item
     pub notifications: usize,
+    /// the newest season added to the series, e.g. to show that "Season 3 is out"
+    pub season_added: Option<u32>,
 }
 
 /// The continue watching section in the app
@@ -77,13 +79,22 @@ fn library_items_update(
                     library_notification
                         .map(|notifs| notifs.len())
                         .unwrap_or_default(),
+                    library_notification.and_then(|notifs| {
+                        notifs
+                            .values()
+                            .filter_map(|notification| match notification.kind {
+                                NotificationKind::SeasonAdded { season } => Some(season),
+                                _ => None,
+                            })
+                            .max()
+                    }),
                 ))
             } else {
                 None
             }
         })
         // either take the oldest video released date or the modification date of the LibraryItem
-        .sorted_by(|(item_a, _), (item_b, _)| {
+        .sorted_by(|(item_a, ..), (item_b, ..)| {
             let a_time = notifications
                 .items
                 .get(&item_a.id)
@@ -115,9 +126,10 @@ fn library_items_update(
             b_time.cmp(&a_time)
         })
         .take(CATALOG_PREVIEW_SIZE)
-        .map(|(library_item, notifications)| Item {
+        .map(|(library_item, notifications, season_added)| Item {
             library_item: library_item.clone(),
             notifications,
+            season_added,
         })
         .collect::<Vec<_>>();
 
@@ -1,22 +1,28 @@
 use crate::constants::LIBRARY_COLLECTION_NAME;
 use crate::models::common::{DescriptorLoadable, Loadable, ResourceLoadable};
 use crate::models::ctx::{
-    update_events, update_library, update_notifications, update_profile, update_search_history,
-    update_streams, update_trakt_addon, CtxError,
+    update_addons_history, update_events, update_intros, update_language_preferences,
+    update_library, update_notifications, update_play_queue, update_profile, update_search_history,
+    update_streams, update_trakt_addon, update_watch_history, CtxError,
 };
 use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
 use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt, Update};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::api::{
     fetch_api, APIRequest, APIResult, AuthRequest, AuthResponse, CollectionResponse,
     DatastoreCommand, DatastoreRequest, LibraryItemsResponse, SuccessResponse,
 };
 use crate::types::events::{DismissedEventsBucket, Events};
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::play_queue::PlayQueueBucket;
 use crate::types::profile::{Auth, AuthKey, Profile};
 use crate::types::resource::MetaItem;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 
 #[cfg(test)]
 use derivative::Derivative;
@@ -52,6 +58,16 @@ pub struct Ctx {
     #[serde(skip)]
     pub dismissed_events: DismissedEventsBucket,
     #[serde(skip)]
+    pub language_preferences: LanguagePreferencesBucket,
+    #[serde(skip)]
+    pub intros: IntrosBucket,
+    #[serde(skip)]
+    pub play_queue: PlayQueueBucket,
+    #[serde(skip)]
+    pub watch_history: WatchHistoryBucket,
+    #[serde(skip)]
+    pub addons_history: AddonsHistoryBucket,
+    #[serde(skip)]
     #[cfg_attr(test, derivative(Default(value = "CtxStatus::Ready")))]
     pub status: CtxStatus,
     #[serde(skip)]
@@ -63,6 +79,7 @@ pub struct Ctx {
 }
 
 impl Ctx {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         profile: Profile,
         library: LibraryBucket,
@@ -70,6 +87,11 @@ impl Ctx {
         notifications: NotificationsBucket,
         search_history: SearchHistoryBucket,
         dismissed_events: DismissedEventsBucket,
+        language_preferences: LanguagePreferencesBucket,
+        intros: IntrosBucket,
+        play_queue: PlayQueueBucket,
+        watch_history: WatchHistoryBucket,
+        addons_history: AddonsHistoryBucket,
     ) -> Self {
         Self {
             profile,
@@ -77,6 +99,11 @@ impl Ctx {
             streams,
             search_history,
             dismissed_events,
+            language_preferences,
+            intros,
+            play_queue,
+            watch_history,
+            addons_history,
             notifications,
             trakt_addon: None,
             notification_catalogs: vec![],
@@ -102,13 +129,30 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     Some(auth_key) => Effects::one(delete_session::<E>(auth_key)).unchanged(),
                     _ => Effects::none().unchanged(),
                 };
-                let profile_effects =
-                    update_profile::<E>(&mut self.profile, &mut self.streams, &self.status, msg);
+                let profile_effects = update_profile::<E>(
+                    &mut self.profile,
+                    &mut self.streams,
+                    &mut self.addons_history,
+                    &self.status,
+                    msg,
+                );
                 let library_effects =
                     update_library::<E>(&mut self.library, &self.profile, &self.status, msg);
                 let streams_effects = update_streams::<E>(&mut self.streams, &self.status, msg);
                 let search_history_effects =
                     update_search_history::<E>(&mut self.search_history, &self.status, msg);
+                let language_preferences_effects = update_language_preferences::<E>(
+                    &mut self.language_preferences,
+                    &self.status,
+                    msg,
+                );
+                let intros_effects = update_intros::<E>(&mut self.intros, &self.status, msg);
+                let play_queue_effects =
+                    update_play_queue::<E>(&mut self.play_queue, &self.status, msg);
+                let watch_history_effects =
+                    update_watch_history::<E>(&mut self.watch_history, &self.status, msg);
+                let addons_history_effects =
+                    update_addons_history::<E>(&mut self.addons_history, &self.status, msg);
                 let events_effects =
                     update_events::<E>(&mut self.events, &mut self.dismissed_events, msg);
                 let trakt_addon_effects = update_trakt_addon::<E>(
@@ -133,13 +177,23 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     .join(library_effects)
                     .join(streams_effects)
                     .join(search_history_effects)
+                    .join(language_preferences_effects)
+                    .join(intros_effects)
+                    .join(play_queue_effects)
+                    .join(watch_history_effects)
+                    .join(addons_history_effects)
                     .join(events_effects)
                     .join(trakt_addon_effects)
                     .join(notifications_effects)
             }
             Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => {
-                let profile_effects =
-                    update_profile::<E>(&mut self.profile, &mut self.streams, &self.status, msg);
+                let profile_effects = update_profile::<E>(
+                    &mut self.profile,
+                    &mut self.streams,
+                    &mut self.addons_history,
+                    &self.status,
+                    msg,
+                );
                 let library_effects =
                     update_library::<E>(&mut self.library, &self.profile, &self.status, msg);
                 let trakt_addon_effects = update_trakt_addon::<E>(
@@ -159,6 +213,18 @@ impl<E: Env + 'static> Update<E> for Ctx {
                 let streams_effects = update_streams::<E>(&mut self.streams, &self.status, msg);
                 let search_history_effects =
                     update_search_history::<E>(&mut self.search_history, &self.status, msg);
+                let language_preferences_effects = update_language_preferences::<E>(
+                    &mut self.language_preferences,
+                    &self.status,
+                    msg,
+                );
+                let intros_effects = update_intros::<E>(&mut self.intros, &self.status, msg);
+                let play_queue_effects =
+                    update_play_queue::<E>(&mut self.play_queue, &self.status, msg);
+                let watch_history_effects =
+                    update_watch_history::<E>(&mut self.watch_history, &self.status, msg);
+                let addons_history_effects =
+                    update_addons_history::<E>(&mut self.addons_history, &self.status, msg);
                 let events_effects =
                     update_events::<E>(&mut self.events, &mut self.dismissed_events, msg);
                 let ctx_effects = match &self.status {
@@ -228,12 +294,22 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     .join(trakt_addon_effects)
                     .join(notifications_effects)
                     .join(search_history_effects)
+                    .join(language_preferences_effects)
+                    .join(intros_effects)
+                    .join(play_queue_effects)
+                    .join(watch_history_effects)
+                    .join(addons_history_effects)
                     .join(events_effects)
                     .join(ctx_effects)
             }
             _ => {
-                let profile_effects =
-                    update_profile::<E>(&mut self.profile, &mut self.streams, &self.status, msg);
+                let profile_effects = update_profile::<E>(
+                    &mut self.profile,
+                    &mut self.streams,
+                    &mut self.addons_history,
+                    &self.status,
+                    msg,
+                );
                 let library_effects =
                     update_library::<E>(&mut self.library, &self.profile, &self.status, msg);
                 let streams_effects = update_streams::<E>(&mut self.streams, &self.status, msg);
@@ -253,6 +329,18 @@ impl<E: Env + 'static> Update<E> for Ctx {
                 );
                 let search_history_effects =
                     update_search_history::<E>(&mut self.search_history, &self.status, msg);
+                let language_preferences_effects = update_language_preferences::<E>(
+                    &mut self.language_preferences,
+                    &self.status,
+                    msg,
+                );
+                let intros_effects = update_intros::<E>(&mut self.intros, &self.status, msg);
+                let play_queue_effects =
+                    update_play_queue::<E>(&mut self.play_queue, &self.status, msg);
+                let watch_history_effects =
+                    update_watch_history::<E>(&mut self.watch_history, &self.status, msg);
+                let addons_history_effects =
+                    update_addons_history::<E>(&mut self.addons_history, &self.status, msg);
                 let events_effects =
                     update_events::<E>(&mut self.events, &mut self.dismissed_events, msg);
                 profile_effects
@@ -261,6 +349,11 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     .join(trakt_addon_effects)
                     .join(notifications_effects)
                     .join(search_history_effects)
+                    .join(language_preferences_effects)
+                    .join(intros_effects)
+                    .join(play_queue_effects)
+                    .join(watch_history_effects)
+                    .join(addons_history_effects)
                     .join(events_effects)
             }
         }
@@ -300,7 +393,6 @@ fn authenticate<E: Env + 'static>(auth_request: &AuthRequest) -> Effect {
                         APIResult::Ok(result) => Ok(result),
                         APIResult::Err(error) => Err(CtxError::from(error)),
                     })
-                    .map(|CollectionResponse { addons, .. }| addons)
             };
 
             let datastore_library_fut = async {
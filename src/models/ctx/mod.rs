@@ -1,12 +1,25 @@
+mod update_addons_history;
+use update_addons_history::*;
+
 mod update_events;
 use update_events::*;
 
+mod update_intros;
+use update_intros::*;
+
+mod update_language_preferences;
+use update_language_preferences::*;
+
 mod update_library;
 use update_library::*;
 
 mod update_notifications;
+pub(crate) use update_notifications::last_videos_request;
 use update_notifications::*;
 
+mod update_play_queue;
+use update_play_queue::*;
+
 mod update_profile;
 use update_profile::*;
 
@@ -19,6 +32,9 @@ use update_search_history::*;
 mod update_trakt_addon;
 use update_trakt_addon::*;
 
+mod update_watch_history;
+use update_watch_history::*;
+
 mod error;
 pub use error::*;
 
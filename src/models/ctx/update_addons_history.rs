@@ -0,0 +1,66 @@
+use enclose::enclose;
+use futures::FutureExt;
+
+use crate::constants::ADDONS_HISTORY_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
+use crate::types::addons_history::AddonsHistoryBucket;
+
+/// The snapshots themselves are recorded by [`update_profile`] whenever it changes the addons.
+///
+/// [`update_profile`]: super::update_profile
+pub fn update_addons_history<E: Env + 'static>(
+    addons_history: &mut AddonsHistoryBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            let next_addons_history = AddonsHistoryBucket::default();
+            if *addons_history != next_addons_history {
+                *addons_history = next_addons_history;
+                Effects::msg(Msg::Internal(Internal::AddonsHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::AddonsHistoryChanged) => {
+            Effects::one(push_addons_history_to_storage::<E>(addons_history)).unchanged()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
+                if loading_auth_request == auth_request =>
+            {
+                let next_addons_history = AddonsHistoryBucket::new(Some(auth.user.id.to_owned()));
+                if *addons_history != next_addons_history {
+                    *addons_history = next_addons_history;
+                    Effects::msg(Msg::Internal(Internal::AddonsHistoryChanged))
+                } else {
+                    Effects::none().unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_addons_history_to_storage<E: Env + 'static>(
+    addons_history: &AddonsHistoryBucket,
+) -> Effect {
+    EffectFuture::Sequential(
+        E::set_storage(ADDONS_HISTORY_STORAGE_KEY, Some(&addons_history))
+            .map(
+                enclose!((addons_history.uid => uid) move |result| match result {
+                    Ok(_) => Msg::Event(Event::AddonsHistoryPushedToStorage { uid }),
+                    Err(error) => Msg::Event(Event::Error {
+                        error: CtxError::from(error),
+                        source: Box::new(Event::AddonsHistoryPushedToStorage { uid }),
+                    })
+                }),
+            )
+            .boxed_env(),
+    )
+    .into()
+}
@@ -0,0 +1,73 @@
+use enclose::enclose;
+use futures::FutureExt;
+
+use crate::constants::INTROS_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
+use crate::types::intros::IntrosBucket;
+
+pub fn update_intros<E: Env + 'static>(
+    intros: &mut IntrosBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            let next_intros = IntrosBucket::default();
+            if *intros != next_intros {
+                *intros = next_intros;
+                Effects::msg(Msg::Internal(Internal::IntrosChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::SeekHistoryRecorded {
+            meta_id,
+            series_info,
+            duration,
+            seek_history,
+        }) => {
+            let mut series_intros = intros.items.get(meta_id).cloned().unwrap_or_default();
+            if series_intros.learn(series_info, *duration, seek_history, E::now()) {
+                intros.items.insert(meta_id.to_owned(), series_intros);
+                Effects::msg(Msg::Internal(Internal::IntrosChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::IntrosChanged) => {
+            Effects::one(push_intros_to_storage::<E>(intros)).unchanged()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
+                if loading_auth_request == auth_request =>
+            {
+                let next_intros = IntrosBucket::new(Some(auth.user.id.to_owned()));
+                if *intros != next_intros {
+                    *intros = next_intros;
+                    Effects::msg(Msg::Internal(Internal::IntrosChanged))
+                } else {
+                    Effects::none().unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_intros_to_storage<E: Env + 'static>(intros: &IntrosBucket) -> Effect {
+    EffectFuture::Sequential(
+        E::set_storage(INTROS_STORAGE_KEY, Some(&intros))
+            .map(enclose!((intros.uid => uid) move |result| match result {
+                Ok(_) => Msg::Event(Event::IntrosPushedToStorage { uid }),
+                Err(error) => Msg::Event(Event::Error {
+                    error: CtxError::from(error),
+                    source: Box::new(Event::IntrosPushedToStorage { uid }),
+                })
+            }))
+            .boxed_env(),
+    )
+    .into()
+}
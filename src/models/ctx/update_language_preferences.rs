@@ -0,0 +1,88 @@
+use enclose::enclose;
+use futures::FutureExt;
+
+use crate::constants::LANGUAGE_PREFERENCES_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
+use crate::types::language_preferences::{LanguagePreference, LanguagePreferencesBucket};
+
+pub fn update_language_preferences<E: Env + 'static>(
+    language_preferences: &mut LanguagePreferencesBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            let next_language_preferences = LanguagePreferencesBucket::default();
+            if *language_preferences != next_language_preferences {
+                *language_preferences = next_language_preferences;
+                Effects::msg(Msg::Internal(Internal::LanguagePreferencesChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::StreamStateChanged {
+            state,
+            meta_request: Some(meta_request),
+            ..
+        }) => {
+            let meta_id = &meta_request.path.id;
+            let previous = language_preferences.items.get(meta_id);
+            let next_preference = LanguagePreference::new(state, previous, E::now());
+            match previous {
+                Some(previous) if previous.is_same_preference(&next_preference) => {
+                    Effects::none().unchanged()
+                }
+                _ => {
+                    language_preferences
+                        .items
+                        .insert(meta_id.to_owned(), next_preference);
+                    Effects::msg(Msg::Internal(Internal::LanguagePreferencesChanged))
+                }
+            }
+        }
+        Msg::Internal(Internal::LanguagePreferencesChanged) => Effects::one(
+            push_language_preferences_to_storage::<E>(language_preferences),
+        )
+        .unchanged(),
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
+                if loading_auth_request == auth_request =>
+            {
+                let next_language_preferences =
+                    LanguagePreferencesBucket::new(Some(auth.user.id.to_owned()));
+                if *language_preferences != next_language_preferences {
+                    *language_preferences = next_language_preferences;
+                    Effects::msg(Msg::Internal(Internal::LanguagePreferencesChanged))
+                } else {
+                    Effects::none().unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_language_preferences_to_storage<E: Env + 'static>(
+    language_preferences: &LanguagePreferencesBucket,
+) -> Effect {
+    EffectFuture::Sequential(
+        E::set_storage(
+            LANGUAGE_PREFERENCES_STORAGE_KEY,
+            Some(&language_preferences),
+        )
+        .map(
+            enclose!((language_preferences.uid => uid) move |result| match result {
+                Ok(_) => Msg::Event(Event::LanguagePreferencesPushedToStorage { uid }),
+                Err(error) => Msg::Event(Event::Error {
+                    error: CtxError::from(error),
+                    source: Box::new(Event::LanguagePreferencesPushedToStorage { uid }),
+                })
+            }),
+        )
+        .boxed_env(),
+    )
+    .into()
+}
@@ -1,17 +1,23 @@
-use std::collections::{hash_map::Entry, HashMap};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    slice,
+};
 
 use chrono::{DateTime, Duration, Utc};
 use futures::FutureExt;
 use lazysort::SortedBy;
-use once_cell::sync::Lazy;
 use tracing::trace;
 
 use crate::{
-    constants::{LAST_VIDEOS_IDS_EXTRA_PROP, NOTIFICATIONS_STORAGE_KEY, NOTIFICATION_ITEMS_COUNT},
+    constants::{
+        LAST_VIDEOS_IDS_EXTRA_PROP, NOTIFICATIONS_BATCH_SIZE, NOTIFICATIONS_CONCURRENT_REQUESTS,
+        NOTIFICATIONS_PULL_INTERVAL_MINUTES, NOTIFICATIONS_PULL_LIMIT, NOTIFICATIONS_STORAGE_KEY,
+        NOTIFICATION_ITEMS_COUNT,
+    },
     models::{
         common::{
-            eq_update, resources_update_with_vector_content, Loadable, ResourceLoadable,
-            ResourcesAction,
+            eq_update, resource_update_with_vector_content, resources_update_with_vector_content,
+            Loadable, ResourceAction, ResourceLoadable, ResourcesAction,
         },
         ctx::{CtxError, CtxStatus},
     },
@@ -20,16 +26,14 @@ use crate::{
         Effect, EffectFuture, Effects, Env, EnvFutureExt,
     },
     types::{
-        addon::{AggrRequest, ExtraType},
-        library::LibraryBucket,
-        notifications::{NotificationItem, NotificationsBucket},
-        profile::Profile,
+        addon::{AggrRequest, Descriptor, ExtraType, ResourceRequest},
+        library::{LibraryBucket, LibraryItem},
+        notifications::{KnownSeasons, NotificationItem, NotificationKind, NotificationsBucket},
+        profile::{NotificationRules, Profile},
         resource::{MetaItem, MetaItemId, VideoId},
     },
 };
 
-static REQUEST_LAST_VIDEOS_EVERY: Lazy<Duration> = Lazy::new(|| Duration::hours(6));
-
 pub fn update_notifications<E: Env + 'static>(
     notifications: &mut NotificationsBucket,
     notification_catalogs: &mut Vec<ResourceLoadable<Vec<MetaItem>>>,
@@ -43,78 +47,82 @@ pub fn update_notifications<E: Env + 'static>(
             Effects::msg(Msg::Internal(Internal::PullNotifications)).unchanged()
         }
         Msg::Internal(Internal::PullNotifications) => {
-            let (reason, should_make_request) = match notifications.last_updated {
-                Some(last_updated) if last_updated + *REQUEST_LAST_VIDEOS_EVERY <= E::now() => (
-                    format!(
-                        "`true` since {last_updated} + {hours} hours <= {now}",
-                        hours = REQUEST_LAST_VIDEOS_EVERY.num_hours(),
-                        now = E::now()
-                    ),
-                    true,
-                ),
-                None => ("`true` since last updated is `None`".to_string(), true),
-                Some(last_updated) => (
-                    format!(
-                        "`false` since {last_updated} + {hours} hours > {now}",
-                        hours = REQUEST_LAST_VIDEOS_EVERY.num_hours(),
-                        now = E::now()
-                    ),
-                    false,
-                ),
+            let prune_effects = if prune_notifications(notifications, library) {
+                Effects::msg(Msg::Internal(Internal::NotificationsChanged)).unchanged()
+            } else {
+                Effects::none().unchanged()
             };
-
-            tracing::debug!(
-                name = "Notifications",
-                reason = reason,
-                last_updated = notifications.last_updated.as_ref().map(ToString::to_string),
-                hours = REQUEST_LAST_VIDEOS_EVERY.num_hours(),
-                "Should last-videos addon resource be called? {should_make_request}"
-            );
-
-            let sorted_library_items_id_types = library
+            let check_ttl =
+                Duration::hours(profile.settings.notification_rules.check_ttl_hours.into());
+            // the checks are spread over the pulls, so that the addons are not flooded with requests
+            let pull_limit = match notifications.last_updated {
+                Some(last_updated)
+                    if last_updated + Duration::minutes(NOTIFICATIONS_PULL_INTERVAL_MINUTES)
+                        > E::now() =>
+                {
+                    0
+                }
+                _ => NOTIFICATIONS_PULL_LIMIT,
+            };
+            // the most recently watched series are checked first
+            let id_types = library
                 .items
                 .values()
                 .filter(|library_item| library_item.should_pull_notifications())
-                .sorted_by(|a, b| b.mtime.cmp(&a.mtime))
+                .filter(|library_item| {
+                    notifications
+                        .last_checked
+                        .get(&library_item.id)
+                        .map_or(true, |last_checked| *last_checked + check_ttl <= E::now())
+                })
+                .sorted_by(|a, b| {
+                    b.state
+                        .last_watched
+                        .cmp(&a.state.last_watched)
+                        .then(b.mtime.cmp(&a.mtime))
+                })
                 .map(|library_item| (library_item.id.to_owned(), library_item.r#type.to_owned()))
+                .take(pull_limit)
                 .collect::<Vec<_>>();
 
-            let notifications_catalog_resource_effects =
-                if !sorted_library_items_id_types.is_empty() && should_make_request {
-                    trace!(
-                        "Sorted by `mtime` LibraryItem id and type: {:?}",
-                        sorted_library_items_id_types
-                    );
-                    let catalog_resource_effects = resources_update_with_vector_content::<E, _>(
-                        notification_catalogs,
-                        // force the making of a requests every time PullNotifications is called.
-                        ResourcesAction::force_request(
-                            &AggrRequest::CatalogsFiltered(vec![ExtraType::Ids {
-                                extra_name: LAST_VIDEOS_IDS_EXTRA_PROP.name.to_owned(),
-                                id_types: sorted_library_items_id_types,
-                                limit: Some(NOTIFICATION_ITEMS_COUNT),
-                            }]),
-                            &profile.addons,
-                        ),
-                    );
+            tracing::debug!(
+                name = "Notifications",
+                last_updated = notifications.last_updated.as_ref().map(ToString::to_string),
+                hours = check_ttl.num_hours(),
+                "Library items with a last check older than the TTL: {}",
+                id_types.len()
+            );
 
-                    notifications.last_updated = Some(E::now());
+            let notifications_catalog_resource_effects = if !id_types.is_empty() {
+                // the requests are queued and sent a few at a time as the previous ones finish.
+                let next_notification_catalogs = last_videos_batches(&id_types, &profile.addons)
+                    .into_iter()
+                    .map(|request| ResourceLoadable {
+                        request,
+                        content: None,
+                    })
+                    .collect::<Vec<_>>();
+                notifications.last_updated = Some(E::now());
 
-                    catalog_resource_effects
-                } else {
-                    Effects::none().unchanged()
-                };
+                eq_update(notification_catalogs, next_notification_catalogs)
+                    .join(send_queued_requests::<E>(notification_catalogs))
+            } else {
+                Effects::none().unchanged()
+            };
 
             // first update the notification items
             let notification_items_effects = update_notification_items::<E>(
                 &mut notifications.items,
+                &mut notifications.known_seasons,
                 notification_catalogs,
                 library,
+                &profile.settings.notification_rules,
             );
 
-            // because notifications are getting loaded by forcing new requests
+            // because notifications are getting loaded by new requests
             // we do not trigger a `NotificationsChanged` as the addons should return results first.
-            notifications_catalog_resource_effects
+            prune_effects
+                .join(notifications_catalog_resource_effects)
                 .join(notification_items_effects)
                 .unchanged()
         }
@@ -162,22 +170,46 @@ pub fn update_notifications<E: Env + 'static>(
                 notification_catalogs,
                 ResourcesAction::ResourceRequestResult { request, result },
             );
-            let notification_items_effects = if notification_catalogs_effects.has_changed {
-                update_notification_items::<E>(
-                    &mut notifications.items,
-                    notification_catalogs,
-                    library,
-                )
-            } else {
-                Effects::none().unchanged()
-            };
+            let (last_checked_changed, next_request_effects, notification_items_effects) =
+                if notification_catalogs_effects.has_changed {
+                    // failed requests are retried on the next pull
+                    let last_checked_changed = result.is_ok()
+                        && request
+                            .path
+                            .get_extra_first_value(&LAST_VIDEOS_IDS_EXTRA_PROP.name)
+                            .map(|ids| {
+                                for id in ids.split(',') {
+                                    notifications.last_checked.insert(id.to_owned(), E::now());
+                                }
+                            })
+                            .is_some();
+                    (
+                        last_checked_changed,
+                        send_queued_requests::<E>(notification_catalogs),
+                        update_notification_items::<E>(
+                            &mut notifications.items,
+                            &mut notifications.known_seasons,
+                            notification_catalogs,
+                            library,
+                            &profile.settings.notification_rules,
+                        ),
+                    )
+                } else {
+                    (
+                        false,
+                        Effects::none().unchanged(),
+                        Effects::none().unchanged(),
+                    )
+                };
 
-            let notifications_effects = if notification_items_effects.has_changed {
-                Effects::msg(Msg::Internal(Internal::NotificationsChanged))
-            } else {
-                Effects::none().unchanged()
-            };
+            let notifications_effects =
+                if last_checked_changed || notification_items_effects.has_changed {
+                    Effects::msg(Msg::Internal(Internal::NotificationsChanged))
+                } else {
+                    Effects::none().unchanged()
+                };
             notification_catalogs_effects
+                .join(next_request_effects)
                 .join(notification_items_effects)
                 .join(notifications_effects)
         }
@@ -191,10 +223,119 @@ pub fn update_notifications<E: Env + 'static>(
     }
 }
 
+/// Removes the check times and the known seasons of the items which are not in the library.
+///
+/// Returns whether anything was removed.
+fn prune_notifications(notifications: &mut NotificationsBucket, library: &LibraryBucket) -> bool {
+    let len = notifications.last_checked.len() + notifications.known_seasons.len();
+    notifications
+        .last_checked
+        .retain(|id, _| library.items.contains_key(id));
+    notifications
+        .known_seasons
+        .retain(|id, _| library.items.contains_key(id));
+    notifications.last_checked.len() + notifications.known_seasons.len() != len
+}
+
+/// The request for the last videos of the given library items, the most recently modified first.
+///
+/// Returns `None` if there are no library items.
+pub(crate) fn last_videos_request<'a>(
+    library_items: impl Iterator<Item = &'a LibraryItem>,
+) -> Option<AggrRequest<'static>> {
+    let sorted_library_items_id_types = library_items
+        .sorted_by(|a, b| b.mtime.cmp(&a.mtime))
+        .map(|library_item| (library_item.id.to_owned(), library_item.r#type.to_owned()))
+        .collect::<Vec<_>>();
+    if sorted_library_items_id_types.is_empty() {
+        return None;
+    }
+
+    trace!(
+        "Sorted by `mtime` LibraryItem id and type: {:?}",
+        sorted_library_items_id_types
+    );
+    Some(AggrRequest::CatalogsFiltered(vec![ExtraType::Ids {
+        extra_name: LAST_VIDEOS_IDS_EXTRA_PROP.name.to_owned(),
+        id_types: sorted_library_items_id_types,
+        limit: Some(NOTIFICATION_ITEMS_COUNT),
+    }]))
+}
+
+/// Splits the ids into batches of requests to each addon which supports them.
+///
+/// The first batches of all addons come first, so that the ids at the start
+/// are checked before the rest.
+fn last_videos_batches(
+    id_types: &[(String, String)],
+    addons: &[Descriptor],
+) -> Vec<ResourceRequest> {
+    addons
+        .iter()
+        .flat_map(|addon| {
+            let mut remaining_id_types = id_types.to_vec();
+            let mut batches = vec![];
+            for batch_index in 0.. {
+                let requests = AggrRequest::CatalogsFiltered(vec![ExtraType::Ids {
+                    extra_name: LAST_VIDEOS_IDS_EXTRA_PROP.name.to_owned(),
+                    id_types: remaining_id_types.to_owned(),
+                    limit: Some(NOTIFICATIONS_BATCH_SIZE),
+                }])
+                .plan(slice::from_ref(addon))
+                .into_iter()
+                .map(|(_, request)| request)
+                .collect::<Vec<_>>();
+                if requests.is_empty() {
+                    break;
+                }
+
+                let batch_ids = requests
+                    .iter()
+                    .filter_map(|request| {
+                        request
+                            .path
+                            .get_extra_first_value(&LAST_VIDEOS_IDS_EXTRA_PROP.name)
+                    })
+                    .flat_map(|ids| ids.split(','))
+                    .collect::<HashSet<_>>();
+                remaining_id_types.retain(|(id, _)| !batch_ids.contains(id.as_str()));
+                batches.extend(requests.into_iter().map(|request| (batch_index, request)));
+            }
+            batches
+        })
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, request)| request)
+        .collect()
+}
+
+/// Sends the queued requests of the notification catalogs,
+/// as long as there are less than [`NOTIFICATIONS_CONCURRENT_REQUESTS`] loading.
+fn send_queued_requests<E: Env + 'static>(
+    notification_catalogs: &mut [ResourceLoadable<Vec<MetaItem>>],
+) -> Effects {
+    let loading = notification_catalogs
+        .iter()
+        .filter(|catalog| matches!(catalog.content, Some(Loadable::Loading)))
+        .count();
+    notification_catalogs
+        .iter_mut()
+        .filter(|catalog| catalog.content.is_none())
+        .take(NOTIFICATIONS_CONCURRENT_REQUESTS.saturating_sub(loading))
+        .fold(Effects::none().unchanged(), |effects, catalog| {
+            let request = catalog.request.to_owned();
+            effects.join(resource_update_with_vector_content::<E, _>(
+                catalog,
+                ResourceAction::ResourceRequested { request: &request },
+            ))
+        })
+}
+
 fn update_notification_items<E: Env + 'static>(
     notification_items: &mut HashMap<MetaItemId, HashMap<VideoId, NotificationItem>>,
+    known_seasons: &mut HashMap<MetaItemId, KnownSeasons>,
     notification_catalogs: &[ResourceLoadable<Vec<MetaItem>>],
     library: &LibraryBucket,
+    rules: &NotificationRules,
 ) -> Effects {
     let selected_catalogs = notification_catalogs
         .iter()
@@ -207,6 +348,18 @@ fn update_notification_items<E: Env + 'static>(
         })
         .collect::<Vec<_>>();
 
+    let is_recent_video = |video_released: &DateTime<Utc>| match rules.max_video_age_days {
+        Some(max_video_age_days) => {
+            *video_released >= E::now() - Duration::days(max_video_age_days.into())
+        }
+        None => true,
+    };
+    // during the quiet hours only the existing notifications are kept
+    let is_quiet = rules
+        .quiet_hours
+        .as_ref()
+        .is_some_and(|quiet_hours| quiet_hours.contains(&E::now()));
+
     // shared function to decide if a given video should be included in notifications
     // or excluded
     // returns the video_released DateTime extracted from the arguments if it should be retained
@@ -217,7 +370,8 @@ fn update_notification_items<E: Env + 'static>(
             (Some(last_watched), Some(video_released)) => {
                 if last_watched < video_released &&
                         // exclude future videos (i.e. that will air in the future)
-                        video_released <= &E::now()
+                        video_released <= &E::now() &&
+                        is_recent_video(video_released)
                 {
                     Some(*video_released)
                 } else {
@@ -225,11 +379,14 @@ fn update_notification_items<E: Env + 'static>(
                 }
             }
             // if you've never watched an episode, then we want to include new videos
-            (None, Some(video_released)) => Some(*video_released),
+            (None, Some(video_released)) if is_recent_video(video_released) => {
+                Some(*video_released)
+            }
             _ => None,
         }
     };
 
+    let mut next_known_seasons = known_seasons.to_owned();
     let next_notification_items =
         library
             .items
@@ -240,6 +397,17 @@ fn update_notification_items<E: Env + 'static>(
                     return map;
                 }
 
+                // Exit early if the series has not been watched recently enough
+                if let Some(watched_within_days) = rules.watched_within_days {
+                    let watched_within =
+                        library_item.state.last_watched.is_some_and(|last_watched| {
+                            last_watched >= E::now() - Duration::days(watched_within_days.into())
+                        });
+                    if !watched_within {
+                        return map;
+                    }
+                }
+
                 // find the first occurrence of the meta item inside the catalogs
                 let meta_item = match selected_catalogs.iter().find_map(|catalog| {
                     catalog
@@ -285,9 +453,109 @@ fn update_notification_items<E: Env + 'static>(
                     }
                 };
 
+                // the seasons and specials added since the videos were last loaded,
+                // nothing is added the first time they are loaded
+                let mut released_seasons = KnownSeasons::new(&meta_item.videos, E::now());
+                let added_notifs = match next_known_seasons.get(meta_id) {
+                    Some(previous) => {
+                        let (seasons, specials) = released_seasons.added_since(previous);
+                        let season_notifs = seasons.into_iter().filter_map(|season| {
+                            meta_item
+                                .videos
+                                .iter()
+                                .filter(|video| {
+                                    video
+                                        .series_info
+                                        .as_ref()
+                                        .is_some_and(|series_info| series_info.season == season)
+                                })
+                                .filter_map(|video| Some((video, video.released?)))
+                                .min_by_key(|(_, video_released)| *video_released)
+                                .map(|(video, _)| (video, NotificationKind::SeasonAdded { season }))
+                        });
+                        let special_notifs = specials.into_iter().filter_map(|video_id| {
+                            meta_item
+                                .videos
+                                .iter()
+                                .find(|video| &video.id == video_id)
+                                .map(|video| (video, NotificationKind::SpecialAdded))
+                        });
+                        season_notifs
+                            .chain(special_notifs)
+                            .filter_map(|(video, kind)| {
+                                let video_released = should_retail_video_released(
+                                    library_item.state.last_watched.as_ref(),
+                                    video.released.as_ref(),
+                                )?;
+                                Some(NotificationItem {
+                                    meta_id: meta_id.to_owned(),
+                                    video_id: video.id.to_owned(),
+                                    video_released,
+                                    kind,
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    None => vec![],
+                };
+
+                // Exit early if the videos released before the new ones are not all watched
+                if rules.only_caught_up {
+                    let watched = library_item.state.watched_bitfield(&meta_item.videos);
+                    let caught_up = meta_item
+                        .videos_iter()
+                        .filter(|video| {
+                            matches!(
+                                (&library_item.state.last_watched, &video.released),
+                                (Some(last_watched), Some(video_released))
+                                    if video_released <= last_watched
+                            )
+                        })
+                        .all(|video| watched.get_video(&video.id));
+                    if !caught_up {
+                        // the added seasons are detected again once the user has caught up
+                        for notif_item in &added_notifs {
+                            released_seasons.forget(notif_item);
+                        }
+                        next_known_seasons.insert(meta_id.to_owned(), released_seasons);
+                        return map;
+                    }
+                }
+
+                let existing_notifs = notification_items.get(meta_id);
                 let mut meta_notifs: &mut HashMap<_, _> =
                     map.entry(meta_id.to_owned()).or_default();
 
+                // the season and special notifications take precedence
+                // over the ones of the same videos
+                let existing_added_notifs = existing_notifs
+                    .into_iter()
+                    .flat_map(|existing_notifs| existing_notifs.values())
+                    .filter(|notif_item| notif_item.kind != NotificationKind::Video)
+                    .filter(|notif_item| {
+                        should_retail_video_released(
+                            library_item.state.last_watched.as_ref(),
+                            Some(&notif_item.video_released),
+                        )
+                        .is_some()
+                    })
+                    .cloned();
+                for notif_item in existing_added_notifs.chain(added_notifs) {
+                    let is_existing = existing_notifs.is_some_and(|existing_notifs| {
+                        existing_notifs.contains_key(&notif_item.video_id)
+                    });
+                    if is_quiet && !is_existing {
+                        // the added seasons are detected again once the quiet hours end
+                        released_seasons.forget(&notif_item);
+                        continue;
+                    }
+
+                    meta_notifs
+                        .entry(notif_item.video_id.to_owned())
+                        .or_insert(notif_item);
+                }
+                next_known_seasons.insert(meta_id.to_owned(), released_seasons);
+
                 // meta items videos
                 meta_item
                     .videos_iter()
@@ -316,13 +584,22 @@ fn update_notification_items<E: Env + 'static>(
                         |meta_notifs, (meta_id, video_id, video_released)| {
                             let notif_entry = meta_notifs.entry(video_id.to_owned());
 
+                            let is_existing = existing_notifs.is_some_and(|existing_notifs| {
+                                existing_notifs.contains_key(video_id)
+                            });
+
                             // for now just skip same videos that already exist
                             // leave the first one found in the Vec.
                             if let Entry::Vacant(new) = notif_entry {
+                                if is_quiet && !is_existing {
+                                    return meta_notifs;
+                                }
+
                                 let notification = NotificationItem {
                                     meta_id: meta_id.to_owned(),
                                     video_id: video_id.to_owned(),
                                     video_released,
+                                    kind: NotificationKind::Video,
                                 };
 
                                 new.insert(notification);
@@ -341,6 +618,7 @@ fn update_notification_items<E: Env + 'static>(
             });
 
     eq_update(notification_items, next_notification_items)
+        .join(eq_update(known_seasons, next_known_seasons))
 }
 
 fn push_notifications_to_storage<E: Env + 'static>(notifications: &NotificationsBucket) -> Effect {
@@ -0,0 +1,101 @@
+use enclose::enclose;
+use futures::FutureExt;
+
+use crate::constants::PLAY_QUEUE_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, ActionLoad, CtxAuthResponse, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
+use crate::types::play_queue::PlayQueueBucket;
+
+pub fn update_play_queue<E: Env + 'static>(
+    play_queue: &mut PlayQueueBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            let next_play_queue = PlayQueueBucket::default();
+            if *play_queue != next_play_queue {
+                *play_queue = next_play_queue;
+                Effects::msg(Msg::Internal(Internal::PlayQueueChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::AddToPlayQueue(item))) => {
+            if play_queue.push(item.to_owned()) {
+                Effects::msg(Msg::Internal(Internal::PlayQueueChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::RemoveFromPlayQueue { meta_id, video_id })) => {
+            if play_queue.remove(meta_id, video_id) {
+                Effects::msg(Msg::Internal(Internal::PlayQueueChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::MovePlayQueueItem { from, to })) => {
+            if play_queue.move_item(*from, *to) {
+                Effects::msg(Msg::Internal(Internal::PlayQueueChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::ClearPlayQueue)) => {
+            if !play_queue.items.is_empty() {
+                play_queue.items.clear();
+                Effects::msg(Msg::Internal(Internal::PlayQueueChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        // A queued video is dequeued once the player starts playing it
+        Msg::Action(Action::Load(ActionLoad::Player(selected))) => {
+            match (&selected.meta_request, &selected.stream_request) {
+                (Some(meta_request), Some(stream_request))
+                    if play_queue.remove(&meta_request.path.id, &stream_request.path.id) =>
+                {
+                    Effects::msg(Msg::Internal(Internal::PlayQueueChanged))
+                }
+                _ => Effects::none().unchanged(),
+            }
+        }
+        Msg::Internal(Internal::PlayQueueChanged) => {
+            Effects::one(push_play_queue_to_storage::<E>(play_queue)).unchanged()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
+                if loading_auth_request == auth_request =>
+            {
+                let next_play_queue = PlayQueueBucket::new(Some(auth.user.id.to_owned()));
+                if *play_queue != next_play_queue {
+                    *play_queue = next_play_queue;
+                    Effects::msg(Msg::Internal(Internal::PlayQueueChanged))
+                } else {
+                    Effects::none().unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_play_queue_to_storage<E: Env + 'static>(play_queue: &PlayQueueBucket) -> Effect {
+    EffectFuture::Sequential(
+        E::set_storage(PLAY_QUEUE_STORAGE_KEY, Some(&play_queue))
+            .map(
+                enclose!((play_queue.uid => uid) move |result| match result {
+                    Ok(_) => Msg::Event(Event::PlayQueuePushedToStorage { uid }),
+                    Err(error) => Msg::Event(Event::Error {
+                        error: CtxError::from(error),
+                        source: Box::new(Event::PlayQueuePushedToStorage { uid }),
+                    })
+                }),
+            )
+            .boxed_env(),
+    )
+    .into()
+}
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use chrono::{DateTime, Utc};
 use enclose::enclose;
 use futures::{future, FutureExt, TryFutureExt};
 
@@ -7,16 +8,18 @@ use crate::constants::{OFFICIAL_ADDONS, PROFILE_STORAGE_KEY};
 use crate::models::ctx::{CtxError, CtxStatus, OtherError};
 use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
 use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
-use crate::types::addon::Descriptor;
+use crate::types::addon::{AggrRequest, Descriptor};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::api::{
     fetch_api, APIError, APIRequest, APIResult, CollectionResponse, SuccessResponse,
 };
-use crate::types::profile::{Auth, AuthKey, Profile, Settings, User};
+use crate::types::profile::{Auth, AuthKey, BoardCatalog, BoardLayout, Profile, Settings, User};
 use crate::types::streams::StreamsBucket;
 
 pub fn update_profile<E: Env + 'static>(
     profile: &mut Profile,
     streams: &mut StreamsBucket,
+    addons_history: &mut AddonsHistoryBucket,
     status: &CtxStatus,
     msg: &Msg,
 ) -> Effects {
@@ -49,10 +52,12 @@ pub fn update_profile<E: Env + 'static>(
             .unchanged(),
         },
         Msg::Action(Action::Ctx(ActionCtx::PushAddonsToAPI)) => match profile.auth_key() {
-            Some(auth_key) => {
-                Effects::one(push_addons_to_api::<E>(profile.addons.to_owned(), auth_key))
-                    .unchanged()
-            }
+            Some(auth_key) => Effects::one(push_addons_to_api::<E>(
+                profile.addons.to_owned(),
+                profile.board_layout.to_owned(),
+                auth_key,
+            ))
+            .unchanged(),
             _ => Effects::msg(Msg::Event(Event::Error {
                 error: CtxError::from(OtherError::UserNotLoggedIn),
                 source: Box::new(Event::AddonsPushedToAPI {
@@ -105,8 +110,14 @@ pub fn update_profile<E: Env + 'static>(
                     .chain(removed_transport_urls)
                     .collect();
                 if profile.addons != next_addons {
-                    profile.addons = next_addons;
+                    let prev_addons = std::mem::replace(&mut profile.addons, next_addons);
                     Effects::msg(Msg::Event(Event::AddonsPulledFromAPI { transport_urls }))
+                        .join(addons_history_effects::<E>(
+                            addons_history,
+                            &prev_addons,
+                            &profile.addons,
+                            None,
+                        ))
                         .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
                 } else {
                     Effects::msg(Msg::Event(Event::AddonsPulledFromAPI { transport_urls }))
@@ -121,6 +132,9 @@ pub fn update_profile<E: Env + 'static>(
             Effects::msg(Msg::Internal(Internal::UninstallAddon(addon.to_owned()))).unchanged()
         }
         Msg::Action(Action::Ctx(ActionCtx::UpgradeAddon(addon))) => {
+            Effects::msg(Msg::Internal(Internal::UpgradeAddon(addon.to_owned()))).unchanged()
+        }
+        Msg::Internal(Internal::UpgradeAddon(addon)) => {
             if profile.addons_locked {
                 return addon_upgrade_error_effects(addon, OtherError::UserAddonsAreLocked);
             }
@@ -143,12 +157,15 @@ pub fn update_profile<E: Env + 'static>(
             if addon.flags.protected || profile.addons[addon_position].flags.protected {
                 return addon_upgrade_error_effects(addon, OtherError::AddonIsProtected);
             }
+            let prev_addons = profile.addons.to_owned();
             addon.clone_into(&mut profile.addons[addon_position]);
             let push_to_api_effects = match profile.auth_key() {
-                Some(auth_key) => {
-                    Effects::one(push_addons_to_api::<E>(profile.addons.to_owned(), auth_key))
-                        .unchanged()
-                }
+                Some(auth_key) => Effects::one(push_addons_to_api::<E>(
+                    profile.addons.to_owned(),
+                    profile.board_layout.to_owned(),
+                    auth_key,
+                ))
+                .unchanged(),
                 _ => Effects::none().unchanged(),
             };
             Effects::msg(Msg::Event(Event::AddonUpgraded {
@@ -156,6 +173,12 @@ pub fn update_profile<E: Env + 'static>(
                 id: addon.manifest.id.to_owned(),
             }))
             .join(push_to_api_effects)
+            .join(addons_history_effects::<E>(
+                addons_history,
+                &prev_addons,
+                &profile.addons,
+                None,
+            ))
             .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
         }
         Msg::Internal(Internal::UninstallAddon(addon)) => {
@@ -170,6 +193,7 @@ pub fn update_profile<E: Env + 'static>(
                 .position(|transport_url| *transport_url == addon.transport_url);
             if let Some(addon_position) = addon_position {
                 if !profile.addons[addon_position].flags.protected && !addon.flags.protected {
+                    let prev_addons = profile.addons.to_owned();
                     profile.addons.remove(addon_position);
 
                     // Remove stream related to this addon from the streams bucket
@@ -180,6 +204,7 @@ pub fn update_profile<E: Env + 'static>(
                     let push_to_api_effects = match profile.auth_key() {
                         Some(auth_key) => Effects::one(push_addons_to_api::<E>(
                             profile.addons.to_owned(),
+                            profile.board_layout.to_owned(),
                             auth_key,
                         ))
                         .unchanged(),
@@ -190,6 +215,12 @@ pub fn update_profile<E: Env + 'static>(
                         id: addon.manifest.id.to_owned(),
                     }))
                     .join(push_to_api_effects)
+                    .join(addons_history_effects::<E>(
+                        addons_history,
+                        &prev_addons,
+                        &profile.addons,
+                        None,
+                    ))
                     .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
                 } else {
                     addon_uninstall_error_effects(addon, OtherError::AddonIsProtected)
@@ -198,6 +229,58 @@ pub fn update_profile<E: Env + 'static>(
                 addon_uninstall_error_effects(addon, OtherError::AddonNotInstalled)
             }
         }
+        Msg::Action(Action::Ctx(ActionCtx::RestoreAddonsSnapshot(id))) => {
+            if profile.addons_locked {
+                return addons_snapshot_restore_error_effects(*id, OtherError::UserAddonsAreLocked);
+            }
+
+            let snapshot_addons = match addons_history.get(*id) {
+                Some(snapshot) => snapshot.addons.to_owned(),
+                None => {
+                    return addons_snapshot_restore_error_effects(
+                        *id,
+                        OtherError::AddonsSnapshotNotFound,
+                    )
+                }
+            };
+            if profile.addons == snapshot_addons {
+                return Effects::msg(Msg::Event(Event::AddonsSnapshotRestored { id: *id }))
+                    .unchanged();
+            }
+
+            let prev_addons = std::mem::replace(&mut profile.addons, snapshot_addons);
+            let push_to_api_effects = match profile.auth_key() {
+                Some(auth_key) => Effects::one(push_addons_to_api::<E>(
+                    profile.addons.to_owned(),
+                    profile.board_layout.to_owned(),
+                    auth_key,
+                ))
+                .unchanged(),
+                _ => Effects::none().unchanged(),
+            };
+            Effects::msg(Msg::Event(Event::AddonsSnapshotRestored { id: *id }))
+                .join(push_to_api_effects)
+                .join(addons_history_effects::<E>(
+                    addons_history,
+                    &prev_addons,
+                    &profile.addons,
+                    None,
+                ))
+                .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+        }
+        Msg::Action(Action::Ctx(ActionCtx::MoveBoardCatalog { catalog, to })) => {
+            let catalogs = board_catalogs(&profile.addons);
+            let changed = profile.board_layout.move_catalog(&catalogs, catalog, *to);
+            board_layout_effects::<E>(profile, changed)
+        }
+        Msg::Action(Action::Ctx(ActionCtx::HideBoardCatalog(catalog))) => {
+            let changed = profile.board_layout.hide(catalog);
+            board_layout_effects::<E>(profile, changed)
+        }
+        Msg::Action(Action::Ctx(ActionCtx::ShowBoardCatalog(catalog))) => {
+            let changed = profile.board_layout.show(catalog);
+            board_layout_effects::<E>(profile, changed)
+        }
         Msg::Action(Action::Ctx(ActionCtx::LogoutTrakt)) => match &mut profile.auth {
             Some(Auth { user, key }) => {
                 if user.trakt.is_some() {
@@ -245,6 +328,7 @@ pub fn update_profile<E: Env + 'static>(
 
             if !profile.addons.contains(addon) {
                 if !addon.manifest.behavior_hints.configuration_required {
+                    let prev_addons = profile.addons.to_owned();
                     let addon_position = profile
                         .addons
                         .iter()
@@ -258,6 +342,7 @@ pub fn update_profile<E: Env + 'static>(
                     let push_to_api_effects = match profile.auth_key() {
                         Some(auth_key) => Effects::one(push_addons_to_api::<E>(
                             profile.addons.to_owned(),
+                            profile.board_layout.to_owned(),
                             auth_key,
                         ))
                         .unchanged(),
@@ -268,6 +353,12 @@ pub fn update_profile<E: Env + 'static>(
                         id: addon.manifest.id.to_owned(),
                     }))
                     .join(push_to_api_effects)
+                    .join(addons_history_effects::<E>(
+                        addons_history,
+                        &prev_addons,
+                        &profile.addons,
+                        None,
+                    ))
                     .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
                 } else {
                     addon_install_error_effects(addon, OtherError::AddonConfigurationRequired)
@@ -287,9 +378,17 @@ pub fn update_profile<E: Env + 'static>(
             ) if loading_auth_request == auth_request => {
                 let next_profile = Profile {
                     auth: Some(auth.to_owned()),
-                    addons: addons_result.to_owned().unwrap_or(OFFICIAL_ADDONS.clone()),
+                    addons: addons_result
+                        .as_ref()
+                        .map(|collection| collection.addons.to_owned())
+                        .unwrap_or(OFFICIAL_ADDONS.clone()),
                     addons_locked: addons_result.is_err(),
                     settings: Settings::default(),
+                    board_layout: addons_result
+                        .as_ref()
+                        .ok()
+                        .and_then(|collection| collection.board_layout.to_owned())
+                        .unwrap_or_default(),
                 };
                 if *profile != next_profile {
                     *profile = next_profile;
@@ -305,7 +404,11 @@ pub fn update_profile<E: Env + 'static>(
             result,
         )) if profile.auth_key() == Some(auth_key) => {
             let profile_effects = match result {
-                Ok(addons) => {
+                Ok(CollectionResponse {
+                    addons,
+                    last_modified,
+                    board_layout,
+                }) => {
                     let prev_transport_urls = profile
                         .addons
                         .iter()
@@ -324,15 +427,29 @@ pub fn update_profile<E: Env + 'static>(
                         .chain(removed_transport_urls)
                         .collect();
                     let profile_changed_effects = if profile.addons != *addons {
-                        addons.clone_into(&mut profile.addons);
+                        let prev_addons = std::mem::replace(&mut profile.addons, addons.to_owned());
 
-                        Effects::msg(Msg::Internal(Internal::ProfileChanged))
+                        addons_history_effects::<E>(
+                            addons_history,
+                            &prev_addons,
+                            &profile.addons,
+                            Some(*last_modified),
+                        )
+                        .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
                     } else {
                         Effects::none().unchanged()
                     };
+                    let board_layout_effects = match board_layout {
+                        Some(board_layout) if profile.board_layout != *board_layout => {
+                            board_layout.clone_into(&mut profile.board_layout);
+                            Effects::msg(Msg::Internal(Internal::ProfileChanged))
+                        }
+                        _ => Effects::none().unchanged(),
+                    };
 
                     Effects::msg(Msg::Event(Event::AddonsPulledFromAPI { transport_urls }))
                         .join(profile_changed_effects)
+                        .join(board_layout_effects)
                 }
                 Err(error) => Effects::msg(Msg::Event(Event::Error {
                     error: error.to_owned(),
@@ -386,7 +503,11 @@ pub fn update_profile<E: Env + 'static>(
     }
 }
 
-fn push_addons_to_api<E: Env + 'static>(addons: Vec<Descriptor>, auth_key: &AuthKey) -> Effect {
+fn push_addons_to_api<E: Env + 'static>(
+    addons: Vec<Descriptor>,
+    board_layout: BoardLayout,
+    auth_key: &AuthKey,
+) -> Effect {
     let transport_urls = addons
         .iter()
         .map(|addon| &addon.transport_url)
@@ -395,6 +516,7 @@ fn push_addons_to_api<E: Env + 'static>(addons: Vec<Descriptor>, auth_key: &Auth
     let request = APIRequest::AddonCollectionSet {
         auth_key: auth_key.to_owned(),
         addons,
+        board_layout,
     };
     EffectFuture::Concurrent(
         fetch_api::<E, _, _, SuccessResponse>(&request)
@@ -469,7 +591,6 @@ fn pull_addons_from_api<E: Env + 'static>(auth_key: &AuthKey) -> Effect {
                 APIResult::Ok(result) => future::ok(result),
                 APIResult::Err(error) => future::err(CtxError::from(error)),
             })
-            .map_ok(|CollectionResponse { addons, .. }| addons)
             .map(move |result| Msg::Internal(Internal::AddonsAPIResult(request, result)))
             .boxed_env(),
     )
@@ -491,6 +612,62 @@ fn push_profile_to_storage<E: Env + 'static>(profile: &Profile) -> Effect {
     .into()
 }
 
+/// Records the replacement of the previous addons in the addons history.
+fn addons_history_effects<E: Env + 'static>(
+    addons_history: &mut AddonsHistoryBucket,
+    prev_addons: &[Descriptor],
+    addons: &[Descriptor],
+    last_modified: Option<DateTime<Utc>>,
+) -> Effects {
+    if addons_history.add(prev_addons, addons, last_modified, E::now()) {
+        Effects::msg(Msg::Internal(Internal::AddonsHistoryChanged))
+    } else {
+        Effects::none().unchanged()
+    }
+}
+
+/// The catalogs on the Board, in the order of the installed addons.
+fn board_catalogs(addons: &[Descriptor]) -> Vec<BoardCatalog> {
+    AggrRequest::AllCatalogs {
+        extra: &vec![],
+        r#type: &None,
+        language: None,
+    }
+    .plan(addons)
+    .iter()
+    .map(|(_, request)| BoardCatalog::from(request))
+    .collect()
+}
+
+/// The board layout is pushed to the API with the addons,
+/// unless they are locked as it would overwrite the user's addons.
+fn board_layout_effects<E: Env + 'static>(profile: &Profile, changed: bool) -> Effects {
+    let event_effects = Effects::msg(Msg::Event(Event::BoardLayoutUpdated {
+        board_layout: profile.board_layout.to_owned(),
+    }))
+    .unchanged();
+    if !changed {
+        return event_effects;
+    }
+
+    let push_to_api_effects = match profile.auth_key() {
+        Some(auth_key) if !profile.addons_locked => Effects::one(push_addons_to_api::<E>(
+            profile.addons.to_owned(),
+            profile.board_layout.to_owned(),
+            auth_key,
+        ))
+        .unchanged(),
+        _ => Effects::none().unchanged(),
+    };
+    event_effects
+        .join(push_to_api_effects)
+        .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+}
+
+fn addons_snapshot_restore_error_effects(id: u64, error: OtherError) -> Effects {
+    addon_action_error_effects(error, Event::AddonsSnapshotRestored { id })
+}
+
 fn addon_upgrade_error_effects(addon: &Descriptor, error: OtherError) -> Effects {
     addon_action_error_effects(
         error,
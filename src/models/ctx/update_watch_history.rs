@@ -0,0 +1,90 @@
+use enclose::enclose;
+use futures::FutureExt;
+
+use crate::constants::WATCH_HISTORY_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, CtxAuthResponse, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt};
+use crate::types::watch_history::WatchHistoryBucket;
+
+pub fn update_watch_history<E: Env + 'static>(
+    watch_history: &mut WatchHistoryBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) | Msg::Internal(Internal::Logout) => {
+            let next_watch_history = WatchHistoryBucket::default();
+            if *watch_history != next_watch_history {
+                *watch_history = next_watch_history;
+                Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::RemoveFromWatchHistory(id))) => {
+            if watch_history.remove(id) {
+                Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::RemoveItemFromWatchHistory(meta_id))) => {
+            if watch_history.remove_meta(meta_id) {
+                Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::ClearWatchHistory)) => {
+            if !watch_history.items.is_empty() {
+                watch_history.items.clear();
+                Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::PlayerSessionEnded(item)) => {
+            if watch_history.add(*item.to_owned()) {
+                Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::WatchHistoryChanged) => {
+            Effects::one(push_watch_history_to_storage::<E>(watch_history)).unchanged()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok(CtxAuthResponse { auth, .. }))
+                if loading_auth_request == auth_request =>
+            {
+                let next_watch_history = WatchHistoryBucket::new(Some(auth.user.id.to_owned()));
+                if *watch_history != next_watch_history {
+                    *watch_history = next_watch_history;
+                    Effects::msg(Msg::Internal(Internal::WatchHistoryChanged))
+                } else {
+                    Effects::none().unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_watch_history_to_storage<E: Env + 'static>(watch_history: &WatchHistoryBucket) -> Effect {
+    EffectFuture::Sequential(
+        E::set_storage(WATCH_HISTORY_STORAGE_KEY, Some(&watch_history))
+            .map(
+                enclose!((watch_history.uid => uid) move |result| match result {
+                    Ok(_) => Msg::Event(Event::WatchHistoryPushedToStorage { uid }),
+                    Err(error) => Msg::Event(Event::Error {
+                        error: CtxError::from(error),
+                        source: Box::new(Event::WatchHistoryPushedToStorage { uid }),
+                    })
+                }),
+            )
+            .boxed_env(),
+    )
+    .into()
+}
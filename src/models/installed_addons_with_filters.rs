@@ -1,13 +1,18 @@
 use crate::constants::TYPE_PRIORITIES;
-use crate::models::common::{compare_with_priorities, eq_update};
+use crate::models::common::{compare_with_priorities, eq_update, DescriptorLoadable, Loadable};
 use crate::models::ctx::Ctx;
-use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
-use crate::runtime::{Effects, Env, UpdateWithCtx};
-use crate::types::addon::{DescriptorPreview, ManifestPreview};
+use crate::runtime::msg::{Action, ActionInstalledAddonsWithFilters, ActionLoad, Internal, Msg};
+use crate::runtime::{EffectFuture, Effects, Env, EnvError, EnvFutureExt, UpdateWithCtx};
+use crate::types::addon::{
+    Descriptor, DescriptorPreview, Manifest, ManifestCatalog, ManifestPreview,
+};
 use crate::types::profile::Profile;
+use futures::FutureExt;
 use itertools::Itertools;
+use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::iter;
+use url::Url;
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct InstalledAddonsRequest {
@@ -31,11 +36,29 @@ pub struct Selectable {
     pub types: Vec<SelectableType>,
 }
 
+/// A newer version of an installed addon.
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonUpgrade {
+    /// The newer version of the addon with the flags of the installed one
+    pub addon: Descriptor,
+    pub installed_version: Version,
+    pub added_resources: Vec<String>,
+    pub removed_resources: Vec<String>,
+    pub added_types: Vec<String>,
+    pub removed_types: Vec<String>,
+    pub added_catalogs: Vec<ManifestCatalog>,
+    pub removed_catalogs: Vec<ManifestCatalog>,
+}
+
 #[derive(Default, Clone, Serialize)]
 pub struct InstalledAddonsWithFilters {
     pub selected: Option<Selected>,
     pub selectable: Selectable,
     pub catalog: Vec<DescriptorPreview>,
+    /// The installed addons as fetched from their transport url, checked for upgrades
+    pub remote_addons: Vec<DescriptorLoadable>,
+    pub upgrades: Vec<AddonUpgrade>,
 }
 
 impl InstalledAddonsWithFilters {
@@ -63,9 +86,22 @@ impl<E: Env + 'static> UpdateWithCtx<E> for InstalledAddonsWithFilters {
                     selectable_update(&mut self.selectable, &self.selected, &ctx.profile);
                 let catalog_effects =
                     catalog_update(&mut self.catalog, &self.selected, &ctx.profile);
+                let remote_addons_effects =
+                    remote_addons_update::<E>(&mut self.remote_addons, &ctx.profile);
                 selected_effects
                     .join(selectable_effects)
                     .join(catalog_effects)
+                    .join(remote_addons_effects)
+            }
+            Msg::Action(Action::InstalledAddonsWithFilters(
+                ActionInstalledAddonsWithFilters::CheckUpgrades,
+            )) => {
+                self.remote_addons.clear();
+                let remote_addons_effects =
+                    remote_addons_update::<E>(&mut self.remote_addons, &ctx.profile);
+                let upgrades_effects =
+                    upgrades_update(&mut self.upgrades, &self.remote_addons, &ctx.profile);
+                remote_addons_effects.join(upgrades_effects)
             }
             Msg::Action(Action::Unload) => {
                 let selected_effects = eq_update(&mut self.selected, None);
@@ -82,7 +118,39 @@ impl<E: Env + 'static> UpdateWithCtx<E> for InstalledAddonsWithFilters {
                     selectable_update(&mut self.selectable, &self.selected, &ctx.profile);
                 let catalog_effects =
                     catalog_update(&mut self.catalog, &self.selected, &ctx.profile);
-                selectable_effects.join(catalog_effects)
+                let upgrades_effects =
+                    upgrades_update(&mut self.upgrades, &self.remote_addons, &ctx.profile);
+                selectable_effects
+                    .join(catalog_effects)
+                    .join(upgrades_effects)
+            }
+            Msg::Internal(Internal::ManifestRequestResult(transport_url, result)) => {
+                let remote_addon_effects =
+                    remote_addon_update(&mut self.remote_addons, transport_url, result);
+                if !remote_addon_effects.has_changed {
+                    return remote_addon_effects;
+                }
+                let upgrades_effects =
+                    upgrades_update(&mut self.upgrades, &self.remote_addons, &ctx.profile);
+                let auto_upgrade_effects = match self
+                    .upgrades
+                    .iter()
+                    .find(|upgrade| upgrade.addon.transport_url == *transport_url)
+                {
+                    Some(upgrade)
+                        if ctx.profile.settings.auto_upgrade_addons
+                            && !upgrade.addon.flags.protected =>
+                    {
+                        Effects::msg(Msg::Internal(Internal::UpgradeAddon(
+                            upgrade.addon.to_owned(),
+                        )))
+                        .unchanged()
+                    }
+                    _ => Effects::none().unchanged(),
+                };
+                remote_addon_effects
+                    .join(upgrades_effects)
+                    .join(auto_upgrade_effects)
             }
             _ => Effects::none().unchanged(),
         }
@@ -160,3 +228,137 @@ fn catalog_update(
     };
     eq_update(catalog, next_catalog)
 }
+
+/// Requests the manifests of the installed addons which have not been requested yet.
+fn remote_addons_update<E: Env + 'static>(
+    remote_addons: &mut Vec<DescriptorLoadable>,
+    profile: &Profile,
+) -> Effects {
+    profile
+        .addons
+        .iter()
+        .filter(|addon| {
+            !remote_addons
+                .iter()
+                .any(|remote_addon| remote_addon.transport_url == addon.transport_url)
+        })
+        .map(|addon| addon.transport_url.to_owned())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .fold(Effects::none().unchanged(), |effects, transport_url| {
+            remote_addons.push(DescriptorLoadable {
+                transport_url: transport_url.to_owned(),
+                content: Loadable::Loading,
+            });
+            effects.join(Effects::future(EffectFuture::Concurrent(
+                E::addon_transport(&transport_url)
+                    .manifest()
+                    .map(move |result| {
+                        Msg::Internal(Internal::ManifestRequestResult(transport_url, result))
+                    })
+                    .boxed_env(),
+            )))
+        })
+}
+
+fn remote_addon_update(
+    remote_addons: &mut [DescriptorLoadable],
+    transport_url: &Url,
+    result: &Result<Manifest, EnvError>,
+) -> Effects {
+    match remote_addons.iter_mut().find(|remote_addon| {
+        remote_addon.transport_url == *transport_url
+            && matches!(remote_addon.content, Loadable::Loading)
+    }) {
+        Some(remote_addon) => {
+            remote_addon.content = match result {
+                Ok(manifest) => Loadable::Ready(Descriptor {
+                    transport_url: transport_url.to_owned(),
+                    manifest: manifest.to_owned(),
+                    flags: Default::default(),
+                }),
+                Err(error) => Loadable::Err(error.to_owned()),
+            };
+            Effects::none()
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn upgrades_update(
+    upgrades: &mut Vec<AddonUpgrade>,
+    remote_addons: &[DescriptorLoadable],
+    profile: &Profile,
+) -> Effects {
+    let next_upgrades = profile
+        .addons
+        .iter()
+        .filter_map(|addon| {
+            let remote_addon = remote_addons
+                .iter()
+                .find_map(|remote_addon| match &remote_addon.content {
+                    Loadable::Ready(remote_addon)
+                        if remote_addon.transport_url == addon.transport_url =>
+                    {
+                        Some(remote_addon)
+                    }
+                    _ => None,
+                })?;
+            if remote_addon.manifest.version <= addon.manifest.version {
+                return None;
+            }
+            let (added_resources, removed_resources) = diff_by(
+                &addon.manifest.resources,
+                &remote_addon.manifest.resources,
+                |resource| resource.name().to_owned(),
+            );
+            let (added_types, removed_types) = diff_by(
+                &addon.manifest.types,
+                &remote_addon.manifest.types,
+                |r#type| r#type.to_owned(),
+            );
+            let (added_catalogs, removed_catalogs) = diff_by(
+                &addon.manifest.catalogs,
+                &remote_addon.manifest.catalogs,
+                |catalog| (catalog.r#type.to_owned(), catalog.id.to_owned()),
+            );
+            Some(AddonUpgrade {
+                addon: Descriptor {
+                    flags: addon.flags.to_owned(),
+                    ..remote_addon.to_owned()
+                },
+                installed_version: addon.manifest.version.to_owned(),
+                added_resources: added_resources
+                    .into_iter()
+                    .map(|resource| resource.name().to_owned())
+                    .collect(),
+                removed_resources: removed_resources
+                    .into_iter()
+                    .map(|resource| resource.name().to_owned())
+                    .collect(),
+                added_types: added_types.into_iter().cloned().collect(),
+                removed_types: removed_types.into_iter().cloned().collect(),
+                added_catalogs: added_catalogs.into_iter().cloned().collect(),
+                removed_catalogs: removed_catalogs.into_iter().cloned().collect(),
+            })
+        })
+        .collect::<Vec<_>>();
+    eq_update(upgrades, next_upgrades)
+}
+
+/// Returns the items which are only in `next` and the ones which are only in `prev`.
+fn diff_by<'a, T, K: PartialEq>(
+    prev: &'a [T],
+    next: &'a [T],
+    key: impl Fn(&T) -> K,
+) -> (Vec<&'a T>, Vec<&'a T>) {
+    let added = next
+        .iter()
+        .filter(|item| !prev.iter().any(|prev_item| key(prev_item) == key(item)))
+        .collect();
+    let removed = prev
+        .iter()
+        .filter(|item| !next.iter().any(|next_item| key(next_item) == key(item)))
+        .collect();
+    (added, removed)
+}
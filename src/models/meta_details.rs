@@ -43,6 +43,9 @@ pub struct Selected {
 pub struct MetaDetails {
     pub selected: Option<Selected>,
     pub meta_items: Vec<ResourceLoadable<MetaItem>>,
+    /// The first loaded meta item enriched with the fields, links, trailers and videos
+    /// of the other loaded meta items, e.g. when the description is missing
+    pub merged_meta_item: Option<MetaItem>,
     pub meta_streams: Vec<ResourceLoadable<Vec<Stream>>>,
     pub streams: Vec<ResourceLoadable<Vec<Stream>>>,
     pub suggested_stream: Option<ResourceLoadable<Option<Stream>>>,
@@ -58,6 +61,8 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                 let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
                 let meta_items_effects =
                     meta_items_update::<E>(&mut self.meta_items, &self.selected, &ctx.profile);
+                let merged_meta_item_effects =
+                    merged_meta_item_update(&mut self.merged_meta_item, &self.meta_items);
                 let selected_override_effects =
                     selected_guess_stream_update(&mut self.selected, &self.meta_items);
                 let meta_streams_effects =
@@ -85,6 +90,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                     .join(selected_effects)
                     .join(selected_override_effects)
                     .join(meta_items_effects)
+                    .join(merged_meta_item_effects)
                     .join(meta_streams_effects)
                     .join(streams_effects)
                     .join(suggested_stream_effects)
@@ -94,6 +100,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
             Msg::Action(Action::Unload) => {
                 let selected_effects = eq_update(&mut self.selected, None);
                 let meta_items_effects = eq_update(&mut self.meta_items, vec![]);
+                let merged_meta_item_effects = eq_update(&mut self.merged_meta_item, None);
                 let meta_streams_effects = eq_update(&mut self.meta_streams, vec![]);
                 let streams_effects = eq_update(&mut self.streams, vec![]);
                 let library_item_effects = eq_update(&mut self.library_item, None);
@@ -101,6 +108,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                 let watched_effects = eq_update(&mut self.watched, None);
                 selected_effects
                     .join(meta_items_effects)
+                    .join(merged_meta_item_effects)
                     .join(meta_streams_effects)
                     .join(streams_effects)
                     .join(suggested_stream_effects)
@@ -149,6 +157,8 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                     &mut self.meta_items,
                     ResourcesAction::ResourceRequestResult { request, result },
                 );
+                let merged_meta_item_effects =
+                    merged_meta_item_update(&mut self.merged_meta_item, &self.meta_items);
                 let selected_override_effects =
                     selected_guess_stream_update(&mut self.selected, &self.meta_items);
                 let streams_effects = if selected_override_effects.has_changed {
@@ -176,6 +186,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                     watched_update(&mut self.watched, &self.meta_items, &self.library_item);
                 selected_override_effects
                     .join(meta_items_effects)
+                    .join(merged_meta_item_effects)
                     .join(meta_streams_effects)
                     .join(streams_effects)
                     .join(suggested_stream_effects)
@@ -213,6 +224,8 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
             Msg::Internal(Internal::ProfileChanged) => {
                 let meta_items_effects =
                     meta_items_update::<E>(&mut self.meta_items, &self.selected, &ctx.profile);
+                let merged_meta_item_effects =
+                    merged_meta_item_update(&mut self.merged_meta_item, &self.meta_items);
                 let meta_streams_effects =
                     meta_streams_update(&mut self.meta_streams, &self.selected, &self.meta_items);
                 let streams_effects =
@@ -234,6 +247,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                 let watched_effects =
                     watched_update(&mut self.watched, &self.meta_items, &self.library_item);
                 meta_items_effects
+                    .join(merged_meta_item_effects)
                     .join(meta_streams_effects)
                     .join(streams_effects)
                     .join(suggested_stream_effects)
@@ -333,6 +347,26 @@ fn selected_guess_stream_update(
     )
 }
 
+/// The first loaded meta item merged with the other loaded meta items, in the order of the addons.
+fn merged_meta_item_update(
+    merged_meta_item: &mut Option<MetaItem>,
+    meta_items: &[ResourceLoadable<MetaItem>],
+) -> Effects {
+    let mut ready_meta_items = meta_items
+        .iter()
+        .filter_map(|meta_item| match &meta_item.content {
+            Some(Loadable::Ready(meta_item)) => Some(meta_item),
+            _ => None,
+        });
+    let next_merged_meta_item = ready_meta_items.next().map(|meta_item| {
+        ready_meta_items.fold(meta_item.to_owned(), |mut merged_meta_item, meta_item| {
+            merged_meta_item.merge(meta_item);
+            merged_meta_item
+        })
+    });
+    eq_update(merged_meta_item, next_merged_meta_item)
+}
+
 fn meta_items_update<E: Env + 'static>(
     meta_items: &mut Vec<ResourceLoadable<MetaItem>>,
     selected: &Option<Selected>,
@@ -342,7 +376,10 @@ fn meta_items_update<E: Env + 'static>(
         Some(Selected { meta_path, .. }) => resources_update::<E, _>(
             meta_items,
             ResourcesAction::ResourcesRequested {
-                request: &AggrRequest::AllOfResource(meta_path.to_owned()),
+                request: &AggrRequest::AllOfResource {
+                    path: meta_path.to_owned(),
+                    priorities: &profile.settings.addon_priorities,
+                },
                 addons: &profile.addons,
                 // use existing loaded MetaItems instead of making a request every time.
                 force: false,
@@ -419,7 +456,10 @@ fn streams_update<E: Env + 'static>(
         }) => resources_update_with_vector_content::<E, _>(
             streams,
             ResourcesAction::ResourcesRequested {
-                request: &AggrRequest::AllOfResource(stream_path.to_owned()),
+                request: &AggrRequest::AllOfResource {
+                    path: stream_path.to_owned(),
+                    priorities: &profile.settings.addon_priorities,
+                },
                 addons: &profile.addons,
                 // use existing loaded MetaItems instead of making a request every time.
                 force: false,
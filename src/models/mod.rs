@@ -1,7 +1,10 @@
 pub mod common;
 pub mod ctx;
 
+pub mod addon_configure;
 pub mod addon_details;
+pub mod calendar;
+pub mod cast_session;
 pub mod catalog_with_filters;
 pub mod catalogs_with_extra;
 pub mod continue_watching_preview;
@@ -13,4 +16,8 @@ pub mod link;
 pub mod local_search;
 pub mod meta_details;
 pub mod player;
+pub mod stats;
 pub mod streaming_server;
+pub mod trailer_player;
+pub mod watch_history;
+pub mod watch_party;
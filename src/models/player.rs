@@ -7,8 +7,8 @@ use num::rational::Ratio;
 
 use crate::constants::{
     BASE64, CREDITS_THRESHOLD_COEF, META_RESOURCE_NAME, PLAYER_IGNORE_SEEK_AFTER,
-    STREAM_RESOURCE_NAME, SUBTITLES_RESOURCE_NAME, VIDEO_FILENAME_EXTRA_PROP,
-    VIDEO_HASH_EXTRA_PROP, VIDEO_SIZE_EXTRA_PROP, WATCHED_THRESHOLD_COEF,
+    STILL_WATCHING_AFTER_EPISODES, STREAM_RESOURCE_NAME, SUBTITLES_RESOURCE_NAME,
+    VIDEO_FILENAME_EXTRA_PROP, VIDEO_HASH_EXTRA_PROP, VIDEO_SIZE_EXTRA_PROP,
 };
 use crate::models::common::{
     eq_update, resource_update, resource_update_with_vector_content,
@@ -18,16 +18,20 @@ use crate::models::common::{
 use crate::models::ctx::{Ctx, CtxError};
 use crate::runtime::msg::{Action, ActionLoad, ActionPlayer, Event, Internal, Msg};
 use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvFutureExt, UpdateWithCtx};
-use crate::types::addon::{AggrRequest, Descriptor, ExtraExt, ResourcePath, ResourceRequest};
+use crate::types::addon::{AggrRequest, ExtraExt, ResourcePath, ResourceRequest};
 use crate::types::api::{
     fetch_api, APIRequest, APIResult, SeekLog, SeekLogRequest, SkipGapsRequest, SkipGapsResponse,
     SuccessResponse,
 };
+use crate::types::language_preferences::{LanguagePreference, LanguagePreferencesBucket};
 use crate::types::library::{LibraryBucket, LibraryItem};
+use crate::types::play_queue::{PlayQueueBucket, PlayQueueItem};
 use crate::types::player::{IntroData, IntroOutro};
 use crate::types::profile::{Profile, Settings as ProfileSettings};
 use crate::types::resource::{MetaItem, SeriesInfo, Stream, StreamSource, Subtitles, Video};
-use crate::types::streams::{StreamItemState, StreamsBucket, StreamsItemKey};
+use crate::types::streams::{StreamItemState, StreamsBucket, StreamsItemKey, SubtitleTrack};
+use crate::types::subtitles::SubtitlesTrack;
+use crate::types::watch_history::WatchHistoryItem;
 
 use stremio_watched_bitfield::WatchedBitField;
 
@@ -85,6 +89,15 @@ pub struct Selected {
     pub subtitles_path: Option<ResourcePath>,
 }
 
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SleepTimer {
+    /// Stop the playback once this time has come
+    Until { time: DateTime<Utc> },
+    /// Stop the playback at the end of the current video instead of playing the next one
+    EndOfVideo,
+}
+
 #[derive(Clone, Derivative, Serialize, Debug)]
 #[derivative(Default)]
 #[serde(rename_all = "camelCase")]
@@ -93,16 +106,45 @@ pub struct Player {
     pub video_params: Option<VideoParams>,
     pub meta_item: Option<ResourceLoadable<MetaItem>>,
     pub subtitles: Vec<ResourceLoadable<Vec<Subtitles>>>,
+    /// The subtitles of the stream and the addons, de-duplicated and ranked
+    pub subtitles_tracks: Vec<SubtitlesTrack>,
+    /// The track remembered for this video, which should be auto-selected
+    pub selected_subtitles_track: Option<SubtitlesTrack>,
     pub next_video: Option<Video>,
     pub next_streams: Option<ResourceLoadable<Vec<Stream>>>,
     pub next_stream: Option<Stream>,
+    /// The first video of the play queue, which is played once there's no next video of the current title
+    pub up_next: Option<PlayQueueItem>,
+    pub up_next_streams: Option<ResourceLoadable<Vec<Stream>>>,
+    /// The stream of [`Player::up_next`] from the same binge group as the selected stream
+    pub up_next_stream: Option<Stream>,
     pub series_info: Option<SeriesInfo>,
     pub library_item: Option<LibraryItem>,
     pub stream_state: Option<StreamItemState>,
+    /// The languages remembered for the series, which are applied to the `stream_state`
+    pub language_preference: Option<LanguagePreference>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub intro_outro: Option<IntroOutro>,
+    pub sleep_timer: Option<SleepTimer>,
+    /// Whether the user is asked if they're still watching after many episodes were auto-advanced.
+    ///
+    /// The progress of the library item isn't updated until they confirm it.
+    pub still_watching_requested: bool,
+    /// The number of consecutive videos loaded by binge watching
+    #[serde(skip_serializing)]
+    pub auto_advanced_count: u32,
+    /// Whether the current video has ended and binge watching plays the next one
+    #[serde(skip_serializing)]
+    pub auto_advancing: bool,
+    /// Whether the next video was requested after the current one has ended,
+    /// so it's still auto-advanced to when the player is unloaded before loading it
+    #[serde(skip_serializing)]
+    pub next_video_requested: bool,
     #[serde(skip_serializing)]
     pub watched: Option<WatchedBitField>,
+    /// The current viewing session, which is recorded in the watch history once it ends
+    #[serde(skip_serializing)]
+    pub session: Option<WatchHistoryItem>,
     #[serde(skip_serializing)]
     pub analytics_context: Option<AnalyticsContext>,
     #[serde(skip_serializing)]
@@ -140,6 +182,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 } else {
                     Effects::none().unchanged()
                 };
+                let session_effects = session_end(&mut self.session);
                 let selected_effects = eq_update(&mut self.selected, Some(*selected.to_owned()));
                 let meta_item_effects = match &selected.meta_request {
                     Some(meta_request) => match &mut self.meta_item {
@@ -167,12 +210,27 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     _ => eq_update(&mut self.meta_item, None),
                 };
                 let stream_state_effects = eq_update(&mut self.stream_state, None);
+                let language_preference_effects = language_preference_update(
+                    &mut self.language_preference,
+                    &self.selected,
+                    &ctx.language_preferences,
+                );
                 let video_params_effects = eq_update(&mut self.video_params, None);
                 let subtitles_effects = subtitles_update::<E>(
                     &mut self.subtitles,
                     &self.selected,
                     &self.video_params,
-                    &ctx.profile.addons,
+                    &ctx.profile,
+                );
+                let subtitles_tracks_effects = subtitles_tracks_update(
+                    &mut self.subtitles_tracks,
+                    &mut self.selected_subtitles_track,
+                    &self.selected,
+                    &self.video_params,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &self.language_preference,
+                    &ctx.profile.settings,
                 );
                 let next_video_effects = next_video_update(
                     &mut self.next_video,
@@ -192,6 +250,16 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     &self.selected,
                     &ctx.profile.settings,
                 );
+                let up_next_effects = up_next_update::<E>(
+                    &mut self.up_next,
+                    &mut self.up_next_streams,
+                    &mut self.up_next_stream,
+                    &self.next_video,
+                    &self.selected,
+                    &self.meta_item,
+                    &ctx.play_queue,
+                    &ctx.profile.settings,
+                );
                 // Make sure to update the steams and in term the StreamsBucket
                 // once the player loads the newly selected item
                 let update_streams_effects = match (&self.selected, &self.meta_item) {
@@ -219,7 +287,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 let skip_gaps_effects = eq_update(&mut self.skip_gaps, None);
                 let intro_outro_update_effects = intro_outro_update::<E>(
                     &mut self.intro_outro,
-                    &ctx.profile,
+                    ctx,
                     self.selected.as_ref(),
                     self.video_params.as_ref(),
                     self.series_info.as_ref(),
@@ -235,6 +303,9 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     .unchanged(),
                     _ => Effects::none().unchanged(),
                 };
+                let loaded_effects =
+                    Effects::msg(Msg::Internal(Internal::PlayerLoaded(selected.to_owned())))
+                        .unchanged();
                 let (id, r#type, name, video_id, time, duration) = self
                     .library_item
                     .as_ref()
@@ -259,19 +330,42 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     has_trakt: ctx.profile.has_trakt::<E>(),
                     ..Default::default()
                 });
+                let still_watching_effects = if self.auto_advancing {
+                    self.auto_advanced_count = self.auto_advanced_count.saturating_add(1);
+                    if !self.still_watching_requested
+                        && ctx.profile.settings.binge_watching
+                        && self.auto_advanced_count >= STILL_WATCHING_AFTER_EPISODES
+                    {
+                        self.still_watching_requested = true;
+                        Effects::msg(Msg::Event(Event::PlayerStillWatchingRequested {
+                            context: self.analytics_context.as_ref().cloned().unwrap_or_default(),
+                        }))
+                    } else {
+                        Effects::none().unchanged()
+                    }
+                } else {
+                    self.auto_advanced_count = 0;
+                    eq_update(&mut self.still_watching_requested, false)
+                };
+                self.auto_advancing = false;
+                self.next_video_requested = false;
                 self.load_time = Some(E::now());
                 self.loaded = false;
                 self.ended = false;
                 self.paused = None;
                 item_state_update_effects
+                    .join(session_effects)
                     .join(selected_effects)
                     .join(meta_item_effects)
                     .join(stream_state_effects)
+                    .join(language_preference_effects)
                     .join(video_params_effects)
                     .join(subtitles_effects)
+                    .join(subtitles_tracks_effects)
                     .join(next_video_effects)
                     .join(next_streams_effects)
                     .join(next_stream_effects)
+                    .join(up_next_effects)
                     .join(update_streams_effects)
                     .join(series_info_effects)
                     .join(library_item_effects)
@@ -279,6 +373,8 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     .join(skip_gaps_effects)
                     .join(intro_outro_update_effects)
                     .join(notification_effects)
+                    .join(still_watching_effects)
+                    .join(loaded_effects)
             }
             Msg::Action(Action::Unload) => {
                 let ended_effects = if !self.ended && self.selected.is_some() {
@@ -302,6 +398,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
 
                 let item_state_update_effects =
                     item_state_update(&mut self.library_item, &self.next_video);
+                let session_effects = session_end(&mut self.session);
                 let push_to_library_effects = match &self.library_item {
                     Some(library_item) => Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(
                         library_item.to_owned(),
@@ -313,14 +410,33 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 let video_params_effects = eq_update(&mut self.video_params, None);
                 let meta_item_effects = eq_update(&mut self.meta_item, None);
                 let stream_state_effects = eq_update(&mut self.stream_state, None);
+                let language_preference_effects = eq_update(&mut self.language_preference, None);
                 let subtitles_effects = eq_update(&mut self.subtitles, vec![]);
+                let subtitles_tracks_effects = eq_update(&mut self.subtitles_tracks, vec![]);
+                let selected_subtitles_track_effects =
+                    eq_update(&mut self.selected_subtitles_track, None);
                 let next_video_effects = eq_update(&mut self.next_video, None);
                 let next_streams_effects = eq_update(&mut self.next_streams, None);
                 let next_stream_effects = eq_update(&mut self.next_stream, None);
+                let up_next_effects = eq_update(&mut self.up_next, None)
+                    .join(eq_update(&mut self.up_next_streams, None))
+                    .join(eq_update(&mut self.up_next_stream, None));
                 let series_info_effects = eq_update(&mut self.series_info, None);
                 let library_item_effects = eq_update(&mut self.library_item, None);
                 let watched_effects = eq_update(&mut self.watched, None);
                 let skip_gaps_effects = eq_update(&mut self.skip_gaps, None);
+                // the player is closed instead of playing the next video
+                if !self.next_video_requested {
+                    self.auto_advancing = false;
+                    self.auto_advanced_count = 0;
+                }
+                self.next_video_requested = false;
+                // the sleep timer keeps running while binge watching plays the next video
+                let sleep_timer_effects = if self.auto_advancing {
+                    Effects::none().unchanged()
+                } else {
+                    eq_update(&mut self.sleep_timer, None)
+                };
                 self.analytics_context = None;
                 self.load_time = None;
                 self.loaded = false;
@@ -329,19 +445,25 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
 
                 seek_history_effects
                     .join(item_state_update_effects)
+                    .join(session_effects)
                     .join(push_to_library_effects)
                     .join(selected_effects)
                     .join(video_params_effects)
                     .join(meta_item_effects)
                     .join(stream_state_effects)
+                    .join(language_preference_effects)
                     .join(subtitles_effects)
+                    .join(subtitles_tracks_effects)
+                    .join(selected_subtitles_track_effects)
                     .join(next_video_effects)
                     .join(next_streams_effects)
                     .join(next_stream_effects)
+                    .join(up_next_effects)
                     .join(series_info_effects)
                     .join(library_item_effects)
                     .join(watched_effects)
                     .join(skip_gaps_effects)
+                    .join(sleep_timer_effects)
                     .join(ended_effects)
             }
             Msg::Action(Action::Player(ActionPlayer::VideoParamsChanged { video_params })) => {
@@ -351,7 +473,17 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     &mut self.subtitles,
                     &self.selected,
                     &self.video_params,
-                    &ctx.profile.addons,
+                    &ctx.profile,
+                );
+                let subtitles_tracks_effects = subtitles_tracks_update(
+                    &mut self.subtitles_tracks,
+                    &mut self.selected_subtitles_track,
+                    &self.selected,
+                    &self.video_params,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &self.language_preference,
+                    &ctx.profile.settings,
                 );
                 let skip_gaps_effects = skip_gaps_update::<E>(
                     &ctx.profile,
@@ -364,11 +496,37 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
 
                 video_params_effects
                     .join(subtitles_effects)
+                    .join(subtitles_tracks_effects)
                     .join(skip_gaps_effects)
             }
             Msg::Action(Action::Player(ActionPlayer::StreamStateChanged { state })) => {
+                // remember which addon the selected subtitles came from and their language
+                let subtitles_track = state
+                    .subtitle_track
+                    .as_ref()
+                    .filter(|subtitle_track| !subtitle_track.embedded)
+                    .and_then(|subtitle_track| {
+                        self.subtitles_tracks
+                            .iter()
+                            .find(|track| track.id == subtitle_track.id)
+                    });
+                let subtitles_origin = subtitles_track.and_then(|track| track.origin.to_owned());
+                let subtitle_track =
+                    state
+                        .subtitle_track
+                        .to_owned()
+                        .map(|subtitle_track| SubtitleTrack {
+                            language: subtitle_track
+                                .language
+                                .or_else(|| subtitles_track.map(|track| track.lang.to_owned())),
+                            ..subtitle_track
+                        });
                 Effects::msg(Msg::Internal(Internal::StreamStateChanged {
-                    state: state.to_owned(),
+                    state: StreamItemState {
+                        subtitle_track,
+                        subtitles_origin,
+                        ..state.to_owned()
+                    },
                     stream_request: self
                         .selected
                         .as_ref()
@@ -384,7 +542,9 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 time,
                 duration,
                 device,
-            })) => match (&self.selected, &mut self.library_item) {
+            })) if self.selected.is_some() => match (&self.selected, &mut self.library_item) {
+                // the user might have fallen asleep, so nothing is considered watched
+                _ if self.still_watching_requested => Effects::none().unchanged(),
                 (
                     Some(Selected {
                         stream_request:
@@ -397,60 +557,28 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     Some(library_item),
                 ) => {
                     let seeking = library_item.state.time_offset.abs_diff(*time) > 1000;
+                    let duration_changed = library_item.state.duration != *duration;
 
-                    // if we've selected a new video (like the next episode)
-                    library_item.state.last_watched = Some(E::now());
-                    if library_item.state.video_id != Some(video_id.to_owned()) {
-                        library_item.state.video_id = Some(video_id.to_owned());
-                        library_item.state.overall_time_watched = library_item
-                            .state
-                            .overall_time_watched
-                            .saturating_add(library_item.state.time_watched);
-                        library_item.state.time_watched = 0;
-                        library_item.state.flagged_watched = 0;
-                    } else {
-                        // else we have added to the currently selected video/stream
-                        // seek logging
-                        if seeking
-                            && library_item.r#type == "series"
-                            && time < &PLAYER_IGNORE_SEEK_AFTER
-                        {
-                            self.seek_history.push(SeekLog {
-                                from: library_item.state.time_offset,
-                                to: *time,
-                            });
-                        }
-
-                        let time_watched =
-                            1000.min(time.saturating_sub(library_item.state.time_offset));
-                        library_item.state.time_watched =
-                            library_item.state.time_watched.saturating_add(time_watched);
-                        library_item.state.overall_time_watched = library_item
-                            .state
-                            .overall_time_watched
-                            .saturating_add(time_watched);
-                    };
-                    time.clone_into(&mut library_item.state.time_offset);
-                    duration.clone_into(&mut library_item.state.duration);
-                    if library_item.state.flagged_watched == 0
-                        && library_item.state.time_watched as f64
-                            > library_item.state.duration as f64 * WATCHED_THRESHOLD_COEF
+                    // seek logging within the currently selected video/stream
+                    if seeking
+                        && library_item.state.video_id.as_ref() == Some(video_id)
+                        && library_item.r#type == "series"
+                        && time < &PLAYER_IGNORE_SEEK_AFTER
                     {
-                        library_item.state.flagged_watched = 1;
-                        library_item.state.times_watched =
-                            library_item.state.times_watched.saturating_add(1);
+                        self.seek_history.push(SeekLog {
+                            from: library_item.state.time_offset,
+                            to: *time,
+                        });
+                    }
+                    let just_watched =
+                        library_item.update_progress::<E>(Some(video_id), *time, *duration, 1000);
+                    if just_watched {
                         if let Some(watched_bit_field) = &self.watched {
                             let mut watched_bit_field = watched_bit_field.to_owned();
                             watched_bit_field.set_video(video_id, true);
                             library_item.state.watched = Some(watched_bit_field.into());
                         }
                     };
-                    if library_item.temp && library_item.state.times_watched == 0 {
-                        library_item.removed = true;
-                    };
-                    if library_item.removed {
-                        library_item.temp = true;
-                    };
                     if let Some(analytics_context) = &mut self.analytics_context {
                         library_item
                             .state
@@ -486,13 +614,49 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                         Effects::none()
                     };
 
+                    // the intro is detected by the duration, which might still be the one
+                    // of the previous video on Load
+                    let intro_outro_effects = if duration_changed {
+                        intro_outro_update::<E>(
+                            &mut self.intro_outro,
+                            ctx,
+                            self.selected.as_ref(),
+                            self.video_params.as_ref(),
+                            self.series_info.as_ref(),
+                            Some(library_item),
+                            &mut self.skip_gaps,
+                        )
+                    } else {
+                        Effects::none().unchanged()
+                    };
+
                     let push_to_library_effects =
                         push_to_library::<E>(&mut self.push_library_item_time, library_item);
 
-                    trakt_event_effects.join(push_to_library_effects)
+                    trakt_event_effects
+                        .join(intro_outro_effects)
+                        .join(push_to_library_effects)
                 }
                 _ => Effects::none().unchanged(),
-            },
+            }
+            .join(if self.still_watching_requested {
+                // the user might have fallen asleep, so the session isn't extended
+                Effects::none().unchanged()
+            } else {
+                session_update::<E>(
+                    &mut self.session,
+                    &self.selected,
+                    &self.library_item,
+                    *time,
+                    *duration,
+                    device,
+                )
+            })
+            .join(sleep_timer_update::<E>(
+                &mut self.sleep_timer,
+                &self.analytics_context,
+            ))
+            .join(Effects::msg(Msg::Internal(Internal::PlayerTimeChanged(*time))).unchanged()),
             Msg::Action(Action::Player(ActionPlayer::PausedChanged { paused }))
                 if self.selected.is_some() =>
             {
@@ -510,11 +674,13 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     }))
                     .unchanged()
                 } else if *paused {
+                    self.auto_advanced_count = 0;
                     Effects::msg(Msg::Event(Event::TraktPaused {
                         context: self.analytics_context.as_ref().cloned().unwrap_or_default(),
                     }))
                     .unchanged()
                 } else {
+                    self.auto_advanced_count = 0;
                     Effects::msg(Msg::Event(Event::TraktPlaying {
                         context: self.analytics_context.as_ref().cloned().unwrap_or_default(),
                     }))
@@ -527,7 +693,11 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     .unchanged(),
                     _ => Effects::none().unchanged(),
                 };
-                trakt_event_effects.join(update_library_item_effects)
+                let paused_effects =
+                    Effects::msg(Msg::Internal(Internal::PlayerPausedChanged(*paused))).unchanged();
+                trakt_event_effects
+                    .join(update_library_item_effects)
+                    .join(paused_effects)
             }
             Msg::Action(Action::Player(ActionPlayer::NextVideo)) => {
                 let seek_history_effects = seek_update::<E>(
@@ -557,13 +727,20 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     })
                     .unwrap_or(Effects::none().unchanged());
 
+                // the user has chosen to play the next video, unless the current one has ended
+                if !self.ended {
+                    self.auto_advancing = false;
+                }
+                self.next_video_requested = self.auto_advancing;
+
                 // Load will actually take care of loading the next video
                 seek_history_effects
                     .join(
                         Effects::msg(Msg::Event(Event::PlayerNextVideo {
                             context: self.analytics_context.as_ref().cloned().unwrap_or_default(),
                             is_binge_enabled: ctx.profile.settings.binge_watching,
-                            is_playing_next_video: self.next_video.is_some(),
+                            is_playing_next_video: self.next_video.is_some()
+                                || self.up_next_stream.is_some(),
                         }))
                         .unchanged(),
                     )
@@ -571,15 +748,65 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
             }
             Msg::Action(Action::Player(ActionPlayer::Ended)) if self.selected.is_some() => {
                 self.ended = true;
-                Effects::msg(Msg::Event(Event::PlayerEnded {
-                    context: self.analytics_context.as_ref().cloned().unwrap_or_default(),
-                    is_binge_enabled: ctx.profile.settings.binge_watching,
-                    is_playing_next_video: self.next_video.is_some(),
-                }))
-                .unchanged()
+                let sleep_timer_elapsed = self.sleep_timer == Some(SleepTimer::EndOfVideo);
+                let sleep_timer_effects = if sleep_timer_elapsed {
+                    self.sleep_timer = None;
+                    Effects::msg(Msg::Event(Event::PlayerSleepTimerElapsed {
+                        context: self.analytics_context.as_ref().cloned().unwrap_or_default(),
+                    }))
+                } else {
+                    Effects::none().unchanged()
+                };
+                // nothing else is played once the sleep timer has elapsed
+                // or while the user hasn't confirmed they're still watching
+                let is_playing_next_video = (self.next_video.is_some()
+                    || self.up_next_stream.is_some())
+                    && !sleep_timer_elapsed
+                    && !self.still_watching_requested;
+                self.auto_advancing = ctx.profile.settings.binge_watching && is_playing_next_video;
+                sleep_timer_effects.join(
+                    Effects::msg(Msg::Event(Event::PlayerEnded {
+                        context: self.analytics_context.as_ref().cloned().unwrap_or_default(),
+                        is_binge_enabled: ctx.profile.settings.binge_watching,
+                        is_playing_next_video,
+                    }))
+                    .unchanged(),
+                )
+            }
+            Msg::Action(Action::Player(ActionPlayer::StartSleepTimer { minutes })) => {
+                let sleep_timer = match minutes {
+                    Some(minutes) => SleepTimer::Until {
+                        time: E::now() + Duration::minutes(*minutes as i64),
+                    },
+                    None => SleepTimer::EndOfVideo,
+                };
+                eq_update(&mut self.sleep_timer, Some(sleep_timer))
+            }
+            Msg::Action(Action::Player(ActionPlayer::CancelSleepTimer)) => {
+                eq_update(&mut self.sleep_timer, None)
+            }
+            Msg::Action(Action::Player(ActionPlayer::ConfirmStillWatching)) => {
+                self.auto_advanced_count = 0;
+                eq_update(&mut self.still_watching_requested, false)
             }
             Msg::Internal(Internal::StreamsChanged(_)) => {
-                stream_state_update(&mut self.stream_state, &self.selected, &ctx.streams)
+                let stream_state_effects = stream_state_update(
+                    &mut self.stream_state,
+                    &self.selected,
+                    &ctx.streams,
+                    &self.language_preference,
+                );
+                let subtitles_tracks_effects = subtitles_tracks_update(
+                    &mut self.subtitles_tracks,
+                    &mut self.selected_subtitles_track,
+                    &self.selected,
+                    &self.video_params,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &self.language_preference,
+                    &ctx.profile.settings,
+                );
+                stream_state_effects.join(subtitles_tracks_effects)
             }
             Msg::Internal(Internal::ResourceRequestResult(request, result))
                 if self.selected.is_some() =>
@@ -616,6 +843,16 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 } else {
                     Effects::none().unchanged()
                 };
+                let subtitles_tracks_effects = subtitles_tracks_update(
+                    &mut self.subtitles_tracks,
+                    &mut self.selected_subtitles_track,
+                    &self.selected,
+                    &self.video_params,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &self.language_preference,
+                    &ctx.profile.settings,
+                );
 
                 let next_streams_effects = match self.next_streams.as_mut() {
                     Some(next_streams) if request.path.resource == STREAM_RESOURCE_NAME => {
@@ -626,6 +863,15 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     }
                     _ => Effects::none().unchanged(),
                 };
+                let up_next_streams_effects = match self.up_next_streams.as_mut() {
+                    Some(up_next_streams) if request.path.resource == STREAM_RESOURCE_NAME => {
+                        resource_update_with_vector_content::<E, _>(
+                            up_next_streams,
+                            ResourceAction::ResourceRequestResult { request, result },
+                        )
+                    }
+                    _ => Effects::none().unchanged(),
+                };
 
                 let next_video_effects = next_video_update(
                     &mut self.next_video,
@@ -648,6 +894,17 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     &ctx.profile.settings,
                 );
 
+                let up_next_effects = up_next_streams_effects.join(up_next_update::<E>(
+                    &mut self.up_next,
+                    &mut self.up_next_streams,
+                    &mut self.up_next_stream,
+                    &self.next_video,
+                    &self.selected,
+                    &self.meta_item,
+                    &ctx.play_queue,
+                    &ctx.profile.settings,
+                ));
+
                 let series_info_effects =
                     series_info_update(&mut self.series_info, &self.selected, &self.meta_item);
                 let library_item_effects = library_item_update::<E>(
@@ -693,14 +950,26 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 meta_item_effects
                     .join(update_streams_effects)
                     .join(subtitles_effects)
+                    .join(subtitles_tracks_effects)
                     .join(next_video_effects)
                     .join(next_streams_effects)
                     .join(next_stream_effects)
+                    .join(up_next_effects)
                     .join(series_info_effects)
                     .join(library_item_effects)
                     .join(watched_effects)
                     .join(skip_gaps_effects)
             }
+            Msg::Internal(Internal::PlayQueueChanged) => up_next_update::<E>(
+                &mut self.up_next,
+                &mut self.up_next_streams,
+                &mut self.up_next_stream,
+                &self.next_video,
+                &self.selected,
+                &self.meta_item,
+                &ctx.play_queue,
+                &ctx.profile.settings,
+            ),
             Msg::Internal(Internal::SkipGapsResult(skip_gaps_request, result)) => {
                 let skip_gaps_next = match result.to_owned() {
                     Ok(response) => Loadable::Ready(response),
@@ -714,7 +983,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
 
                 let intro_outro_effects = intro_outro_update::<E>(
                     &mut self.intro_outro,
-                    &ctx.profile,
+                    ctx,
                     self.selected.as_ref(),
                     self.video_params.as_ref(),
                     self.series_info.as_ref(),
@@ -728,7 +997,16 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 if let Some(analytics_context) = &mut self.analytics_context {
                     analytics_context.has_trakt = ctx.profile.has_trakt::<E>();
                 };
-                Effects::none().unchanged()
+                subtitles_tracks_update(
+                    &mut self.subtitles_tracks,
+                    &mut self.selected_subtitles_track,
+                    &self.selected,
+                    &self.video_params,
+                    &self.subtitles,
+                    &self.stream_state,
+                    &self.language_preference,
+                    &ctx.profile.settings,
+                )
             }
             _ => Effects::none().unchanged(),
         }
@@ -737,7 +1015,7 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
 
 /// We will push an [`Internal::UpdateLibraryItem`] message only if
 /// at least [`PUSH_TO_LIBRARY_EVERY`] time has passed since the last update.
-fn push_to_library<E: Env + 'static>(
+pub(crate) fn push_to_library<E: Env + 'static>(
     push_library_item_time: &mut DateTime<Utc>,
     library_item: &mut LibraryItem,
 ) -> Effects {
@@ -753,6 +1031,91 @@ fn push_to_library<E: Env + 'static>(
     }
 }
 
+/// Ends the sleep timer once its time has passed.
+fn sleep_timer_update<E: Env + 'static>(
+    sleep_timer: &mut Option<SleepTimer>,
+    analytics_context: &Option<AnalyticsContext>,
+) -> Effects {
+    match sleep_timer {
+        Some(SleepTimer::Until { time }) if *time <= E::now() => {
+            *sleep_timer = None;
+            Effects::msg(Msg::Event(Event::PlayerSleepTimerElapsed {
+                context: analytics_context.as_ref().cloned().unwrap_or_default(),
+            }))
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+/// Extends the current viewing session or starts a new one once another video is played.
+fn session_update<E: Env + 'static>(
+    session: &mut Option<WatchHistoryItem>,
+    selected: &Option<Selected>,
+    library_item: &Option<LibraryItem>,
+    time: u64,
+    duration: u64,
+    device: &str,
+) -> Effects {
+    match (selected, library_item) {
+        (
+            Some(Selected {
+                stream,
+                stream_request:
+                    Some(ResourceRequest {
+                        path: ResourcePath { id: video_id, .. },
+                        ..
+                    }),
+                ..
+            }),
+            Some(library_item),
+        ) => {
+            let now = E::now();
+            match session {
+                Some(session) if session.is_same_video(&library_item.id, video_id) => {
+                    session.time_watched = session
+                        .time_watched
+                        .saturating_add(1000.min(time.saturating_sub(session.to)));
+                    session.to = time;
+                    session.end = now;
+                    session.duration = duration;
+                    Effects::none().unchanged()
+                }
+                _ => {
+                    let session_effects = session_end(session);
+                    *session = Some(WatchHistoryItem {
+                        id: format!("{}:{}", video_id, now.timestamp_millis()),
+                        meta_id: library_item.id.to_owned(),
+                        r#type: library_item.r#type.to_owned(),
+                        name: library_item.name.to_owned(),
+                        poster: library_item.poster.to_owned(),
+                        video_id: video_id.to_owned(),
+                        stream_source: stream.source.to_owned(),
+                        start: now,
+                        end: now,
+                        from: time,
+                        to: time,
+                        time_watched: 0,
+                        duration,
+                        device: device.to_owned(),
+                    });
+                    session_effects
+                }
+            }
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn session_end(session: &mut Option<WatchHistoryItem>) -> Effects {
+    match session.take() {
+        Some(session) => Effects::msg(Msg::Internal(Internal::PlayerSessionEnded(Box::new(
+            session,
+        ))))
+        .unchanged(),
+        _ => Effects::none().unchanged(),
+    }
+}
+
 fn item_state_update(
     library_item: &mut Option<LibraryItem>,
     next_video: &Option<Video>,
@@ -783,6 +1146,7 @@ fn stream_state_update(
     state: &mut Option<StreamItemState>,
     selected: &Option<Selected>,
     streams: &StreamsBucket,
+    language_preference: &Option<LanguagePreference>,
 ) -> Effects {
     let next_state = match selected {
         Some(Selected {
@@ -794,16 +1158,33 @@ fn stream_state_update(
                 meta_id: meta_request.path.id.to_owned(),
                 video_id: stream_request.path.id.to_owned(),
             };
-            streams
+            let stream_state = streams
                 .items
                 .get(&key)
-                .and_then(|stream_item| stream_item.state.to_owned())
+                .and_then(|stream_item| stream_item.state.to_owned());
+            match language_preference {
+                Some(language_preference) => language_preference.apply(stream_state),
+                _ => stream_state,
+            }
         }
         _ => None,
     };
     eq_update(state, next_state)
 }
 
+fn language_preference_update(
+    language_preference: &mut Option<LanguagePreference>,
+    selected: &Option<Selected>,
+    language_preferences: &LanguagePreferencesBucket,
+) -> Effects {
+    let next_language_preference = selected
+        .as_ref()
+        .and_then(|selected| selected.meta_request.as_ref())
+        .and_then(|meta_request| language_preferences.items.get(&meta_request.path.id))
+        .cloned();
+    eq_update(language_preference, next_language_preference)
+}
+
 fn next_video_update(
     video: &mut Option<Video>,
     stream: &Option<Stream>,
@@ -879,35 +1260,101 @@ where
     match next_video {
         Some(next_video) => {
             stream_request.path.id.clone_from(&next_video.id);
-
-            match next_streams.as_mut() {
-                Some(next_streams) => resource_update_with_vector_content::<E, _>(
-                    next_streams,
-                    ResourceAction::ResourceRequested {
-                        request: &stream_request,
-                    },
-                ),
-                None => {
-                    let mut new_next_streams = ResourceLoadable {
-                        request: stream_request.to_owned(),
-                        content: None,
-                    };
-                    let next_streams_effects = resource_update_with_vector_content::<E, _>(
-                        &mut new_next_streams,
-                        ResourceAction::ResourceRequested {
-                            request: &stream_request,
-                        },
-                    );
-
-                    *next_streams = Some(new_next_streams);
-                    next_streams_effects
-                }
-            }
+            streams_requested_update::<E>(next_streams, &stream_request)
         }
         None => Effects::none().unchanged(),
     }
 }
 
+fn streams_requested_update<E>(
+    streams: &mut Option<ResourceLoadable<Vec<Stream>>>,
+    stream_request: &ResourceRequest,
+) -> Effects
+where
+    E: Env + 'static,
+{
+    match streams.as_mut() {
+        Some(streams) => resource_update_with_vector_content::<E, _>(
+            streams,
+            ResourceAction::ResourceRequested {
+                request: stream_request,
+            },
+        ),
+        None => {
+            let mut new_streams = ResourceLoadable {
+                request: stream_request.to_owned(),
+                content: None,
+            };
+            let streams_effects = resource_update_with_vector_content::<E, _>(
+                &mut new_streams,
+                ResourceAction::ResourceRequested {
+                    request: stream_request,
+                },
+            );
+
+            *streams = Some(new_streams);
+            streams_effects
+        }
+    }
+}
+
+/// Picks the head of the play queue once the meta item is loaded and there's no next video of it.
+///
+/// The streams of the queued video are requested from the addon of the selected stream,
+/// so a stream from the same binge group can be played right away.
+#[allow(clippy::too_many_arguments)]
+fn up_next_update<E>(
+    up_next: &mut Option<PlayQueueItem>,
+    up_next_streams: &mut Option<ResourceLoadable<Vec<Stream>>>,
+    up_next_stream: &mut Option<Stream>,
+    next_video: &Option<Video>,
+    selected: &Option<Selected>,
+    meta_item: &Option<ResourceLoadable<MetaItem>>,
+    play_queue: &PlayQueueBucket,
+    settings: &ProfileSettings,
+) -> Effects
+where
+    E: Env + 'static,
+{
+    let next_up_next = match (selected, meta_item) {
+        (
+            Some(Selected {
+                stream_request: Some(stream_request),
+                meta_request: Some(meta_request),
+                ..
+            }),
+            Some(ResourceLoadable {
+                content: Some(Loadable::Ready(_) | Loadable::Err(_)),
+                ..
+            }),
+        ) if next_video.is_none() => play_queue
+            .next(&meta_request.path.id, &stream_request.path.id)
+            .cloned(),
+        _ => None,
+    };
+    let up_next_effects = eq_update(up_next, next_up_next);
+    let up_next_streams_effects = match (selected, up_next.as_ref()) {
+        (
+            Some(Selected {
+                stream_request: Some(stream_request),
+                ..
+            }),
+            Some(up_next),
+        ) => {
+            let mut stream_request = stream_request.to_owned();
+            stream_request.path.r#type.clone_from(&up_next.r#type);
+            stream_request.path.id.clone_from(&up_next.video_id);
+            streams_requested_update::<E>(up_next_streams, &stream_request)
+        }
+        _ => eq_update(up_next_streams, None),
+    };
+    let up_next_stream_effects =
+        next_stream_update(up_next_stream, up_next_streams, selected, settings);
+    up_next_effects
+        .join(up_next_streams_effects)
+        .join(up_next_stream_effects)
+}
+
 fn next_stream_update(
     stream: &mut Option<Stream>,
     next_streams: &Option<ResourceLoadable<Vec<Stream>>>,
@@ -1045,7 +1492,7 @@ fn subtitles_update<E: Env + 'static>(
     subtitles: &mut Vec<ResourceLoadable<Vec<Subtitles>>>,
     selected: &Option<Selected>,
     video_params: &Option<VideoParams>,
-    addons: &[Descriptor],
+    profile: &Profile,
 ) -> Effects {
     match (selected, video_params) {
         (
@@ -1057,25 +1504,86 @@ fn subtitles_update<E: Env + 'static>(
         ) => resources_update_with_vector_content::<E, _>(
             subtitles,
             ResourcesAction::force_request(
-                &AggrRequest::AllOfResource(ResourcePath {
-                    extra: subtitles_path
-                        .extra
-                        .to_owned()
-                        .extend_one(&VIDEO_HASH_EXTRA_PROP, video_params.hash.to_owned())
-                        .extend_one(
-                            &VIDEO_SIZE_EXTRA_PROP,
-                            video_params.size.as_ref().map(|size| size.to_string()),
-                        )
-                        .extend_one(&VIDEO_FILENAME_EXTRA_PROP, video_params.filename.to_owned()),
-                    ..subtitles_path.to_owned()
-                }),
-                addons,
+                &AggrRequest::AllOfResource {
+                    path: ResourcePath {
+                        extra: subtitles_path
+                            .extra
+                            .to_owned()
+                            .extend_one(&VIDEO_HASH_EXTRA_PROP, video_params.hash.to_owned())
+                            .extend_one(
+                                &VIDEO_SIZE_EXTRA_PROP,
+                                video_params.size.as_ref().map(|size| size.to_string()),
+                            )
+                            .extend_one(
+                                &VIDEO_FILENAME_EXTRA_PROP,
+                                video_params.filename.to_owned(),
+                            ),
+                        ..subtitles_path.to_owned()
+                    },
+                    priorities: &profile.settings.addon_priorities,
+                },
+                &profile.addons,
             ),
         ),
         _ => eq_update(subtitles, vec![]),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn subtitles_tracks_update(
+    subtitles_tracks: &mut Vec<SubtitlesTrack>,
+    selected_subtitles_track: &mut Option<SubtitlesTrack>,
+    selected: &Option<Selected>,
+    video_params: &Option<VideoParams>,
+    subtitles: &[ResourceLoadable<Vec<Subtitles>>],
+    stream_state: &Option<StreamItemState>,
+    language_preference: &Option<LanguagePreference>,
+    settings: &ProfileSettings,
+) -> Effects {
+    let next_subtitles_tracks = match selected {
+        Some(selected) => SubtitlesTrack::merge(
+            &selected.stream,
+            subtitles
+                .iter()
+                .filter_map(|subtitles| match &subtitles.content {
+                    Some(Loadable::Ready(content)) => {
+                        Some((&subtitles.request.base, content.as_slice()))
+                    }
+                    _ => None,
+                }),
+            video_params
+                .as_ref()
+                .and_then(|video_params| video_params.hash.as_deref()),
+            video_params
+                .as_ref()
+                .and_then(|video_params| video_params.size),
+            settings,
+        ),
+        _ => vec![],
+    };
+    let next_selected_subtitles_track = match stream_state {
+        // the tracks applied from the language preference have no id
+        Some(StreamItemState {
+            subtitle_track: Some(subtitle_track),
+            ..
+        }) if !subtitle_track.id.is_empty() => stream_state
+            .as_ref()
+            .and_then(|stream_state| SubtitlesTrack::select(&next_subtitles_tracks, stream_state)),
+        // no subtitles were chosen for this stream, use the ones of the series
+        _ => language_preference
+            .as_ref()
+            .and_then(|language_preference| language_preference.subtitle_language.as_ref())
+            .and_then(|language| {
+                SubtitlesTrack::select_by_language(&next_subtitles_tracks, language)
+            }),
+    }
+    .cloned();
+    eq_update(subtitles_tracks, next_subtitles_tracks).join(eq_update(
+        selected_subtitles_track,
+        next_selected_subtitles_track,
+    ))
+}
+
 fn seek_update<E: Env + 'static>(
     selected: Option<&Selected>,
     video_params: Option<&VideoParams>,
@@ -1130,8 +1638,23 @@ fn seek_update<E: Env + 'static>(
         }
         _ => Effects::none().unchanged(),
     };
+    // learning the intros locally doesn't depend on the stream
+    let seek_history_effects = match (seek_history.is_empty(), series_info, library_item) {
+        (false, Some(series_info), Some(library_item)) => {
+            Effects::msg(Msg::Internal(Internal::SeekHistoryRecorded {
+                meta_id: library_item.id.to_owned(),
+                series_info: series_info.to_owned(),
+                duration: library_item.state.duration,
+                seek_history: seek_history.to_owned(),
+            }))
+            .unchanged()
+        }
+        _ => Effects::none().unchanged(),
+    };
 
-    seek_request_effects.join(eq_update(seek_history, vec![]))
+    seek_request_effects
+        .join(seek_history_effects)
+        .join(eq_update(seek_history, vec![]))
 }
 
 fn push_seek_to_api<E: Env + 'static>(seek_log_req: SeekLogRequest) -> Effect {
@@ -1165,7 +1688,7 @@ fn calculate_outro(library_item: &LibraryItem, closest_duration: u64, closest_ou
 
 fn intro_outro_update<E: Env + 'static>(
     intro_outro: &mut Option<IntroOutro>,
-    profile: &Profile,
+    ctx: &Ctx,
     selected: Option<&Selected>,
     video_params: Option<&VideoParams>,
     series_info: Option<&SeriesInfo>,
@@ -1173,7 +1696,7 @@ fn intro_outro_update<E: Env + 'static>(
     skip_gaps: &mut Option<(SkipGapsRequest, Loadable<SkipGapsResponse, CtxError>)>,
 ) -> Effects {
     let skip_gaps_effects = skip_gaps_update::<E>(
-        profile,
+        &ctx.profile,
         selected,
         video_params,
         series_info,
@@ -1181,6 +1704,19 @@ fn intro_outro_update<E: Env + 'static>(
         skip_gaps,
     );
 
+    // the intro learned on this device is used when the skip gaps have no intro
+    let local_intro = match (series_info, library_item) {
+        (Some(series_info), Some(library_item)) => {
+            ctx.intros
+                .items
+                .get(&library_item.id)
+                .and_then(|series_intros| {
+                    series_intros.detect(series_info, library_item.state.duration)
+                })
+        }
+        _ => None,
+    };
+
     let intro_outro_effects = match (skip_gaps, library_item) {
         (Some((_, Loadable::Ready(response))), Some(library_item)) => {
             let outro_time = {
@@ -1242,12 +1778,21 @@ fn intro_outro_update<E: Env + 'static>(
             eq_update(
                 intro_outro,
                 Some(IntroOutro {
-                    intro: intro_time,
+                    intro: intro_time.or(local_intro),
                     outro: outro_time,
                 }),
             )
         }
-        _ => Effects::none().unchanged(),
+        _ => match local_intro {
+            Some(local_intro) => eq_update(
+                intro_outro,
+                Some(IntroOutro {
+                    intro: Some(local_intro),
+                    outro: None,
+                }),
+            ),
+            _ => Effects::none().unchanged(),
+        },
     };
 
     skip_gaps_effects.join(intro_outro_effects)
@@ -0,0 +1,473 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    constants::{GENRES_LINK_CATEGORY, META_RESOURCE_NAME},
+    models::{
+        common::{eq_update, resource_update, Loadable, ResourceAction, ResourceLoadable},
+        ctx::Ctx,
+    },
+    runtime::{
+        msg::{Action, ActionLoad, Internal, Msg},
+        Effects, Env, UpdateWithCtx,
+    },
+    types::{
+        addon::{AggrRequest, ResourcePath, ResourceRequest},
+        library::{LibraryBucket, LibraryItem, LibraryItemId},
+        profile::Profile,
+        resource::{MetaItem, Video},
+        watch_history::WatchHistoryBucket,
+    },
+};
+
+/// The most watched items which the genres and videos are fetched for
+const MAX_META_ITEMS: usize = 50;
+const MAX_TOP_GENRES: usize = 10;
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Totals {
+    /// In milliseconds
+    pub time_watched: u64,
+    pub times_watched: u64,
+    pub movies_watched: usize,
+    pub series_watched: usize,
+    /// The watched videos of the series which videos are known
+    pub videos_watched: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Period {
+    /// The first day of the period
+    pub start: NaiveDate,
+    /// In milliseconds
+    pub time_watched: u64,
+    pub sessions: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Genre {
+    pub name: String,
+    /// In milliseconds
+    pub time_watched: u64,
+    pub items: usize,
+}
+
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Streak {
+    /// The consecutive days up to today or yesterday on which something was watched
+    pub current: u32,
+    pub longest: u32,
+}
+
+/// The contribution of a single library item to the stats.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ItemStats {
+    /// The modification time of the library item the stats were computed from
+    pub mtime: DateTime<Utc>,
+    pub r#type: String,
+    /// In milliseconds
+    pub time_watched: u64,
+    pub times_watched: u32,
+    pub last_watched: Option<DateTime<Utc>>,
+    pub genres: Vec<String>,
+    /// `None` when the videos of the item are not known
+    pub videos_watched: Option<usize>,
+    pub completed: bool,
+}
+
+impl ItemStats {
+    pub fn new(library_item: &LibraryItem, meta_item: Option<&MetaItem>) -> Self {
+        let genres = meta_item
+            .map(|meta_item| {
+                meta_item
+                    .preview
+                    .links
+                    .iter()
+                    .filter(|link| link.category == GENRES_LINK_CATEGORY)
+                    .map(|link| link.name.to_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        // specials are not needed for completing a series
+        let videos_count = meta_item
+            .map(|meta_item| {
+                meta_item
+                    .videos
+                    .iter()
+                    .filter(|video| is_regular_video(video))
+                    .count()
+            })
+            .filter(|videos_count| *videos_count > 0);
+        let videos_watched = meta_item
+            .filter(|_| videos_count.is_some())
+            .map(|meta_item| {
+                let watched = library_item.state.watched_bitfield(&meta_item.videos);
+                meta_item
+                    .videos
+                    .iter()
+                    .filter(|video| is_regular_video(video) && watched.get_video(&video.id))
+                    .count()
+            });
+        let completed = library_item.r#type == "series"
+            && videos_count.is_some()
+            && videos_count == videos_watched;
+        ItemStats {
+            mtime: library_item.mtime,
+            r#type: library_item.r#type.to_owned(),
+            time_watched: library_item.state.overall_time_watched,
+            times_watched: library_item.state.times_watched,
+            last_watched: library_item.state.last_watched,
+            genres,
+            videos_watched,
+            completed,
+        }
+    }
+    pub fn is_watched(&self) -> bool {
+        self.time_watched > 0 || self.times_watched > 0
+    }
+}
+
+fn is_regular_video(video: &Video) -> bool {
+    video
+        .series_info
+        .as_ref()
+        .map_or(true, |series_info| series_info.season != 0)
+}
+
+/// Viewing statistics of the user, computed from the library and the watch history.
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    pub totals: Totals,
+    /// Time watched per week starting on Monday, the most recent first
+    pub weeks: Vec<Period>,
+    /// Time watched per year, the most recent first
+    pub years: Vec<Period>,
+    /// The genres of the most watched items, ordered by time watched
+    pub top_genres: Vec<Genre>,
+    /// The series which all videos have been watched
+    pub completed_series: Vec<LibraryItemId>,
+    pub streak: Streak,
+    /// The meta items of the most watched library items
+    #[serde(skip_serializing)]
+    pub meta_items: Vec<ResourceLoadable<MetaItem>>,
+    /// The stats of each watched library item, which are updated only when the item changes
+    #[serde(skip_serializing)]
+    pub items: HashMap<LibraryItemId, ItemStats>,
+    #[serde(skip_serializing)]
+    pub loaded: bool,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for Stats {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::Stats)) => {
+                self.loaded = true;
+                self.library_update::<E>(ctx)
+            }
+            Msg::Internal(Internal::LibraryChanged(_)) if self.loaded => {
+                self.library_update::<E>(ctx)
+            }
+            Msg::Internal(Internal::WatchHistoryChanged) if self.loaded => {
+                self.history_update(&ctx.watch_history, &ctx.library, E::now())
+            }
+            Msg::Internal(Internal::ResourceRequestResult(request, result))
+                if request.path.resource == META_RESOURCE_NAME =>
+            {
+                let meta_item = match self
+                    .meta_items
+                    .iter_mut()
+                    .find(|meta_item| meta_item.request == *request)
+                {
+                    Some(meta_item) => meta_item,
+                    _ => return Effects::none().unchanged(),
+                };
+                let meta_item_effects = resource_update::<E, _>(
+                    meta_item,
+                    ResourceAction::ResourceRequestResult { request, result },
+                );
+                // only the stats of the item which the meta item is of are recomputed
+                let item_changed =
+                    match (ctx.library.items.get(&request.path.id), &meta_item.content) {
+                        (Some(library_item), Some(Loadable::Ready(meta_item))) => {
+                            let item_stats = ItemStats::new(library_item, Some(meta_item));
+                            self.items
+                                .insert(library_item.id.to_owned(), item_stats.to_owned())
+                                != Some(item_stats)
+                        }
+                        _ => false,
+                    };
+                let aggregates_effects = if item_changed {
+                    self.aggregates_update()
+                } else {
+                    Effects::none().unchanged()
+                };
+                meta_item_effects.join(aggregates_effects)
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+impl Stats {
+    fn library_update<E: Env + 'static>(&mut self, ctx: &Ctx) -> Effects {
+        let meta_items_effects =
+            meta_items_update::<E>(&mut self.meta_items, &ctx.library, &ctx.profile);
+        let aggregates_effects = if items_update(&mut self.items, &ctx.library, &self.meta_items) {
+            self.aggregates_update()
+        } else {
+            Effects::none().unchanged()
+        };
+        let history_effects = self.history_update(&ctx.watch_history, &ctx.library, E::now());
+        meta_items_effects
+            .join(aggregates_effects)
+            .join(history_effects)
+    }
+    fn aggregates_update(&mut self) -> Effects {
+        let watched_items = self
+            .items
+            .iter()
+            .filter(|(_, item_stats)| item_stats.is_watched())
+            .collect::<Vec<_>>();
+        let next_totals = Totals {
+            time_watched: watched_items
+                .iter()
+                .map(|(_, item_stats)| item_stats.time_watched)
+                .sum(),
+            times_watched: watched_items
+                .iter()
+                .map(|(_, item_stats)| item_stats.times_watched as u64)
+                .sum(),
+            movies_watched: watched_items
+                .iter()
+                .filter(|(_, item_stats)| item_stats.r#type == "movie")
+                .count(),
+            series_watched: watched_items
+                .iter()
+                .filter(|(_, item_stats)| item_stats.r#type == "series")
+                .count(),
+            videos_watched: watched_items
+                .iter()
+                .filter_map(|(_, item_stats)| item_stats.videos_watched)
+                .sum(),
+        };
+        let next_top_genres = watched_items
+            .iter()
+            .flat_map(|(_, item_stats)| {
+                item_stats
+                    .genres
+                    .iter()
+                    .map(|genre| (genre, item_stats.time_watched))
+            })
+            .fold(
+                BTreeMap::<&String, (u64, usize)>::new(),
+                |mut genres, (genre, time_watched)| {
+                    let entry = genres.entry(genre).or_default();
+                    entry.0 = entry.0.saturating_add(time_watched);
+                    entry.1 += 1;
+                    genres
+                },
+            )
+            .into_iter()
+            .map(|(name, (time_watched, items))| Genre {
+                name: name.to_owned(),
+                time_watched,
+                items,
+            })
+            .sorted_by(|a, b| b.time_watched.cmp(&a.time_watched))
+            .take(MAX_TOP_GENRES)
+            .collect::<Vec<_>>();
+        let next_completed_series = watched_items
+            .iter()
+            .filter(|(_, item_stats)| item_stats.completed)
+            .map(|(id, _)| id.to_string())
+            .sorted()
+            .collect::<Vec<_>>();
+        eq_update(&mut self.totals, next_totals)
+            .join(eq_update(&mut self.top_genres, next_top_genres))
+            .join(eq_update(&mut self.completed_series, next_completed_series))
+    }
+    fn history_update(
+        &mut self,
+        watch_history: &WatchHistoryBucket,
+        library: &LibraryBucket,
+        now: DateTime<Utc>,
+    ) -> Effects {
+        let periods = |start_of: fn(NaiveDate) -> NaiveDate| {
+            watch_history
+                .items
+                .iter()
+                .fold(
+                    BTreeMap::<NaiveDate, (u64, usize)>::new(),
+                    |mut periods, item| {
+                        let entry = periods
+                            .entry(start_of(item.start.date_naive()))
+                            .or_default();
+                        entry.0 = entry.0.saturating_add(item.time_watched);
+                        entry.1 += 1;
+                        periods
+                    },
+                )
+                .into_iter()
+                .rev()
+                .map(|(start, (time_watched, sessions))| Period {
+                    start,
+                    time_watched,
+                    sessions,
+                })
+                .collect::<Vec<_>>()
+        };
+        let next_weeks =
+            periods(|date| date - Duration::days(date.weekday().num_days_from_monday() as i64));
+        let next_years = periods(|date| date.with_ordinal(1).expect("first day of the year"));
+        // the days with a session or on which a library item was last watched
+        let days = watch_history
+            .items
+            .iter()
+            .map(|item| item.start.date_naive())
+            .chain(
+                library
+                    .items
+                    .values()
+                    .filter(|library_item| library_item.state.overall_time_watched > 0)
+                    .filter_map(|library_item| library_item.state.last_watched)
+                    .map(|last_watched| last_watched.date_naive()),
+            )
+            .collect::<HashSet<_>>();
+        let next_streak = streak(&days, now.date_naive());
+        eq_update(&mut self.weeks, next_weeks)
+            .join(eq_update(&mut self.years, next_years))
+            .join(eq_update(&mut self.streak, next_streak))
+    }
+}
+
+fn streak(days: &HashSet<NaiveDate>, today: NaiveDate) -> Streak {
+    let (longest, _) =
+        days.iter()
+            .sorted()
+            .fold((0, None::<(NaiveDate, u32)>), |(longest, run), day| {
+                let length = match run {
+                    Some((previous, length)) if *day - previous == Duration::days(1) => length + 1,
+                    _ => 1,
+                };
+                (longest.max(length), Some((*day, length)))
+            });
+    let yesterday = today - Duration::days(1);
+    let last_day = if days.contains(&today) {
+        Some(today)
+    } else if days.contains(&yesterday) {
+        Some(yesterday)
+    } else {
+        None
+    };
+    let current = last_day
+        .map(|last_day| {
+            (0..)
+                .take_while(|offset| days.contains(&(last_day - Duration::days(*offset))))
+                .count() as u32
+        })
+        .unwrap_or_default();
+    Streak { current, longest }
+}
+
+/// Recomputes the stats only of the library items which have changed since the last time.
+///
+/// Returns `true` if any of them has changed.
+fn items_update(
+    items: &mut HashMap<LibraryItemId, ItemStats>,
+    library: &LibraryBucket,
+    meta_items: &[ResourceLoadable<MetaItem>],
+) -> bool {
+    let len = items.len();
+    items.retain(|id, _| library.items.contains_key(id));
+    let mut changed = items.len() != len;
+    for library_item in library.items.values() {
+        if items
+            .get(&library_item.id)
+            .is_some_and(|item_stats| item_stats.mtime == library_item.mtime)
+        {
+            continue;
+        }
+        let meta_item = meta_items
+            .iter()
+            .find(|meta_item| meta_item.request.path.id == library_item.id)
+            .and_then(|meta_item| match &meta_item.content {
+                Some(Loadable::Ready(meta_item)) => Some(meta_item),
+                _ => None,
+            });
+        let item_stats = ItemStats::new(library_item, meta_item);
+        if items.get(&library_item.id) != Some(&item_stats) {
+            items.insert(library_item.id.to_owned(), item_stats);
+            changed = true;
+        }
+    }
+    changed
+}
+
+fn meta_items_update<E: Env + 'static>(
+    meta_items: &mut Vec<ResourceLoadable<MetaItem>>,
+    library: &LibraryBucket,
+    profile: &Profile,
+) -> Effects {
+    let requests = library
+        .items
+        .values()
+        .filter(|library_item| library_item.state.overall_time_watched > 0)
+        .sorted_by(|a, b| {
+            b.state
+                .overall_time_watched
+                .cmp(&a.state.overall_time_watched)
+        })
+        .take(MAX_META_ITEMS)
+        .filter_map(|library_item| {
+            // only the first addon providing the meta item is requested
+            AggrRequest::AllOfResource {
+                path: ResourcePath::without_extra(
+                    META_RESOURCE_NAME,
+                    &library_item.r#type,
+                    &library_item.id,
+                ),
+                priorities: &profile.settings.addon_priorities,
+            }
+            .plan(&profile.addons)
+            .into_iter()
+            .next()
+            .map(|(_, request)| request)
+        })
+        .collect::<Vec<ResourceRequest>>();
+    let (next_meta_items, effects) = requests.into_iter().fold(
+        (vec![], Effects::none().unchanged()),
+        |(mut next_meta_items, effects), request| {
+            let effects = match meta_items
+                .iter()
+                .find(|meta_item| meta_item.request == request)
+            {
+                Some(meta_item) => {
+                    next_meta_items.push(meta_item.to_owned());
+                    effects
+                }
+                _ => {
+                    let mut meta_item = ResourceLoadable {
+                        request: request.to_owned(),
+                        content: None,
+                    };
+                    let meta_item_effects = resource_update::<E, _>(
+                        &mut meta_item,
+                        ResourceAction::ResourceRequested { request: &request },
+                    );
+                    next_meta_items.push(meta_item);
+                    effects.join(meta_item_effects)
+                }
+            };
+            (next_meta_items, effects)
+        },
+    );
+    effects.join(eq_update(meta_items, next_meta_items))
+}
@@ -17,9 +17,11 @@ use crate::types::addon::ResourcePath;
 use crate::types::api::SuccessResponse;
 use crate::types::empty_string_as_null;
 use crate::types::profile::{AuthKey, Profile};
+use crate::types::resource::Stream;
 use crate::types::streaming_server::{
     CreateMagnetRequest, CreateTorrentBlobRequest, DeviceInfo, GetHTTPSResponse, NetworkInfo,
-    Settings, SettingsResponse, Statistics, StatisticsRequest, TorrentStatisticsRequest,
+    PlaybackCapabilities, Settings, SettingsResponse, Statistics, StatisticsRequest,
+    StreamPlayback, TorrentStatisticsRequest,
 };
 use crate::types::torrent::InfoHash;
 
@@ -48,6 +50,8 @@ pub struct StreamingServer {
     pub playback_devices: Loadable<Vec<PlaybackDevice>, EnvError>,
     pub network_info: Loadable<NetworkInfo, EnvError>,
     pub device_info: Loadable<DeviceInfo, EnvError>,
+    /// Reported by the host, used to decide whether a stream needs transcoding.
+    pub playback_capabilities: PlaybackCapabilities,
     pub torrent: Option<(InfoHash, Loadable<ResourcePath, EnvError>)>,
     /// [`Loadable::Loading`] is used only on the first statistics request.
     pub statistics: Option<Loadable<Statistics, EnvError>>,
@@ -73,12 +77,37 @@ impl StreamingServer {
                 playback_devices: Loadable::Loading,
                 network_info: Loadable::Loading,
                 device_info: Loadable::Loading,
+                playback_capabilities: PlaybackCapabilities::default(),
                 torrent: None,
                 statistics: None,
             },
             effects.unchanged(),
         )
     }
+    /// The server's `transcode_profile`, if the device supports it.
+    pub fn hardware_acceleration(&self) -> Option<&str> {
+        match (&self.settings, &self.device_info) {
+            (Loadable::Ready(settings), Loadable::Ready(device_info)) => {
+                settings.transcode_profile.as_deref().filter(|profile| {
+                    device_info
+                        .available_hardware_accelerations
+                        .iter()
+                        .any(|acceleration| acceleration == profile)
+                })
+            }
+            _ => None,
+        }
+    }
+    /// Decides how the stream should be played on the host:
+    /// directly, remuxed or transcoded by the streaming server.
+    pub fn stream_playback(&self, stream: &Stream) -> Option<StreamPlayback> {
+        StreamPlayback::new(
+            stream,
+            self.base_url.as_ref(),
+            &self.playback_capabilities,
+            self.hardware_acceleration(),
+        )
+    }
 }
 
 impl<E: Env + 'static> UpdateWithCtx<E> for StreamingServer {
@@ -219,6 +248,9 @@ impl<E: Env + 'static> UpdateWithCtx<E> for StreamingServer {
                     _ => Effects::none().unchanged(),
                 }
             }
+            Msg::Action(Action::StreamingServer(
+                ActionStreamingServer::UpdatePlaybackCapabilities(capabilities),
+            )) => eq_update(&mut self.playback_capabilities, capabilities.to_owned()),
             Msg::Internal(Internal::ProfileChanged)
                 if self.selected.transport_url != ctx.profile.settings.streaming_server_url =>
             {
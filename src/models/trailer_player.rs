@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::models::common::eq_update;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
+use crate::runtime::{Effects, Env, UpdateWithCtx};
+use crate::types::profile::Settings;
+use crate::types::resource::Stream;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    /// One of the `trailer_streams` of the meta item or the video
+    pub stream: Stream,
+    /// Whether the trailer is played automatically on the detail page,
+    /// it's played only if the trailer autoplay setting is enabled
+    #[serde(default)]
+    pub autoplay: bool,
+}
+
+/// Playback of trailers.
+///
+/// Unlike the [`Player`](crate::models::player::Player), nothing about the playback
+/// is stored: the library items, the streams and the seek logs are left untouched.
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TrailerPlayer {
+    pub selected: Option<Selected>,
+    /// The url to play the trailer from, YouTube trailers are played through the streaming server.
+    ///
+    /// `None` when the trailer can't be played or it's an autoplay which is disabled.
+    pub stream_url: Option<Url>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for TrailerPlayer {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::TrailerPlayer(selected))) => {
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let stream_url_effects =
+                    stream_url_update(&mut self.stream_url, &self.selected, &ctx.profile.settings);
+                selected_effects.join(stream_url_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let stream_url_effects = eq_update(&mut self.stream_url, None);
+                selected_effects.join(stream_url_effects)
+            }
+            Msg::Internal(Internal::ProfileChanged) => {
+                stream_url_update(&mut self.stream_url, &self.selected, &ctx.profile.settings)
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+fn stream_url_update(
+    stream_url: &mut Option<Url>,
+    selected: &Option<Selected>,
+    settings: &Settings,
+) -> Effects {
+    let next_stream_url = selected
+        .as_ref()
+        .filter(|selected| !selected.autoplay || settings.trailer_autoplay)
+        .and_then(|selected| {
+            selected
+                .stream
+                .streaming_url(Some(&settings.streaming_server_url))
+        });
+    eq_update(stream_url, next_stream_url)
+}
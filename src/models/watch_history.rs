@@ -0,0 +1,63 @@
+use chrono::NaiveDate;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    models::{common::eq_update, ctx::Ctx},
+    runtime::{
+        msg::{Internal, Msg},
+        Effects, Env, UpdateWithCtx,
+    },
+    types::watch_history::{WatchHistoryBucket, WatchHistoryItem},
+};
+
+/// The viewing sessions of a single day
+#[derive(Clone, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Day {
+    /// The date the sessions started at, in UTC
+    pub date: NaiveDate,
+    /// The sessions, the most recent first
+    pub items: Vec<WatchHistoryItem>,
+}
+
+/// The watch history of the user grouped by day
+#[derive(Default, Clone, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchHistory {
+    /// The days, the most recent first
+    pub days: Vec<Day>,
+}
+
+impl WatchHistory {
+    pub fn new(watch_history: &WatchHistoryBucket) -> (Self, Effects) {
+        let mut days = vec![];
+        let effects = days_update(&mut days, watch_history);
+        (Self { days }, effects.unchanged())
+    }
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for WatchHistory {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Internal(Internal::WatchHistoryChanged) => {
+                days_update(&mut self.days, &ctx.watch_history)
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+fn days_update(days: &mut Vec<Day>, watch_history: &WatchHistoryBucket) -> Effects {
+    let next_days = watch_history
+        .items
+        .iter()
+        .group_by(|item| item.start.date_naive())
+        .into_iter()
+        .map(|(date, items)| Day {
+            date,
+            items: items.cloned().collect(),
+        })
+        .collect::<Vec<_>>();
+    eq_update(days, next_days)
+}
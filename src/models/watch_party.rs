@@ -0,0 +1,525 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use derivative::Derivative;
+use futures::{FutureExt, TryFutureExt};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::models::common::eq_update;
+use crate::models::ctx::Ctx;
+use crate::models::player::Selected as PlayerSelected;
+use crate::runtime::msg::{Action, ActionLoad, ActionWatchParty, Event, Internal, Msg};
+use crate::runtime::{Effect, EffectFuture, Effects, Env, EnvError, EnvFutureExt, UpdateWithCtx};
+use crate::types::resource::Stream;
+use crate::types::watch_party::{WatchPartyCommand, WatchPartyEnvelope, WatchPartyMessage};
+
+/// Differences from the playback of the leader up to this are tolerated
+/// and larger jumps in the time of the player are considered seeking, in milliseconds.
+const MAX_DRIFT: u64 = 3_000;
+/// Members which haven't sent anything for this long are considered gone.
+static MEMBER_TIMEOUT: Lazy<Duration> = Lazy::new(|| Duration::seconds(30));
+/// The interval in which the playback is reported to the other members.
+static SEND_STATE_EVERY: Lazy<Duration> = Lazy::new(|| Duration::seconds(5));
+/// Commands which the player hasn't applied for this long are considered dropped,
+/// so the same action of the user is sent to the party again.
+static PENDING_TIMEOUT: Lazy<Duration> = Lazy::new(|| Duration::seconds(5));
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Selected {
+    /// The url of the party, which the transport is chosen by
+    pub party_url: Url,
+    /// The id of this member, unique in the party
+    pub member_id: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Member {
+    pub id: String,
+    /// The sequence number of the member's join message
+    pub joined: u64,
+    pub last_seen: DateTime<Utc>,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Playback {
+    /// In milliseconds, `None` until the player reports it
+    pub time: Option<u64>,
+    pub paused: bool,
+    /// When the time was reported
+    pub updated: DateTime<Utc>,
+}
+
+/// A received command which the player was asked to apply.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PendingCommand {
+    pub command: WatchPartyCommand,
+    pub received: DateTime<Utc>,
+}
+
+impl Playback {
+    /// The time the playback is expected to be at, in milliseconds.
+    pub fn time_at(&self, now: DateTime<Utc>) -> Option<u64> {
+        if self.paused {
+            self.time
+        } else {
+            let elapsed = now.signed_duration_since(self.updated).num_milliseconds();
+            self.time
+                .map(|time| time.saturating_add(elapsed.max(0) as u64))
+        }
+    }
+}
+
+/// Playback synchronized with the other members of a party.
+///
+/// The member who joined first leads the party and the others correct their drift from it,
+/// while any member can play, pause, seek or change the stream for everyone.
+#[derive(Clone, Derivative, Serialize, Debug)]
+#[derivative(Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchParty {
+    pub selected: Option<Selected>,
+    pub members: Vec<Member>,
+    pub leader: Option<String>,
+    /// The playback of the local player
+    pub playback: Option<Playback>,
+    pub error: Option<EnvError>,
+    /// The stream of the local player
+    #[serde(skip_serializing)]
+    pub stream: Option<Stream>,
+    /// The sequence number of the last received message
+    #[serde(skip_serializing)]
+    pub last_seq: Option<u64>,
+    /// The sequence number of our join message.
+    ///
+    /// The commands before it are history and only the last stream change is applied from it.
+    #[serde(skip_serializing)]
+    pub joined: Option<u64>,
+    /// Commands the player was asked to apply,
+    /// so they are not sent back to the party once the player applies them
+    #[serde(skip_serializing)]
+    pub pending: Vec<PendingCommand>,
+    #[serde(skip_serializing)]
+    #[derivative(Default(value = "Utc.timestamp_opt(0, 0).unwrap()"))]
+    pub state_sent_time: DateTime<Utc>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for WatchParty {
+    fn update(&mut self, msg: &Msg, _ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::WatchParty(selected))) => {
+                let leave_effects = match &self.selected {
+                    Some(selected) => {
+                        Effects::one(send::<E>(selected, WatchPartyCommand::Leave, None))
+                            .unchanged()
+                    }
+                    _ => Effects::none().unchanged(),
+                };
+                *self = WatchParty {
+                    selected: Some(selected.to_owned()),
+                    ..Default::default()
+                };
+                leave_effects.join(Effects::one(send::<E>(
+                    selected,
+                    WatchPartyCommand::Join,
+                    None,
+                )))
+            }
+            Msg::Action(Action::Unload) => match &self.selected {
+                Some(selected) => {
+                    let leave_effects =
+                        Effects::one(send::<E>(selected, WatchPartyCommand::Leave, None));
+                    *self = WatchParty::default();
+                    leave_effects
+                }
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Action(Action::WatchParty(ActionWatchParty::Sync)) => match &self.selected {
+                Some(selected) => Effects::one(sync::<E>(
+                    selected,
+                    &self.playback,
+                    &mut self.state_sent_time,
+                    self.last_seq,
+                ))
+                .unchanged(),
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Internal(Internal::PlayerLoaded(player_selected)) => match &self.selected {
+                Some(selected) => {
+                    let PlayerSelected {
+                        stream,
+                        stream_request,
+                        meta_request,
+                        ..
+                    } = player_selected.as_ref();
+                    let command = WatchPartyCommand::StreamChanged {
+                        stream: Box::new(stream.to_owned()),
+                        stream_request: stream_request.to_owned(),
+                        meta_request: meta_request.to_owned(),
+                    };
+                    let send_effects = if take_pending::<E>(&mut self.pending, &command) {
+                        Effects::none().unchanged()
+                    } else {
+                        Effects::one(send::<E>(selected, command, self.last_seq)).unchanged()
+                    };
+                    let stream_effects = eq_update(&mut self.stream, Some(stream.to_owned()));
+                    let playback_effects = eq_update(&mut self.playback, None);
+                    send_effects.join(stream_effects).join(playback_effects)
+                }
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Internal(Internal::PlayerPausedChanged(paused)) => match &self.selected {
+                Some(selected) => {
+                    let now = E::now();
+                    let previous_playback = self.playback.to_owned();
+                    let time = previous_playback
+                        .as_ref()
+                        .and_then(|playback| playback.time_at(now));
+                    self.playback = Some(Playback {
+                        time,
+                        paused: *paused,
+                        updated: now,
+                    });
+                    let time = time.unwrap_or_default();
+                    let command = if *paused {
+                        WatchPartyCommand::Pause { time }
+                    } else {
+                        WatchPartyCommand::Play { time }
+                    };
+                    // the first report of the player comes from loading the stream, not from the user
+                    match previous_playback {
+                        Some(playback)
+                            if playback.paused != *paused
+                                && !take_pending::<E>(&mut self.pending, &command) =>
+                        {
+                            Effects::one(send::<E>(selected, command, self.last_seq))
+                        }
+                        _ => Effects::none(),
+                    }
+                }
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Internal(Internal::PlayerTimeChanged(time)) => match &self.selected {
+                Some(selected) => {
+                    let now = E::now();
+                    let expected_time = self
+                        .playback
+                        .as_ref()
+                        .and_then(|playback| playback.time_at(now));
+                    let seeking = expected_time
+                        .is_some_and(|expected_time| expected_time.abs_diff(*time) > MAX_DRIFT);
+                    // the time is expected to advance while playing,
+                    // so the playback is updated only when it's reported for the first time or it jumps
+                    let playback_effects = if expected_time.is_none() || seeking {
+                        let paused = self
+                            .playback
+                            .as_ref()
+                            .map(|playback| playback.paused)
+                            .unwrap_or_default();
+                        self.playback = Some(Playback {
+                            time: Some(*time),
+                            paused,
+                            updated: now,
+                        });
+                        Effects::none()
+                    } else {
+                        Effects::none().unchanged()
+                    };
+                    let command = WatchPartyCommand::Seek { time: *time };
+                    let seek_effects = if seeking && !take_pending::<E>(&mut self.pending, &command)
+                    {
+                        Effects::one(send::<E>(selected, command, self.last_seq)).unchanged()
+                    } else {
+                        Effects::none().unchanged()
+                    };
+                    // the messages of the party are received along with reporting the playback
+                    let state_effects = if is_state_due::<E>(&self.state_sent_time) {
+                        Effects::one(sync::<E>(
+                            selected,
+                            &self.playback,
+                            &mut self.state_sent_time,
+                            self.last_seq,
+                        ))
+                        .unchanged()
+                    } else {
+                        Effects::none().unchanged()
+                    };
+                    playback_effects.join(seek_effects).join(state_effects)
+                }
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Internal(Internal::WatchPartyReceiveResult(party_url, result)) => {
+                match &self.selected {
+                    Some(selected) if selected.party_url == *party_url => match result {
+                        Ok(envelopes) => {
+                            let error_effects = eq_update(&mut self.error, None);
+                            let selected = selected.to_owned();
+                            error_effects.join(self.receive::<E>(&selected, envelopes))
+                        }
+                        Err(error) => eq_update(&mut self.error, Some(error.to_owned())),
+                    },
+                    _ => Effects::none().unchanged(),
+                }
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+impl WatchParty {
+    fn receive<E: Env + 'static>(
+        &mut self,
+        selected: &Selected,
+        envelopes: &[WatchPartyEnvelope],
+    ) -> Effects {
+        let envelopes = envelopes
+            .iter()
+            .filter(|envelope| {
+                self.last_seq
+                    .map_or(true, |last_seq| envelope.seq > last_seq)
+            })
+            .collect::<Vec<_>>();
+        if envelopes.is_empty() {
+            return Effects::none().unchanged();
+        }
+        let now = E::now();
+        for WatchPartyEnvelope { seq, message } in envelopes.iter() {
+            self.last_seq = Some(*seq);
+            match message.command {
+                WatchPartyCommand::Join if message.member_id == selected.member_id => {
+                    self.joined = Some(*seq);
+                }
+                WatchPartyCommand::Leave => {
+                    self.members.retain(|member| member.id != message.member_id);
+                    continue;
+                }
+                _ => {}
+            };
+            match self
+                .members
+                .iter_mut()
+                .find(|member| member.id == message.member_id)
+            {
+                Some(member) => member.last_seen = member.last_seen.max(message.sent),
+                None => self.members.push(Member {
+                    id: message.member_id.to_owned(),
+                    joined: *seq,
+                    last_seen: message.sent,
+                }),
+            };
+        }
+        self.members.retain(|member| {
+            member.id == selected.member_id
+                || now.signed_duration_since(member.last_seen) < *MEMBER_TIMEOUT
+        });
+        self.leader = self
+            .members
+            .iter()
+            .min_by_key(|member| member.joined)
+            .map(|member| member.id.to_owned());
+
+        let (history, recent) = envelopes.into_iter().partition::<Vec<_>, _>(|envelope| {
+            self.joined.map_or(true, |joined| envelope.seq < joined)
+        });
+        // the stream the party was watching before we joined
+        let history_commands = history
+            .into_iter()
+            .rev()
+            .find(|envelope| {
+                matches!(
+                    envelope.message.command,
+                    WatchPartyCommand::StreamChanged { .. }
+                )
+            })
+            .filter(|envelope| !self.is_applied(&envelope.message.command))
+            .map(|envelope| &envelope.message);
+        let recent_commands = recent
+            .iter()
+            .map(|envelope| &envelope.message)
+            .filter(|message| {
+                message.member_id != selected.member_id
+                    && matches!(
+                        message.command,
+                        WatchPartyCommand::Play { .. }
+                            | WatchPartyCommand::Pause { .. }
+                            | WatchPartyCommand::Seek { .. }
+                            | WatchPartyCommand::StreamChanged { .. }
+                    )
+            });
+        let commands = history_commands
+            .into_iter()
+            .chain(recent_commands)
+            .map(|message| (message.member_id.to_owned(), message.command.to_owned()))
+            .collect::<Vec<_>>();
+        // the drift is corrected only when the player isn't asked to change anything else
+        let drift_commands = if commands.is_empty() {
+            self.drift_command(selected, &recent, now)
+        } else {
+            None
+        };
+        commands.into_iter().chain(drift_commands).fold(
+            Effects::none(),
+            |effects, (member_id, command)| {
+                self.pending.push(PendingCommand {
+                    command: command.to_owned(),
+                    received: now,
+                });
+                effects.join(
+                    Effects::msg(Msg::Event(Event::WatchPartyCommandReceived {
+                        member_id,
+                        command,
+                    }))
+                    .unchanged(),
+                )
+            },
+        )
+    }
+    /// Whether the local player already does what the command asks for.
+    fn is_applied(&self, command: &WatchPartyCommand) -> bool {
+        match command {
+            WatchPartyCommand::StreamChanged { stream, .. } => {
+                self.stream.as_ref() == Some(stream.as_ref())
+            }
+            _ => false,
+        }
+    }
+    /// A command for moving to the playback the last state of the leader implies.
+    fn drift_command(
+        &self,
+        selected: &Selected,
+        envelopes: &[&WatchPartyEnvelope],
+        now: DateTime<Utc>,
+    ) -> Option<(String, WatchPartyCommand)> {
+        let leader = self
+            .leader
+            .as_ref()
+            .filter(|leader| **leader != selected.member_id)?;
+        let playback = self.playback.as_ref()?;
+        let (message, leader_time, leader_paused) = envelopes
+            .iter()
+            .rev()
+            .map(|envelope| &envelope.message)
+            .filter(|message| message.member_id == *leader)
+            .find_map(|message| match message.command {
+                WatchPartyCommand::State {
+                    time: Some(time),
+                    paused,
+                } => Some((message, time, paused)),
+                _ => None,
+            })?;
+        let leader_playback = Playback {
+            time: Some(leader_time),
+            paused: leader_paused,
+            updated: message.sent,
+        };
+        let time = leader_playback.time_at(now).unwrap_or(leader_time);
+        let command = if leader_paused != playback.paused {
+            if leader_paused {
+                WatchPartyCommand::Pause { time }
+            } else {
+                WatchPartyCommand::Play { time }
+            }
+        } else if playback
+            .time_at(now)
+            .is_some_and(|local_time| local_time.abs_diff(time) > MAX_DRIFT)
+        {
+            WatchPartyCommand::Seek { time }
+        } else {
+            return None;
+        };
+        Some((leader.to_owned(), command))
+    }
+}
+
+/// Removes the pending command which the player has applied, along with the expired ones.
+fn take_pending<E: Env + 'static>(
+    pending: &mut Vec<PendingCommand>,
+    command: &WatchPartyCommand,
+) -> bool {
+    let now = E::now();
+    pending.retain(|pending| now.signed_duration_since(pending.received) < *PENDING_TIMEOUT);
+    let position = pending
+        .iter()
+        .position(|pending| match (&pending.command, command) {
+            (WatchPartyCommand::Play { .. }, WatchPartyCommand::Play { .. })
+            | (WatchPartyCommand::Pause { .. }, WatchPartyCommand::Pause { .. }) => true,
+            (WatchPartyCommand::Seek { time: a }, WatchPartyCommand::Seek { time: b }) => {
+                a.abs_diff(*b) <= MAX_DRIFT
+            }
+            (
+                WatchPartyCommand::StreamChanged { stream: a, .. },
+                WatchPartyCommand::StreamChanged { stream: b, .. },
+            ) => a == b,
+            _ => false,
+        });
+    match position {
+        Some(position) => {
+            pending.remove(position);
+            true
+        }
+        None => false,
+    }
+}
+
+fn is_state_due<E: Env + 'static>(state_sent_time: &DateTime<Utc>) -> bool {
+    E::now().signed_duration_since(*state_sent_time) >= *SEND_STATE_EVERY
+}
+
+/// Reports the playback if it's time to, otherwise only receives the new messages.
+fn sync<E: Env + 'static>(
+    selected: &Selected,
+    playback: &Option<Playback>,
+    state_sent_time: &mut DateTime<Utc>,
+    last_seq: Option<u64>,
+) -> Effect {
+    let now = E::now();
+    if is_state_due::<E>(state_sent_time) {
+        *state_sent_time = now;
+        let command = WatchPartyCommand::State {
+            time: playback.as_ref().and_then(|playback| playback.time_at(now)),
+            paused: playback
+                .as_ref()
+                .map(|playback| playback.paused)
+                .unwrap_or(true),
+        };
+        send::<E>(selected, command, last_seq)
+    } else {
+        let party_url = selected.party_url.to_owned();
+        EffectFuture::Concurrent(
+            E::watch_party_transport(&party_url)
+                .receive(last_seq)
+                .map(move |result| {
+                    Msg::Internal(Internal::WatchPartyReceiveResult(party_url, result))
+                })
+                .boxed_env(),
+        )
+        .into()
+    }
+}
+
+/// Sends the command and receives the messages after it, in order to learn its sequence number.
+fn send<E: Env + 'static>(
+    selected: &Selected,
+    command: WatchPartyCommand,
+    last_seq: Option<u64>,
+) -> Effect {
+    let party_url = selected.party_url.to_owned();
+    let message = WatchPartyMessage {
+        member_id: selected.member_id.to_owned(),
+        sent: E::now(),
+        command,
+    };
+    EffectFuture::Sequential(
+        E::watch_party_transport(&party_url)
+            .send(&message)
+            .and_then({
+                let party_url = party_url.to_owned();
+                move |_| E::watch_party_transport(&party_url).receive(last_seq)
+            })
+            .map(move |result| Msg::Internal(Internal::WatchPartyReceiveResult(party_url, result)))
+            .boxed_env(),
+    )
+    .into()
+}
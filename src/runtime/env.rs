@@ -1,11 +1,17 @@
 use crate::addon_transport::{AddonHTTPTransport, AddonTransport, UnsupportedTransport};
 use crate::constants::{
-    DISMISSED_EVENTS_STORAGE_KEY, LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY,
-    PROFILE_STORAGE_KEY, SCHEMA_VERSION, SCHEMA_VERSION_STORAGE_KEY, SEARCH_HISTORY_STORAGE_KEY,
-    STREAMS_STORAGE_KEY,
+    ADDONS_HISTORY_STORAGE_KEY, DISMISSED_EVENTS_STORAGE_KEY, INTROS_STORAGE_KEY,
+    LANGUAGE_PREFERENCES_STORAGE_KEY, LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY,
+    PLAY_QUEUE_STORAGE_KEY, PROFILE_STORAGE_KEY, SCHEMA_VERSION, SCHEMA_VERSION_STORAGE_KEY,
+    SEARCH_HISTORY_STORAGE_KEY, STREAMS_STORAGE_KEY, WATCH_HISTORY_STORAGE_KEY,
 };
 use crate::models::ctx::Ctx;
 use crate::models::streaming_server::StreamingServer;
+use crate::types::language_preferences::{LanguagePreference, LanguagePreferencesBucket};
+use crate::types::streams::StreamsBucket;
+use crate::watch_party_transport::{
+    UnsupportedWatchPartyTransport, WatchPartyHTTPTransport, WatchPartyTransport,
+};
 use chrono::{DateTime, Utc};
 use futures::{future, Future, TryFutureExt};
 use http::Request;
@@ -143,6 +149,8 @@ pub trait Env {
     >(
         request: Request<IN>,
     ) -> TryEnvFuture<OUT>;
+    /// Fetches the raw body of the response, for the resources which are not JSON (e.g. subtitles).
+    fn fetch_bytes(request: Request<()>) -> TryEnvFuture<Vec<u8>>;
 
     fn get_storage<T: for<'de> Deserialize<'de> + ConditionalSend + 'static>(
         key: &str,
@@ -168,6 +176,17 @@ pub trait Env {
             _ => Box::new(UnsupportedTransport::new(transport_url.to_owned())),
         }
     }
+    fn watch_party_transport(party_url: &Url) -> Box<dyn WatchPartyTransport>
+    where
+        Self: Sized + 'static,
+    {
+        match party_url.scheme() {
+            "http" | "https" => {
+                Box::new(WatchPartyHTTPTransport::<Self>::new(party_url.to_owned()))
+            }
+            _ => Box::new(UnsupportedWatchPartyTransport::new(party_url.to_owned())),
+        }
+    }
     fn migrate_storage_schema() -> TryEnvFuture<()>
     where
         Self: Sized,
@@ -265,6 +284,66 @@ pub trait Env {
                         .await?;
                     schema_version = 14;
                 }
+                if schema_version == 14 {
+                    migrate_storage_schema_to_v15::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 15;
+                }
+                if schema_version == 15 {
+                    migrate_storage_schema_to_v16::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 16;
+                }
+                if schema_version == 16 {
+                    migrate_storage_schema_to_v17::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 17;
+                }
+                if schema_version == 17 {
+                    migrate_storage_schema_to_v18::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 18;
+                }
+                if schema_version == 18 {
+                    migrate_storage_schema_to_v19::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 19;
+                }
+                if schema_version == 19 {
+                    migrate_storage_schema_to_v20::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 20;
+                }
+                if schema_version == 20 {
+                    migrate_storage_schema_to_v21::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 21;
+                }
+                if schema_version == 21 {
+                    migrate_storage_schema_to_v22::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 22;
+                }
+                if schema_version == 22 {
+                    migrate_storage_schema_to_v23::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 23;
+                }
+                if schema_version == 23 {
+                    migrate_storage_schema_to_v24::<Self>()
+                        .map_err(|error| EnvError::StorageSchemaVersionUpgrade(Box::new(error)))
+                        .await?;
+                    schema_version = 24;
+                }
                 if schema_version != SCHEMA_VERSION {
                     panic!(
                         "Storage schema version must be upgraded from {} to {}",
@@ -594,19 +673,194 @@ fn migrate_storage_schema_to_v14<E: Env>() -> TryEnvFuture<()> {
         .boxed_env()
 }
 
+/// Learns the language preferences of each series from the latest state of its streams.
+fn migrate_storage_schema_to_v15<E: Env>() -> TryEnvFuture<()> {
+    E::get_storage::<StreamsBucket>(STREAMS_STORAGE_KEY)
+        .and_then(|streams| match streams {
+            Some(streams) => {
+                let mut items = streams.items.into_values().collect::<Vec<_>>();
+                items.sort_by_key(|item| item.mtime);
+                let mut language_preferences = LanguagePreferencesBucket::new(streams.uid);
+                for item in items {
+                    if let Some(state) = &item.state {
+                        let previous = language_preferences.items.get(&item.meta_id);
+                        let preference = LanguagePreference::new(state, previous, item.mtime);
+                        language_preferences.items.insert(item.meta_id, preference);
+                    }
+                }
+                E::set_storage(
+                    LANGUAGE_PREFERENCES_STORAGE_KEY,
+                    Some(&language_preferences),
+                )
+            }
+            _ => E::set_storage::<()>(LANGUAGE_PREFERENCES_STORAGE_KEY, None),
+        })
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&15)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v16<E: Env>() -> TryEnvFuture<()> {
+    E::set_storage::<()>(INTROS_STORAGE_KEY, None)
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&16)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v17<E: Env>() -> TryEnvFuture<()> {
+    E::set_storage::<()>(PLAY_QUEUE_STORAGE_KEY, None)
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&17)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v18<E: Env>() -> TryEnvFuture<()> {
+    E::set_storage::<()>(WATCH_HISTORY_STORAGE_KEY, None)
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&18)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v19<E: Env>() -> TryEnvFuture<()> {
+    E::get_storage::<serde_json::Value>(PROFILE_STORAGE_KEY)
+        .and_then(|mut profile| {
+            match profile
+                .as_mut()
+                .and_then(|profile| profile.as_object_mut())
+                .and_then(|profile| profile.get_mut("settings"))
+                .and_then(|settings| settings.as_object_mut())
+            {
+                Some(settings) => {
+                    settings.insert(
+                        "notificationRules".to_owned(),
+                        serde_json::json!({
+                            "watchedWithinDays": null,
+                            "onlyCaughtUp": false,
+                            "maxVideoAgeDays": null,
+                            "quietHours": null
+                        }),
+                    );
+                    E::set_storage(PROFILE_STORAGE_KEY, Some(&profile))
+                }
+                _ => E::set_storage::<()>(PROFILE_STORAGE_KEY, None),
+            }
+        })
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&19)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v20<E: Env>() -> TryEnvFuture<()> {
+    E::get_storage::<serde_json::Value>(PROFILE_STORAGE_KEY)
+        .and_then(|mut profile| {
+            match profile
+                .as_mut()
+                .and_then(|profile| profile.as_object_mut())
+                .and_then(|profile| profile.get_mut("settings"))
+                .and_then(|settings| settings.as_object_mut())
+                .and_then(|settings| settings.get_mut("notificationRules"))
+                .and_then(|notification_rules| notification_rules.as_object_mut())
+            {
+                Some(notification_rules) => {
+                    notification_rules.insert(
+                        "checkTtlHours".to_owned(),
+                        serde_json::Value::Number(6.into()),
+                    );
+                    E::set_storage(PROFILE_STORAGE_KEY, Some(&profile))
+                }
+                _ => E::set_storage::<()>(PROFILE_STORAGE_KEY, None),
+            }
+        })
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&20)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v21<E: Env>() -> TryEnvFuture<()> {
+    E::set_storage::<()>(ADDONS_HISTORY_STORAGE_KEY, None)
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&21)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v22<E: Env>() -> TryEnvFuture<()> {
+    E::get_storage::<serde_json::Value>(PROFILE_STORAGE_KEY)
+        .and_then(|mut profile| {
+            match profile
+                .as_mut()
+                .and_then(|profile| profile.as_object_mut())
+                .and_then(|profile| profile.get_mut("settings"))
+                .and_then(|settings| settings.as_object_mut())
+            {
+                Some(settings) => {
+                    settings.insert(
+                        "autoUpgradeAddons".to_owned(),
+                        serde_json::Value::Bool(false),
+                    );
+                    E::set_storage(PROFILE_STORAGE_KEY, Some(&profile))
+                }
+                _ => E::set_storage::<()>(PROFILE_STORAGE_KEY, None),
+            }
+        })
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&22)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v23<E: Env>() -> TryEnvFuture<()> {
+    E::get_storage::<serde_json::Value>(PROFILE_STORAGE_KEY)
+        .and_then(|mut profile| {
+            match profile
+                .as_mut()
+                .and_then(|profile| profile.as_object_mut())
+                .and_then(|profile| profile.get_mut("settings"))
+                .and_then(|settings| settings.as_object_mut())
+            {
+                Some(settings) => {
+                    settings.insert(
+                        "addonPriorities".to_owned(),
+                        serde_json::Value::Object(Default::default()),
+                    );
+                    E::set_storage(PROFILE_STORAGE_KEY, Some(&profile))
+                }
+                _ => E::set_storage::<()>(PROFILE_STORAGE_KEY, None),
+            }
+        })
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&23)))
+        .boxed_env()
+}
+
+fn migrate_storage_schema_to_v24<E: Env>() -> TryEnvFuture<()> {
+    E::get_storage::<serde_json::Value>(PROFILE_STORAGE_KEY)
+        .and_then(|mut profile| {
+            match profile
+                .as_mut()
+                .and_then(|profile| profile.as_object_mut())
+                .and_then(|profile| profile.get_mut("settings"))
+                .and_then(|settings| settings.as_object_mut())
+            {
+                Some(settings) => {
+                    settings.insert("trailerAutoplay".to_owned(), serde_json::Value::Bool(false));
+                    E::set_storage(PROFILE_STORAGE_KEY, Some(&profile))
+                }
+                _ => E::set_storage::<()>(PROFILE_STORAGE_KEY, None),
+            }
+        })
+        .and_then(|_| E::set_storage(SCHEMA_VERSION_STORAGE_KEY, Some(&24)))
+        .boxed_env()
+}
+
 #[cfg(test)]
 mod test {
     use serde_json::{json, Value};
 
     use crate::{
         constants::{
-            PROFILE_STORAGE_KEY, SCHEMA_VERSION, SCHEMA_VERSION_STORAGE_KEY, STREAMS_STORAGE_KEY,
+            LANGUAGE_PREFERENCES_STORAGE_KEY, PROFILE_STORAGE_KEY, SCHEMA_VERSION,
+            SCHEMA_VERSION_STORAGE_KEY, STREAMS_STORAGE_KEY,
         },
         runtime::{
             env::{
                 migrate_storage_schema_to_v10, migrate_storage_schema_to_v11,
                 migrate_storage_schema_to_v12, migrate_storage_schema_to_v13,
-                migrate_storage_schema_to_v14, migrate_storage_schema_to_v6,
+                migrate_storage_schema_to_v14, migrate_storage_schema_to_v15,
+                migrate_storage_schema_to_v16, migrate_storage_schema_to_v17,
+                migrate_storage_schema_to_v18, migrate_storage_schema_to_v19,
+                migrate_storage_schema_to_v20, migrate_storage_schema_to_v21,
+                migrate_storage_schema_to_v22, migrate_storage_schema_to_v23,
+                migrate_storage_schema_to_v24, migrate_storage_schema_to_v6,
                 migrate_storage_schema_to_v7, migrate_storage_schema_to_v8,
                 migrate_storage_schema_to_v9,
             },
@@ -1125,4 +1379,347 @@ mod test {
             "Profile should match"
         );
     }
+
+    #[tokio::test]
+    async fn test_migration_from_14_to_15() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        let stream_item = |video_id: &str, mtime: &str, state: Value| {
+            json!([
+                {
+                    "metaId": "tt1",
+                    "videoId": video_id
+                },
+                {
+                    "stream": {
+                        "url": "https://example.com/video.mp4"
+                    },
+                    "type": "series",
+                    "metaId": "tt1",
+                    "videoId": video_id,
+                    "metaTransportUrl": "https://example.com/manifest.json",
+                    "streamTransportUrl": "https://example.com/manifest.json",
+                    "state": state,
+                    "_mtime": mtime
+                }
+            ])
+        };
+        let streams = json!({
+            "uid": "user_id",
+            "items": [
+                stream_item(
+                    "tt1:1:2",
+                    "2024-01-02T00:00:00Z",
+                    json!({
+                        "subtitleTrack": {
+                            "id": "2",
+                            "embedded": true,
+                            "language": "eng"
+                        },
+                        "subtitleDelay": 500
+                    })
+                ),
+                stream_item(
+                    "tt1:1:1",
+                    "2024-01-01T00:00:00Z",
+                    json!({
+                        "audioTrack": {
+                            "id": "1",
+                            "language": "jpn"
+                        }
+                    })
+                ),
+                stream_item("tt1:1:3", "2024-01-03T00:00:00Z", Value::Null)
+            ]
+        });
+
+        let migrated_language_preferences = json!({
+            "uid": "user_id",
+            "items": {
+                "tt1": {
+                    "audioLanguage": "jpn",
+                    "subtitleLanguage": "eng",
+                    "subtitleDelay": 500,
+                    "_mtime": "2024-01-02T00:00:00Z"
+                }
+            }
+        });
+
+        set_streams_and_schema_version(&streams, 14);
+
+        migrate_storage_schema_to_v15::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        let storage = STORAGE.read().expect("Should lock");
+
+        assert_eq!(
+            &15.to_string(),
+            storage
+                .get(SCHEMA_VERSION_STORAGE_KEY)
+                .expect("Should have the schema set"),
+            "Scheme version should now be updated"
+        );
+        assert_eq!(
+            migrated_language_preferences,
+            serde_json::from_str::<Value>(
+                storage
+                    .get(LANGUAGE_PREFERENCES_STORAGE_KEY)
+                    .expect("Should have the language preferences set")
+            )
+            .unwrap(),
+            "Language preferences should match"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_15_to_16() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        migrate_storage_schema_to_v16::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        {
+            assert_storage_schema_version(16);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_16_to_17() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        migrate_storage_schema_to_v17::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        {
+            assert_storage_schema_version(17);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_17_to_18() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        migrate_storage_schema_to_v18::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        {
+            assert_storage_schema_version(18);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_18_to_19() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        let init_profile = json!({
+            "settings": {}
+        });
+
+        let migrated_profile = json!({
+            "settings": {
+                "notificationRules": {
+                    "watchedWithinDays": null,
+                    "onlyCaughtUp": false,
+                    "maxVideoAgeDays": null,
+                    "quietHours": null
+                }
+            }
+        });
+
+        set_profile_and_schema_version(&init_profile, 18);
+
+        migrate_storage_schema_to_v19::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        let storage = STORAGE.read().expect("Should lock");
+
+        assert_eq!(
+            &19.to_string(),
+            storage
+                .get(SCHEMA_VERSION_STORAGE_KEY)
+                .expect("Should have the schema set"),
+            "Scheme version should now be updated"
+        );
+        assert_eq!(
+            &migrated_profile.to_string(),
+            storage
+                .get(PROFILE_STORAGE_KEY)
+                .expect("Should have the profile set"),
+            "Profile should match"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_19_to_20() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        let init_profile = json!({
+            "settings": {
+                "notificationRules": {}
+            }
+        });
+
+        let migrated_profile = json!({
+            "settings": {
+                "notificationRules": {
+                    "checkTtlHours": 6
+                }
+            }
+        });
+
+        set_profile_and_schema_version(&init_profile, 19);
+
+        migrate_storage_schema_to_v20::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        let storage = STORAGE.read().expect("Should lock");
+
+        assert_eq!(
+            &20.to_string(),
+            storage
+                .get(SCHEMA_VERSION_STORAGE_KEY)
+                .expect("Should have the schema set"),
+            "Scheme version should now be updated"
+        );
+        assert_eq!(
+            &migrated_profile.to_string(),
+            storage
+                .get(PROFILE_STORAGE_KEY)
+                .expect("Should have the profile set"),
+            "Profile should match"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_20_to_21() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        migrate_storage_schema_to_v21::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        {
+            assert_storage_schema_version(21);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_21_to_22() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        let init_profile = json!({
+            "settings": {}
+        });
+
+        let migrated_profile = json!({
+            "settings": {
+                "autoUpgradeAddons": false
+            }
+        });
+
+        set_profile_and_schema_version(&init_profile, 21);
+
+        migrate_storage_schema_to_v22::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        let storage = STORAGE.read().expect("Should lock");
+
+        assert_eq!(
+            &22.to_string(),
+            storage
+                .get(SCHEMA_VERSION_STORAGE_KEY)
+                .expect("Should have the schema set"),
+            "Scheme version should now be updated"
+        );
+        assert_eq!(
+            &migrated_profile.to_string(),
+            storage
+                .get(PROFILE_STORAGE_KEY)
+                .expect("Should have the profile set"),
+            "Profile should match"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_22_to_23() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        let init_profile = json!({
+            "settings": {}
+        });
+
+        let migrated_profile = json!({
+            "settings": {
+                "addonPriorities": {}
+            }
+        });
+
+        set_profile_and_schema_version(&init_profile, 22);
+
+        migrate_storage_schema_to_v23::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        let storage = STORAGE.read().expect("Should lock");
+
+        assert_eq!(
+            &23.to_string(),
+            storage
+                .get(SCHEMA_VERSION_STORAGE_KEY)
+                .expect("Should have the schema set"),
+            "Scheme version should now be updated"
+        );
+        assert_eq!(
+            &migrated_profile.to_string(),
+            storage
+                .get(PROFILE_STORAGE_KEY)
+                .expect("Should have the profile set"),
+            "Profile should match"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_from_23_to_24() {
+        let _test_env_guard = TestEnv::reset().expect("Should lock TestEnv");
+
+        let init_profile = json!({
+            "settings": {}
+        });
+
+        let migrated_profile = json!({
+            "settings": {
+                "trailerAutoplay": false
+            }
+        });
+
+        set_profile_and_schema_version(&init_profile, 23);
+
+        migrate_storage_schema_to_v24::<TestEnv>()
+            .await
+            .expect("Should migrate");
+
+        let storage = STORAGE.read().expect("Should lock");
+
+        assert_eq!(
+            &24.to_string(),
+            storage
+                .get(SCHEMA_VERSION_STORAGE_KEY)
+                .expect("Should have the schema set"),
+            "Scheme version should now be updated"
+        );
+        assert_eq!(
+            &migrated_profile.to_string(),
+            storage
+                .get(PROFILE_STORAGE_KEY)
+                .expect("Should have the profile set"),
+            "Profile should match"
+        );
+    }
 }
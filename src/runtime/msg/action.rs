@@ -1,4 +1,4 @@
-use std::ops::Range;
+use std::{collections::HashMap, ops::Range};
 
 use serde::Deserialize;
 use url::Url;
@@ -6,7 +6,10 @@ use url::Url;
 use crate::types::streams::StreamItemState;
 use crate::{
     models::{
+        addon_configure::Selected as AddonConfigureSelected,
         addon_details::Selected as AddonDetailsSelected,
+        calendar::Selected as CalendarSelected,
+        cast_session::Selected as CastSessionSelected,
         catalog_with_filters::Selected as CatalogWithFiltersSelected,
         catalogs_with_extra::Selected as CatalogsWithExtraSelected,
         installed_addons_with_filters::Selected as InstalledAddonsWithFiltersSelected,
@@ -14,15 +17,18 @@ use crate::{
         library_with_filters::Selected as LibraryWithFiltersSelected,
         meta_details::Selected as MetaDetailsSelected,
         player::{Selected as PlayerSelected, VideoParams},
+        trailer_player::Selected as TrailerPlayerSelected,
+        watch_party::Selected as WatchPartySelected,
     },
     types::{
         addon::Descriptor,
         api::AuthRequest,
         library::LibraryItemId,
-        profile::Settings as ProfileSettings,
-        resource::{MetaItemId, MetaItemPreview, Video},
+        play_queue::PlayQueueItem,
+        profile::{BoardCatalog, Settings as ProfileSettings},
+        resource::{MetaItemId, MetaItemPreview, Video, VideoId},
         streaming_server::{
-            Settings as StreamingServerSettings,
+            PlaybackCapabilities, Settings as StreamingServerSettings,
             StatisticsRequest as StreamingServerStatisticsRequest,
         },
     },
@@ -58,7 +64,8 @@ pub enum ActionCtx {
     SyncLibraryWithAPI,
     /// Pull notifications for all [`LibraryItem`]s that we should pull notifications for.
     ///
-    /// **Warning:** The action will **always** trigger requests to the addons.
+    /// Only the items which were last checked before the `check_ttl_hours` of the notification rules
+    /// are requested, in batches per addon, the most recently watched first.
     ///
     /// See `LibraryItem::should_pull_notifications()`
     ///
@@ -68,6 +75,49 @@ pub enum ActionCtx {
     GetEvents,
     /// Dismiss an event by id, either a Modal or Notification
     DismissEvent(String),
+    /// Append a video to the end of the play queue.
+    AddToPlayQueue(PlayQueueItem),
+    RemoveFromPlayQueue {
+        meta_id: MetaItemId,
+        video_id: VideoId,
+    },
+    /// Move the queued video at position `from` to position `to`.
+    MovePlayQueueItem {
+        from: usize,
+        to: usize,
+    },
+    ClearPlayQueue,
+    /// Remove a viewing session by id from the watch history.
+    RemoveFromWatchHistory(String),
+    /// Remove all the viewing sessions of a [`LibraryItem`] from the watch history.
+    ///
+    /// [`LibraryItem`]: crate::types::library::LibraryItem
+    RemoveItemFromWatchHistory(LibraryItemId),
+    ClearWatchHistory,
+    /// Replace the installed addons with a snapshot by id from the addons history
+    /// and push them to the API.
+    RestoreAddonsSnapshot(u64),
+    /// Move the Board catalog to position `to` among the visible catalogs.
+    MoveBoardCatalog {
+        catalog: BoardCatalog,
+        to: usize,
+    },
+    HideBoardCatalog(BoardCatalog),
+    ShowBoardCatalog(BoardCatalog),
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionAddonConfigure {
+    /// Validates the values of the settings and installs the configured addon.
+    Configure(HashMap<String, String>),
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionInstalledAddonsWithFilters {
+    /// Fetches the manifests of all the installed addons again to check for newer versions.
+    CheckUpgrades,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -135,6 +185,39 @@ pub enum ActionStreamingServer {
     CreateTorrent(CreateTorrentArgs),
     GetStatistics(StreamingServerStatisticsRequest),
     PlayOnDevice(PlayOnDeviceArgs),
+    /// Report the media formats the host can play natively.
+    UpdatePlaybackCapabilities(PlaybackCapabilities),
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionCastSession {
+    Play,
+    Pause,
+    /// Seek to the given time in milliseconds
+    Seek {
+        time: u64,
+    },
+    /// Set the volume in the range `0.0..=1.0`
+    SetVolume {
+        volume: f64,
+    },
+    /// Stop the playback on the device and end the session
+    Stop,
+    /// Request the current status of the device's player.
+    ///
+    /// Should be dispatched periodically while the session is active.
+    GetStatus,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionWatchParty {
+    /// Receive the messages of the other members and report the playback to them.
+    ///
+    /// The playback of the player does it as well once in a few seconds,
+    /// so it should be dispatched periodically only while nothing is played or it's paused.
+    Sync,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -169,12 +252,25 @@ pub enum ActionPlayer {
     /// - We've watched a movie to the last second
     /// - We've watched a movie series to the last second
     Ended,
+    /// Stops the playback after the given minutes or,
+    /// when there are none, at the end of the current video.
+    StartSleepTimer {
+        minutes: Option<u32>,
+    },
+    CancelSleepTimer,
+    /// User has confirmed they're still watching after many episodes were binge watched.
+    ConfirmStillWatching,
 }
 
 #[derive(Clone, Deserialize, Debug)]
 #[serde(tag = "model", content = "args")]
 pub enum ActionLoad {
+    /// Loads the settings of a configurable addon
+    AddonConfigure(AddonConfigureSelected),
     AddonDetails(AddonDetailsSelected),
+    /// Loads the upcoming videos of the library series in the selected range
+    Calendar(CalendarSelected),
+    CastSession(CastSessionSelected),
     CatalogWithFilters(Option<CatalogWithFiltersSelected>),
     CatalogsWithExtra(CatalogsWithExtraSelected),
     DataExport,
@@ -186,6 +282,12 @@ pub enum ActionLoad {
     MetaDetails(MetaDetailsSelected),
     Player(Box<PlayerSelected>),
     Link,
+    /// Loads the viewing statistics, fetching the genres and videos of the most watched items
+    Stats,
+    /// Plays a trailer without storing any progress
+    TrailerPlayer(TrailerPlayerSelected),
+    /// Joins the party, leaving the previous one.
+    WatchParty(WatchPartySelected),
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -208,12 +310,16 @@ pub enum ActionSearch {
 pub enum Action {
     Ctx(ActionCtx),
     Link(ActionLink),
+    AddonConfigure(ActionAddonConfigure),
     CatalogWithFilters(ActionCatalogWithFilters),
+    InstalledAddonsWithFilters(ActionInstalledAddonsWithFilters),
     CatalogsWithExtra(ActionCatalogsWithExtra),
     LibraryByType(ActionLibraryByType),
     LibraryWithFilters(ActionLibraryWithFilters),
     MetaDetails(ActionMetaDetails),
     StreamingServer(ActionStreamingServer),
+    CastSession(ActionCastSession),
+    WatchParty(ActionWatchParty),
     Player(ActionPlayer),
     Load(ActionLoad),
     Search(ActionSearch),
@@ -2,7 +2,8 @@ use crate::models::ctx::CtxError;
 use crate::models::player::AnalyticsContext as PlayerAnalyticsContext;
 use crate::types::api::AuthRequest;
 use crate::types::library::LibraryItemId;
-use crate::types::profile::{AuthKey, Settings, UID};
+use crate::types::profile::{AuthKey, BoardLayout, Settings, UID};
+use crate::types::watch_party::WatchPartyCommand;
 use serde::Serialize;
 use url::Url;
 
@@ -30,6 +31,14 @@ pub enum Event {
         is_binge_enabled: bool,
         is_playing_next_video: bool,
     },
+    /// The playback should be stopped by the UI.
+    PlayerSleepTimerElapsed {
+        context: PlayerAnalyticsContext,
+    },
+    /// The UI should ask the user if they're still watching.
+    PlayerStillWatchingRequested {
+        context: PlayerAnalyticsContext,
+    },
     TraktPlaying {
         context: PlayerAnalyticsContext,
     },
@@ -48,6 +57,21 @@ pub enum Event {
     SearchHistoryPushedToStorage {
         uid: UID,
     },
+    LanguagePreferencesPushedToStorage {
+        uid: UID,
+    },
+    IntrosPushedToStorage {
+        uid: UID,
+    },
+    PlayQueuePushedToStorage {
+        uid: UID,
+    },
+    WatchHistoryPushedToStorage {
+        uid: UID,
+    },
+    AddonsHistoryPushedToStorage {
+        uid: UID,
+    },
     NotificationsPushedToStorage {
         ids: Vec<String>,
     },
@@ -109,6 +133,12 @@ pub enum Event {
         transport_url: Url,
         id: String,
     },
+    AddonsSnapshotRestored {
+        id: u64,
+    },
+    BoardLayoutUpdated {
+        board_layout: BoardLayout,
+    },
     SettingsUpdated {
         settings: Settings,
     },
@@ -142,6 +172,12 @@ pub enum Event {
     PlayingOnDevice {
         device: String,
     },
+    /// A command of another member which the player should apply,
+    /// either sent by them or issued to correct the drift from the leader.
+    WatchPartyCommandReceived {
+        member_id: String,
+        command: WatchPartyCommand,
+    },
     Error {
         error: CtxError,
         source: Box<Event>,
@@ -4,22 +4,26 @@ use crate::models::common::ResourceLoadable;
 use crate::models::ctx::CtxError;
 use crate::models::link::LinkError;
 use crate::models::local_search::Searchable;
+use crate::models::player::Selected as PlayerSelected;
 use crate::models::streaming_server::PlaybackDevice;
 use crate::runtime::EnvError;
 use crate::types::addon::{Descriptor, Manifest, ResourceRequest, ResourceResponse};
 use crate::types::api::{
-    APIRequest, AuthRequest, DataExportResponse, DatastoreRequest, GetModalResponse,
-    GetNotificationResponse, LinkCodeResponse, LinkDataResponse, SeekLogRequest, SkipGapsRequest,
-    SkipGapsResponse, SuccessResponse,
+    APIRequest, AuthRequest, CollectionResponse, DataExportResponse, DatastoreRequest,
+    GetModalResponse, GetNotificationResponse, LinkCodeResponse, LinkDataResponse, SeekLog,
+    SeekLogRequest, SkipGapsRequest, SkipGapsResponse, SuccessResponse,
 };
 use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemId};
 use crate::types::profile::{Auth, AuthKey, Profile, User};
 use crate::types::streaming_server::{
-    DeviceInfo, GetHTTPSResponse, NetworkInfo, SettingsResponse, Statistics, StatisticsRequest,
+    CastingStatus, DeviceInfo, GetHTTPSResponse, NetworkInfo, SettingsResponse, Statistics,
+    StatisticsRequest,
 };
 use crate::types::streams::StreamItemState;
+use crate::types::watch_history::WatchHistoryItem;
+use crate::types::watch_party::WatchPartyEnvelope;
 use crate::types::{
-    resource::{MetaItem, Stream},
+    resource::{MetaItem, SeriesInfo, Stream},
     torrent::InfoHash,
 };
 
@@ -32,7 +36,7 @@ pub type CtxStorageResponse = (
 #[derive(Debug)]
 pub struct CtxAuthResponse {
     pub auth: Auth,
-    pub addons_result: Result<Vec<Descriptor>, CtxError>,
+    pub addons_result: Result<CollectionResponse, CtxError>,
     pub library_items_result: Result<Vec<LibraryItem>, CtxError>,
 }
 
@@ -46,7 +50,7 @@ pub enum Internal {
     /// Result for authenticate to API.
     CtxAuthResult(AuthRequest, Result<CtxAuthResponse, CtxError>),
     /// Result for pull addons from API.
-    AddonsAPIResult(APIRequest, Result<Vec<Descriptor>, CtxError>),
+    AddonsAPIResult(APIRequest, Result<CollectionResponse, CtxError>),
     /// Result for pull user from API.
     UserAPIResult(APIRequest, Result<User, CtxError>),
     /// Result for library sync plan with API.
@@ -62,6 +66,8 @@ pub enum Internal {
     InstallAddon(Descriptor),
     /// Dispatched when addons needs to be uninstalled.
     UninstallAddon(Descriptor),
+    /// Dispatched when addons needs to be upgraded.
+    UpgradeAddon(Descriptor),
     UninstallTraktAddon,
     /// Dispatched when a new stream is loaded into the Player.
     StreamLoaded {
@@ -89,6 +95,16 @@ pub enum Internal {
     StreamsChanged(bool),
     /// Search history has changed.
     SearchHistoryChanged,
+    /// Language preferences of the series have changed.
+    LanguagePreferencesChanged,
+    /// Intros learned on this device have changed.
+    IntrosChanged,
+    /// The videos queued to be played next have changed.
+    PlayQueueChanged,
+    /// The viewing sessions in the watch history have changed.
+    WatchHistoryChanged,
+    /// The snapshots of the installed addons have changed.
+    AddonsHistoryChanged,
     /// User notifications have changed
     NotificationsChanged,
     /// Pulling of notifications triggered either by the user (with an action) or
@@ -128,6 +144,19 @@ pub enum Internal {
         (Url, StatisticsRequest),
         Result<Option<Statistics>, EnvError>,
     ),
+    /// Result for a command sent to the player of a casting device.
+    CastSessionCommandResult(String, Result<(), EnvError>),
+    /// Result for polling the status of the player of a casting device.
+    CastSessionStatusResult(String, Result<CastingStatus, EnvError>),
+    /// The time of the player has changed, in milliseconds.
+    PlayerTimeChanged(u64),
+    PlayerPausedChanged(bool),
+    /// The player has loaded a stream.
+    PlayerLoaded(Box<PlayerSelected>),
+    /// The player has stopped playing a video, either by unloading or loading another one.
+    PlayerSessionEnded(Box<WatchHistoryItem>),
+    /// Result for receiving the messages of a watch party.
+    WatchPartyReceiveResult(Url, Result<Vec<WatchPartyEnvelope>, EnvError>),
     /// Result for fetching resource from addons.
     ResourceRequestResult(ResourceRequest, Box<Result<ResourceResponse, EnvError>>),
     /// Result for fetching manifest from addon.
@@ -142,6 +171,15 @@ pub enum Internal {
     SeekLogsResult(SeekLogRequest, Result<SuccessResponse, CtxError>),
     /// Retrieve the skip gaps for skipping intro and outro.
     SkipGapsResult(SkipGapsRequest, Result<SkipGapsResponse, CtxError>),
+    /// Dispatched when the player is done with the seeks made during an episode,
+    /// used for learning its intro locally.
+    SeekHistoryRecorded {
+        meta_id: String,
+        series_info: SeriesInfo,
+        /// Duration of the episode, in milliseconds
+        duration: u64,
+        seek_history: Vec<SeekLog>,
+    },
     /// The result of querying the data for LocalSearch
     LoadLocalSearchResult(Url, Result<Vec<Searchable>, EnvError>),
     /// Result for getModal request
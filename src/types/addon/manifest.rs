@@ -4,11 +4,13 @@ use derivative::Derivative;
 use derive_more::Deref;
 use either::Either;
 use serde::{Deserialize, Deserializer, Serialize};
-use serde_with::{serde_as, DefaultOnError, DefaultOnNull, DeserializeAs, NoneAsEmptyString};
+use serde_with::{
+    serde_as, DefaultOnError, DefaultOnNull, DeserializeAs, NoneAsEmptyString, VecSkipError,
+};
 use url::Url;
 
-use crate::constants::SKIP_EXTRA_PROP;
-use crate::types::addon::{ExtraValue, ResourcePath};
+use crate::constants::{LANGUAGE_EXTRA_PROP, SKIP_EXTRA_PROP};
+use crate::types::addon::{ExtraValue, ManifestConfig, ResourcePath};
 use crate::types::{UniqueVec, UniqueVecAdapter};
 
 /// Re-export the semver::Version
@@ -102,6 +104,10 @@ pub struct Manifest {
     pub addon_catalogs: Vec<ManifestCatalog>,
     #[serde(default)]
     pub behavior_hints: ManifestBehaviorHints,
+    /// The settings of a configurable addon, invalid settings are skipped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde_as(deserialize_as = "DefaultOnError<VecSkipError<_>>")]
+    pub config: Vec<ManifestConfig>,
 }
 
 impl Manifest {
@@ -240,7 +246,7 @@ impl From<&str> for ManifestResource {
 
 impl ManifestResource {
     #[inline]
-    fn name(&self) -> &str {
+    pub fn name(&self) -> &str {
         match self {
             ManifestResource::Short(name) => name,
             ManifestResource::Full { name, .. } => name,
@@ -260,6 +266,18 @@ pub struct ManifestCatalog {
 }
 
 impl ManifestCatalog {
+    /// Returns the language if the catalog declares the `language` extra
+    /// and the language is one of its options, the extra without options accepts any language.
+    pub fn supported_language<'a>(&self, language: &'a str) -> Option<&'a str> {
+        self.extra
+            .iter()
+            .find(|extra_prop| extra_prop.name == LANGUAGE_EXTRA_PROP.name)
+            .filter(|extra_prop| {
+                extra_prop.options.is_empty()
+                    || extra_prop.options.iter().any(|option| option == language)
+            })
+            .map(|_| language)
+    }
     pub fn is_extra_supported(&self, extra: &[ExtraValue]) -> bool {
         let all_supported = extra.iter().all(|extra_value| {
             self.extra
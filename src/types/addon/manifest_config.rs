@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// A setting of a configurable addon, see [`ManifestBehaviorHints::configurable`].
+///
+/// The values of all settings are strings, a checked checkbox has the value
+/// [`ManifestConfig::CHECKBOX_CHECKED`].
+///
+/// [`ManifestBehaviorHints::configurable`]: crate::types::addon::ManifestBehaviorHints::configurable
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestConfig {
+    pub key: String,
+    pub r#type: ManifestConfigType,
+    pub title: Option<String>,
+    /// The options of a [`ManifestConfigType::Select`]
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl ManifestConfig {
+    pub const CHECKBOX_CHECKED: &'static str = "checked";
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestConfigType {
+    Text,
+    Number,
+    Password,
+    Checkbox,
+    Select,
+}
@@ -4,8 +4,14 @@ pub use descriptor::*;
 mod manifest;
 pub use manifest::*;
 
+mod manifest_config;
+pub use manifest_config::*;
+
 mod request;
 pub use request::*;
 
 mod response;
 pub use response::*;
+
+mod validation;
+pub use validation::*;
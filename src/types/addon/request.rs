@@ -1,10 +1,15 @@
+use std::collections::HashMap;
+
 use derive_more::{From, Into};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::{
-    constants::CATALOG_RESOURCE_NAME,
-    types::addon::{Descriptor, ExtraProp, ManifestResource},
+    constants::{CATALOG_RESOURCE_NAME, LANGUAGE_EXTRA_PROP},
+    types::{
+        addon::{Descriptor, ExtraProp, ManifestResource},
+        profile::AddonResourcePriority,
+    },
 };
 
 #[derive(Clone, From, Into, PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -159,15 +164,26 @@ pub enum AggrRequest<'a> {
     AllCatalogs {
         extra: &'a Vec<ExtraValue>,
         r#type: &'a Option<String>,
+        /// Passed to the catalogs which declare the `language` extra with it as an option
+        language: Option<&'a str>,
     },
     CatalogsFiltered(Vec<ExtraType>),
-    AllOfResource(ResourcePath),
+    /// The addons supporting the resource, ordered and filtered
+    /// by the user's priority of the addons for the resource.
+    AllOfResource {
+        path: ResourcePath,
+        priorities: &'a HashMap<String, AddonResourcePriority>,
+    },
 }
 
 impl AggrRequest<'_> {
     pub fn plan<'a>(&self, addons: &'a [Descriptor]) -> Vec<(&'a Descriptor, ResourceRequest)> {
         match &self {
-            AggrRequest::AllCatalogs { extra, r#type } => addons
+            AggrRequest::AllCatalogs {
+                extra,
+                r#type,
+                language,
+            } => addons
                 .iter()
                 .flat_map(|addon| {
                     addon
@@ -182,6 +198,14 @@ impl AggrRequest<'_> {
                                     .unwrap_or(true)
                         })
                         .map(move |catalog| {
+                            let extra = match language
+                                .and_then(|language| catalog.supported_language(language))
+                            {
+                                Some(language) => extra
+                                    .to_vec()
+                                    .extend_one(&LANGUAGE_EXTRA_PROP, Some(language.to_owned())),
+                                _ => extra.to_vec(),
+                            };
                             (
                                 addon,
                                 ResourceRequest::new(
@@ -190,7 +214,7 @@ impl AggrRequest<'_> {
                                         CATALOG_RESOURCE_NAME,
                                         &catalog.r#type,
                                         &catalog.id,
-                                        extra,
+                                        &extra,
                                     ),
                                 ),
                             )
@@ -356,16 +380,28 @@ impl AggrRequest<'_> {
 
                 addon_requests
             }
-            AggrRequest::AllOfResource(path) => addons
-                .iter()
-                .filter(|addon| addon.manifest.is_resource_supported(path))
-                .map(|addon| {
-                    (
-                        addon,
-                        ResourceRequest::new(addon.transport_url.to_owned(), path.to_owned()),
-                    )
-                })
-                .collect(),
+            AggrRequest::AllOfResource { path, priorities } => {
+                let priority = priorities.get(&path.resource);
+                let mut addons = addons
+                    .iter()
+                    .filter(|addon| addon.manifest.is_resource_supported(path))
+                    .filter(|addon| {
+                        !priority.is_some_and(|priority| priority.is_disabled(&addon.transport_url))
+                    })
+                    .collect::<Vec<_>>();
+                if let Some(priority) = priority {
+                    addons.sort_by_key(|addon| priority.position(&addon.transport_url));
+                }
+                addons
+                    .into_iter()
+                    .map(|addon| {
+                        (
+                            addon,
+                            ResourceRequest::new(addon.transport_url.to_owned(), path.to_owned()),
+                        )
+                    })
+                    .collect()
+            }
         }
     }
 }
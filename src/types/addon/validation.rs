@@ -0,0 +1,334 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::constants::{META_RESOURCE_NAME, SEARCH_EXTRA_NAME};
+use crate::types::addon::{DescriptorPreview, Manifest, ManifestResource, ResourceResponse};
+use crate::types::resource::{MetaItem, MetaItemPreview, PosterShape, Stream, Subtitles};
+
+/// A problem in an addon [`Manifest`] or response which core would otherwise
+/// silently tolerate, e.g. by skipping the invalid items.
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub enum ValidationIssue {
+    /// The value at the path failed to deserialize
+    #[error("{path}: {message}")]
+    Deserialization { path: String, message: String },
+    /// The catalog can't be requested as there are no options to pick a value for the extra from
+    #[error(
+        "Catalog {catalog_type}/{catalog_id} requires the {extra_name} extra which has no options"
+    )]
+    RequiredExtraWithoutOptions {
+        catalog_type: String,
+        catalog_id: String,
+        extra_name: String,
+    },
+    /// None of the resources supports the type
+    #[error("Type {0} is not supported by any of the resources")]
+    TypeWithoutResource(String),
+    /// The returned id doesn't match any of the id prefixes of the meta resource,
+    /// so the item's details are never requested from the addon
+    #[error("{path}: {id} does not match any of the id prefixes")]
+    IdPrefixMismatch { path: String, id: String },
+    /// The poster shape is unknown and falls back to the default one
+    #[error("{path}: {value} is not a valid poster shape")]
+    InvalidPosterShape { path: String, value: String },
+}
+
+/// Deserializes the manifest and validates it, see [`validate_manifest`].
+pub fn validate_manifest_json(manifest: &str) -> Vec<ValidationIssue> {
+    let mut deserializer = serde_json::Deserializer::from_str(manifest);
+    match serde_path_to_error::deserialize::<_, Manifest>(&mut deserializer) {
+        Ok(manifest) => validate_manifest(&manifest),
+        Err(error) => vec![ValidationIssue::Deserialization {
+            path: error.path().to_string(),
+            message: error.inner().to_string(),
+        }],
+    }
+}
+
+/// Reports the catalogs which require an extra without options
+/// (other than `search`) and the types which no resource supports.
+pub fn validate_manifest(manifest: &Manifest) -> Vec<ValidationIssue> {
+    let extra_issues = manifest
+        .catalogs
+        .iter()
+        .chain(&manifest.addon_catalogs)
+        .flat_map(|catalog| {
+            catalog
+                .extra
+                .iter()
+                .filter(|extra| {
+                    extra.is_required && extra.options.is_empty() && extra.name != SEARCH_EXTRA_NAME
+                })
+                .map(|extra| ValidationIssue::RequiredExtraWithoutOptions {
+                    catalog_type: catalog.r#type.to_owned(),
+                    catalog_id: catalog.id.to_owned(),
+                    extra_name: extra.name.to_owned(),
+                })
+                .collect::<Vec<_>>()
+        });
+    let type_issues = manifest
+        .types
+        .iter()
+        .filter(|r#type| {
+            !manifest.resources.iter().any(|resource| match resource {
+                ManifestResource::Short(_) => true,
+                ManifestResource::Full { types, .. } => {
+                    types.as_ref().map_or(true, |types| types.contains(r#type))
+                }
+            })
+        })
+        .map(|r#type| ValidationIssue::TypeWithoutResource(r#type.to_owned()));
+    extra_issues.chain(type_issues).collect()
+}
+
+/// Validates a response of the addon, e.g. `{ "metas": [...] }`.
+///
+/// Unlike [`ResourceResponse`] deserialization, which skips the invalid items,
+/// every item is deserialized on its own and reported with its path.
+/// The ids of the meta items are checked against the id prefixes of the meta resource.
+pub fn validate_response(manifest: &Manifest, response: &str) -> Vec<ValidationIssue> {
+    let response = match serde_json::from_str::<Value>(response) {
+        Ok(response) => response,
+        Err(error) => {
+            return vec![ValidationIssue::Deserialization {
+                path: ".".to_owned(),
+                message: error.to_string(),
+            }]
+        }
+    };
+    let id_prefixes = meta_id_prefixes(manifest);
+    let mut issues = vec![];
+    for (key, value) in response.as_object().into_iter().flatten() {
+        let items = match value {
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| (format!("{key}[{index}]"), item))
+                .collect::<Vec<_>>(),
+            Value::Null => vec![],
+            item => vec![(key.to_owned(), item)],
+        };
+        for (path, item) in items {
+            let deserialization_issue = match key.as_str() {
+                "metas" => deserialization_issue::<MetaItemPreview>(&path, item),
+                "metasDetailed" | "meta" => deserialization_issue::<MetaItem>(&path, item),
+                "streams" => deserialization_issue::<Stream>(&path, item),
+                "subtitles" => deserialization_issue::<Subtitles>(&path, item),
+                "addons" => deserialization_issue::<DescriptorPreview>(&path, item),
+                _ => None,
+            };
+            issues.extend(deserialization_issue);
+            if matches!(key.as_str(), "metas" | "metasDetailed" | "meta") {
+                issues.extend(id_prefix_issue(&path, item, id_prefixes));
+                issues.extend(poster_shape_issue(&path, item));
+            }
+        }
+    }
+    let item_deserialization_failed = issues
+        .iter()
+        .any(|issue| matches!(issue, ValidationIssue::Deserialization { .. }));
+    if !item_deserialization_failed {
+        if let Err(error) = serde_json::from_value::<ResourceResponse>(response) {
+            issues.insert(
+                0,
+                ValidationIssue::Deserialization {
+                    path: ".".to_owned(),
+                    message: error.to_string(),
+                },
+            );
+        }
+    }
+    issues
+}
+
+fn deserialization_issue<T: DeserializeOwned>(path: &str, item: &Value) -> Option<ValidationIssue> {
+    serde_path_to_error::deserialize::<_, T>(item)
+        .err()
+        .map(|error| ValidationIssue::Deserialization {
+            path: match error.path().to_string().as_str() {
+                "." => path.to_owned(),
+                item_path if item_path.starts_with('[') => format!("{path}{item_path}"),
+                item_path => format!("{path}.{item_path}"),
+            },
+            message: error.inner().to_string(),
+        })
+}
+
+fn meta_id_prefixes(manifest: &Manifest) -> Option<&Vec<String>> {
+    manifest
+        .resources
+        .iter()
+        .find(|resource| resource.name() == META_RESOURCE_NAME)
+        .and_then(|resource| match resource {
+            ManifestResource::Short(_) => manifest.id_prefixes.as_ref(),
+            ManifestResource::Full { id_prefixes, .. } => {
+                id_prefixes.as_ref().or(manifest.id_prefixes.as_ref())
+            }
+        })
+}
+
+fn id_prefix_issue(
+    path: &str,
+    item: &Value,
+    id_prefixes: Option<&Vec<String>>,
+) -> Option<ValidationIssue> {
+    let id = item.get("id")?.as_str()?;
+    let id_prefixes = id_prefixes?;
+    if id_prefixes.iter().any(|prefix| id.starts_with(prefix)) {
+        return None;
+    }
+    Some(ValidationIssue::IdPrefixMismatch {
+        path: format!("{path}.id"),
+        id: id.to_owned(),
+    })
+}
+
+fn poster_shape_issue(path: &str, item: &Value) -> Option<ValidationIssue> {
+    let value = item.get("posterShape")?;
+    // unknown poster shapes are deserialized as the default one
+    let poster_shape = serde_json::from_value::<PosterShape>(value.to_owned()).ok()?;
+    if serde_json::to_value(poster_shape).ok().as_ref() == Some(value) {
+        return None;
+    }
+    Some(ValidationIssue::InvalidPosterShape {
+        path: format!("{path}.posterShape"),
+        value: value
+            .as_str()
+            .map(ToOwned::to_owned)
+            .unwrap_or(value.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn manifest() -> Manifest {
+        serde_json::from_value(json!({
+            "id": "addon",
+            "version": "0.0.1",
+            "name": "Addon",
+            "types": ["movie", "series", "channel"],
+            "resources": [
+                "catalog",
+                { "name": "meta", "types": ["movie", "series"], "idPrefixes": ["tt"] },
+                { "name": "stream", "types": ["movie"] },
+            ],
+            "catalogs": [
+                {
+                    "type": "movie",
+                    "id": "top",
+                    "extra": [
+                        { "name": "genre", "isRequired": true },
+                        { "name": "skip" },
+                    ],
+                },
+                {
+                    "type": "movie",
+                    "id": "search",
+                    "extra": [{ "name": "search", "isRequired": true }],
+                },
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_manifest() {
+        assert_eq!(
+            validate_manifest(&manifest()),
+            vec![ValidationIssue::RequiredExtraWithoutOptions {
+                catalog_type: "movie".to_owned(),
+                catalog_id: "top".to_owned(),
+                extra_name: "genre".to_owned(),
+            }],
+            "Short resources support all the types"
+        );
+
+        let mut manifest = manifest();
+        manifest.resources.remove(0);
+        manifest.catalogs.clear();
+        assert_eq!(
+            validate_manifest(&manifest),
+            vec![ValidationIssue::TypeWithoutResource("channel".to_owned())]
+        );
+
+        let issues = validate_manifest_json(r#"{ "id": "addon", "version": "invalid" }"#);
+        assert!(
+            matches!(&issues[..], [ValidationIssue::Deserialization { path, .. }] if path == "version"),
+            "{issues:?}"
+        );
+    }
+
+    #[test]
+    fn test_validate_response() {
+        let response = json!({
+            "metas": [
+                { "id": "tt1", "type": "movie", "name": "Movie", "posterShape": "landscape" },
+                { "id": "kitsu:1", "type": "movie", "name": "Movie", "posterShape": "wide" },
+                { "id": "tt2", "type": "movie", "name": "Movie", "released": "yesterday" },
+            ],
+        });
+        let issues = validate_response(&manifest(), &response.to_string());
+        assert_eq!(
+            issues[..2],
+            [
+                ValidationIssue::IdPrefixMismatch {
+                    path: "metas[1].id".to_owned(),
+                    id: "kitsu:1".to_owned(),
+                },
+                ValidationIssue::InvalidPosterShape {
+                    path: "metas[1].posterShape".to_owned(),
+                    value: "wide".to_owned(),
+                },
+            ]
+        );
+        assert!(
+            matches!(&issues[2..], [ValidationIssue::Deserialization { path, .. }] if path == "metas[2].released"),
+            "{issues:?}"
+        );
+
+        let response = json!({
+            "streams": [
+                { "url": "https://stream.com/video.mp4" },
+                { "infoHash": "not a hash" },
+            ],
+        });
+        let issues = validate_response(&manifest(), &response.to_string());
+        assert!(
+            matches!(&issues[..], [ValidationIssue::Deserialization { path, .. }] if path.starts_with("streams[1]")),
+            "Streams which core would skip are reported: {issues:?}"
+        );
+
+        let issues = validate_response(&manifest(), r#"{ "unknown": [] }"#);
+        assert!(
+            matches!(&issues[..], [ValidationIssue::Deserialization { path, .. }] if path == "."),
+            "{issues:?}"
+        );
+    }
+
+    #[test]
+    fn test_meta_id_prefixes() {
+        let mut manifest = manifest();
+        manifest.id_prefixes = Some(vec!["kitsu:".to_owned()]);
+        assert_eq!(
+            meta_id_prefixes(&manifest),
+            Some(&vec!["tt".to_owned()]),
+            "The id prefixes of the meta resource come first"
+        );
+
+        manifest.resources[1] = ManifestResource::Full {
+            name: META_RESOURCE_NAME.to_owned(),
+            types: Some(vec!["movie".to_owned()]),
+            id_prefixes: None,
+        };
+        assert_eq!(
+            meta_id_prefixes(&manifest),
+            Some(&vec!["kitsu:".to_owned()]),
+            "The id prefixes of the manifest are used by a meta resource without its own"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::addon::Descriptor;
+use crate::types::addons_history::AddonsSnapshot;
+use crate::types::profile::UID;
+
+const MAX_SNAPSHOTS: usize = 20;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddonsHistoryBucket {
+    pub uid: UID,
+    /// The snapshots of the installed addons, the most recent first
+    pub snapshots: Vec<AddonsSnapshot>,
+}
+
+impl AddonsHistoryBucket {
+    pub fn new(uid: UID) -> Self {
+        AddonsHistoryBucket {
+            uid,
+            snapshots: vec![],
+        }
+    }
+    /// Records the replacement of the `previous` addons with the `next` ones,
+    /// dropping the oldest snapshots once there are too many.
+    ///
+    /// The `previous` addons are recorded too if they are not the latest snapshot,
+    /// so that the first change can be rolled back as well.
+    ///
+    /// Returns `false` if the addons have not changed.
+    pub fn add(
+        &mut self,
+        previous: &[Descriptor],
+        next: &[Descriptor],
+        last_modified: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        if previous == next {
+            return false;
+        }
+        if self.snapshots.first().map(|snapshot| &snapshot.addons[..]) != Some(previous) {
+            self.push(previous, None, now);
+        }
+        self.push(next, last_modified, now);
+        self.snapshots.truncate(MAX_SNAPSHOTS);
+        true
+    }
+    pub fn get(&self, id: u64) -> Option<&AddonsSnapshot> {
+        self.snapshots.iter().find(|snapshot| snapshot.id == id)
+    }
+    fn push(
+        &mut self,
+        addons: &[Descriptor],
+        last_modified: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        let id = self
+            .snapshots
+            .first()
+            .map(|snapshot| snapshot.id + 1)
+            .unwrap_or_default();
+        self.snapshots.insert(
+            0,
+            AddonsSnapshot {
+                id,
+                addons: addons.to_owned(),
+                last_modified,
+                created: now,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use url::Url;
+
+    use super::*;
+
+    fn addons(names: &[&str]) -> Vec<Descriptor> {
+        names
+            .iter()
+            .map(|name| Descriptor {
+                manifest: Default::default(),
+                transport_url: Url::parse(&format!("https://{name}/manifest.json")).unwrap(),
+                flags: Default::default(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_add() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut bucket = AddonsHistoryBucket::default();
+        assert!(!bucket.add(&addons(&["a"]), &addons(&["a"]), None, now));
+        assert!(bucket.add(&addons(&["a"]), &addons(&["a", "b"]), None, now));
+        assert!(bucket.add(&addons(&["a", "b"]), &addons(&["b"]), Some(now), now));
+        assert_eq!(
+            bucket
+                .snapshots
+                .iter()
+                .map(|snapshot| (snapshot.id, snapshot.addons.len()))
+                .collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2), (0, 1)],
+            "The previous addons are recorded only if they are not the latest snapshot"
+        );
+        assert_eq!(bucket.get(2).unwrap().last_modified, Some(now));
+        assert_eq!(bucket.get(3), None);
+
+        for _ in 0..MAX_SNAPSHOTS {
+            bucket.add(&addons(&["b"]), &addons(&["c"]), None, now);
+            bucket.add(&addons(&["c"]), &addons(&["b"]), None, now);
+        }
+        assert_eq!(bucket.snapshots.len(), MAX_SNAPSHOTS);
+        assert_eq!(bucket.get(0), None, "Oldest snapshots are dropped");
+    }
+}
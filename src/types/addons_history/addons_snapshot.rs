@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::addon::Descriptor;
+
+/// The installed addons at some point in time.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonsSnapshot {
+    /// Unique id of the snapshot, increasing with each new one
+    pub id: u64,
+    pub addons: Vec<Descriptor>,
+    /// The `lastModified` of the collection if it was pulled from the API
+    pub last_modified: Option<DateTime<Utc>>,
+    pub created: DateTime<Utc>,
+}
@@ -0,0 +1,5 @@
+mod addons_snapshot;
+pub use addons_snapshot::*;
+
+mod addons_history_bucket;
+pub use addons_history_bucket::*;
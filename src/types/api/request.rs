@@ -3,7 +3,7 @@ use core::fmt;
 use crate::constants::{API_URL, LINK_API_URL};
 use crate::types::addon::Descriptor;
 use crate::types::library::LibraryItem;
-use crate::types::profile::{AuthKey, GDPRConsent, User};
+use crate::types::profile::{AuthKey, BoardLayout, GDPRConsent, User};
 use crate::types::resource::SeriesInfo;
 use chrono::{DateTime, Local};
 #[cfg(test)]
@@ -58,6 +58,7 @@ pub enum APIRequest {
     AddonCollectionSet {
         auth_key: AuthKey,
         addons: Vec<Descriptor>,
+        board_layout: BoardLayout,
     },
     #[serde(rename_all = "camelCase")]
     GetUser {
@@ -11,7 +11,7 @@ use url::Url;
 use crate::types::{
     addon::Descriptor,
     library::LibraryItem,
-    profile::{AuthKey, User},
+    profile::{AuthKey, BoardLayout, User},
     True,
 };
 
@@ -31,11 +31,14 @@ pub struct APIError {
     pub code: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CollectionResponse {
     pub addons: Vec<Descriptor>,
     pub last_modified: DateTime<Utc>,
+    /// `None` if the board layout has never been pushed with the collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_layout: Option<BoardLayout>,
 }
 
 #[derive(Serialize, Deserialize)]
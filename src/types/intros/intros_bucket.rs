@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::intros::SeriesIntros;
+use crate::types::profile::UID;
+
+/// The intros learned on this device, keyed by the id of the series.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntrosBucket {
+    pub uid: UID,
+    pub items: HashMap<String, SeriesIntros>,
+}
+
+impl IntrosBucket {
+    pub fn new(uid: UID) -> Self {
+        IntrosBucket {
+            uid,
+            items: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod series_intros;
+pub use series_intros::*;
+
+mod intros_bucket;
+pub use intros_bucket::*;
@@ -0,0 +1,306 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::PLAYER_IGNORE_SEEK_AFTER;
+use crate::types::api::SeekLog;
+use crate::types::player::IntroData;
+use crate::types::resource::SeriesInfo;
+
+/// Skipping forward less than this is not considered skipping an intro, in milliseconds
+const MIN_INTRO_LENGTH: u64 = 10_000;
+/// Skipping forward more than this is not considered skipping an intro, in milliseconds
+const MAX_INTRO_LENGTH: u64 = 180_000;
+/// Episodes with a difference in duration less than `duration * coef` are considered similar
+const SIMILAR_DURATION_COEF: f64 = 0.1;
+/// Skips which start and end this close to each other are considered the same intro, in milliseconds
+const SAME_INTRO_TOLERANCE: u64 = 5_000;
+/// The number of episodes needed to agree on an intro
+const MIN_AGREEING_EPISODES: usize = 2;
+const MAX_SAMPLES: usize = 30;
+
+/// An intro skip made by the user during an episode.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntroSample {
+    pub season: u32,
+    pub episode: u32,
+    /// Duration of the episode, in milliseconds
+    pub duration: u64,
+    /// In milliseconds
+    pub from: u64,
+    /// In milliseconds
+    pub to: u64,
+}
+
+impl IntroSample {
+    fn is_same_intro(&self, other: &IntroSample) -> bool {
+        self.from.abs_diff(other.from) <= SAME_INTRO_TOLERANCE
+            && self.to.abs_diff(other.to) <= SAME_INTRO_TOLERANCE
+    }
+}
+
+/// The intros of a series learned from the seeks of the user,
+/// used when the skip gaps API has no data for the stream.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesIntros {
+    pub samples: Vec<IntroSample>,
+    /// Modification time
+    #[serde(rename = "_mtime")]
+    pub mtime: DateTime<Utc>,
+}
+
+impl SeriesIntros {
+    /// Learns from the first seek which looks like skipping the intro.
+    ///
+    /// Returns `true` if a sample was recorded.
+    pub fn learn(
+        &mut self,
+        series_info: &SeriesInfo,
+        duration: u64,
+        seek_history: &[SeekLog],
+        mtime: DateTime<Utc>,
+    ) -> bool {
+        let intro_seek = seek_history.iter().find(|seek| {
+            seek.from < PLAYER_IGNORE_SEEK_AFTER
+                && (MIN_INTRO_LENGTH..=MAX_INTRO_LENGTH)
+                    .contains(&seek.to.saturating_sub(seek.from))
+        });
+        match intro_seek {
+            Some(seek) if duration > 0 => {
+                self.samples.retain(|sample| {
+                    sample.season != series_info.season || sample.episode != series_info.episode
+                });
+                self.samples.push(IntroSample {
+                    season: series_info.season,
+                    episode: series_info.episode,
+                    duration,
+                    from: seek.from,
+                    to: seek.to,
+                });
+                if self.samples.len() > MAX_SAMPLES {
+                    self.samples.drain(..self.samples.len() - MAX_SAMPLES);
+                }
+                self.mtime = mtime;
+                true
+            }
+            _ => false,
+        }
+    }
+    /// Finds the intro which the user skipped in other episodes
+    /// of the same season having a similar duration.
+    pub fn detect(&self, series_info: &SeriesInfo, duration: u64) -> Option<IntroData> {
+        let similar_samples = self
+            .samples
+            .iter()
+            .filter(|sample| {
+                sample.season == series_info.season
+                    && sample.episode != series_info.episode
+                    && (sample.duration.abs_diff(duration) as f64)
+                        <= duration as f64 * SIMILAR_DURATION_COEF
+            })
+            .collect::<Vec<_>>();
+        let agreeing_samples = similar_samples
+            .iter()
+            .map(|sample| {
+                similar_samples
+                    .iter()
+                    .filter(|other| sample.is_same_intro(other))
+                    .collect::<Vec<_>>()
+            })
+            .max_by_key(|agreeing_samples| agreeing_samples.len())
+            .filter(|agreeing_samples| agreeing_samples.len() >= MIN_AGREEING_EPISODES)?;
+        let count = agreeing_samples.len() as u64;
+        let from = agreeing_samples
+            .iter()
+            .map(|sample| sample.from)
+            .sum::<u64>()
+            / count;
+        let to = agreeing_samples.iter().map(|sample| sample.to).sum::<u64>() / count;
+        let samples_duration = agreeing_samples
+            .iter()
+            .map(|sample| sample.duration)
+            .sum::<u64>()
+            / count;
+        let duration_diff_in_secs = samples_duration.abs_diff(duration) / 1000;
+        Some(IntroData {
+            from,
+            to,
+            duration: if duration_diff_in_secs > 0 {
+                Some(to.abs_diff(from))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    const EPISODE_DURATION: u64 = 2_400_000;
+
+    fn learn(intros: &mut SeriesIntros, episode: u32, duration: u64, seek_history: &[SeekLog]) {
+        intros.learn(
+            &SeriesInfo { season: 1, episode },
+            duration,
+            seek_history,
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_learn() {
+        let mut intros = SeriesIntros::default();
+        learn(
+            &mut intros,
+            1,
+            EPISODE_DURATION,
+            &[
+                SeekLog {
+                    from: 5_000,
+                    to: 7_000,
+                },
+                SeekLog {
+                    from: 60_000,
+                    to: 150_000,
+                },
+            ],
+        );
+        assert_eq!(
+            intros.samples,
+            vec![IntroSample {
+                season: 1,
+                episode: 1,
+                duration: EPISODE_DURATION,
+                from: 60_000,
+                to: 150_000,
+            }],
+            "Short seeks are ignored"
+        );
+
+        learn(
+            &mut intros,
+            1,
+            EPISODE_DURATION,
+            &[SeekLog {
+                from: 61_000,
+                to: 151_000,
+            }],
+        );
+        assert_eq!(
+            intros.samples.len(),
+            1,
+            "The sample of an episode is replaced"
+        );
+        assert_eq!(intros.samples[0].from, 61_000);
+
+        learn(
+            &mut intros,
+            2,
+            EPISODE_DURATION,
+            &[SeekLog {
+                from: 700_000,
+                to: 790_000,
+            }],
+        );
+        assert_eq!(intros.samples.len(), 1, "Late seeks are ignored");
+    }
+
+    #[test]
+    fn test_detect() {
+        let mut intros = SeriesIntros::default();
+        learn(
+            &mut intros,
+            1,
+            EPISODE_DURATION,
+            &[SeekLog {
+                from: 60_000,
+                to: 150_000,
+            }],
+        );
+        assert_eq!(
+            intros.detect(
+                &SeriesInfo {
+                    season: 1,
+                    episode: 3
+                },
+                EPISODE_DURATION
+            ),
+            None,
+            "A single episode is not enough"
+        );
+
+        learn(
+            &mut intros,
+            2,
+            EPISODE_DURATION,
+            &[SeekLog {
+                from: 62_000,
+                to: 152_000,
+            }],
+        );
+        learn(
+            &mut intros,
+            4,
+            EPISODE_DURATION,
+            &[SeekLog {
+                from: 300_000,
+                to: 390_000,
+            }],
+        );
+        assert_eq!(
+            intros.detect(
+                &SeriesInfo {
+                    season: 1,
+                    episode: 3
+                },
+                EPISODE_DURATION
+            ),
+            Some(IntroData {
+                from: 61_000,
+                to: 151_000,
+                duration: None,
+            })
+        );
+        assert_eq!(
+            intros.detect(
+                &SeriesInfo {
+                    season: 1,
+                    episode: 3
+                },
+                EPISODE_DURATION + 60_000
+            ),
+            Some(IntroData {
+                from: 61_000,
+                to: 151_000,
+                duration: Some(90_000),
+            })
+        );
+        assert_eq!(
+            intros.detect(
+                &SeriesInfo {
+                    season: 1,
+                    episode: 3
+                },
+                EPISODE_DURATION * 2
+            ),
+            None,
+            "Episodes with a different duration are not similar"
+        );
+        assert_eq!(
+            intros.detect(
+                &SeriesInfo {
+                    season: 2,
+                    episode: 1
+                },
+                EPISODE_DURATION
+            ),
+            None,
+            "Other seasons may have a different intro"
+        );
+    }
+}
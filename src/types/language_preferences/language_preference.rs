@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::streams::{AudioTrack, StreamItemState, SubtitleTrack};
+
+/// The audio and subtitles choices of the user for a series,
+/// which are applied to every stream of it, regardless of its binge group.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguagePreference {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_language: Option<String>,
+    /// `None` when the subtitles were turned off
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle_language: Option<String>,
+    /// In milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle_delay: Option<i64>,
+    /// Modification time
+    #[serde(rename = "_mtime")]
+    pub mtime: DateTime<Utc>,
+}
+
+impl LanguagePreference {
+    /// Learns the preference from the state the user has chosen for a stream.
+    ///
+    /// The audio language of the previous preference is retained
+    /// when the state has no audio track chosen, as that's the default track of the stream.
+    pub fn new(
+        state: &StreamItemState,
+        previous: Option<&LanguagePreference>,
+        mtime: DateTime<Utc>,
+    ) -> Self {
+        let audio_language = match &state.audio_track {
+            Some(audio_track) => audio_track.language.to_owned(),
+            _ => previous.and_then(|previous| previous.audio_language.to_owned()),
+        };
+        let subtitle_language = state
+            .subtitle_track
+            .as_ref()
+            .and_then(|subtitle_track| subtitle_track.language.to_owned());
+        let subtitle_delay = subtitle_language.as_ref().and(state.subtitle_delay);
+        LanguagePreference {
+            audio_language,
+            subtitle_language,
+            subtitle_delay,
+            mtime,
+        }
+    }
+    /// Whether both preferences have the same languages and delay, regardless of the time they were made.
+    pub fn is_same_preference(&self, other: &LanguagePreference) -> bool {
+        self.audio_language == other.audio_language
+            && self.subtitle_language == other.subtitle_language
+            && self.subtitle_delay == other.subtitle_delay
+    }
+    /// Applies the languages to a state without chosen tracks,
+    /// which is the case for a stream that doesn't match the previously played one.
+    ///
+    /// The tracks of the stream are not known in advance, so the applied tracks have an empty id
+    /// and the player should choose them by the language alone.
+    /// The subtitle delay is applied only along with the subtitle language.
+    pub fn apply(&self, state: Option<StreamItemState>) -> Option<StreamItemState> {
+        let audio_track = self.audio_language.as_ref().map(|language| AudioTrack {
+            id: String::new(),
+            language: Some(language.to_owned()),
+        });
+        let subtitle_track = self
+            .subtitle_language
+            .as_ref()
+            .map(|language| SubtitleTrack {
+                id: String::new(),
+                embedded: true,
+                language: Some(language.to_owned()),
+            });
+        let subtitle_delay = self.subtitle_language.as_ref().and(self.subtitle_delay);
+        match state {
+            Some(state) => {
+                let (subtitle_track, subtitle_delay) = match state.subtitle_track {
+                    Some(_) => (state.subtitle_track, state.subtitle_delay),
+                    None => (subtitle_track, state.subtitle_delay.or(subtitle_delay)),
+                };
+                Some(StreamItemState {
+                    audio_track: state.audio_track.or(audio_track),
+                    subtitle_track,
+                    subtitle_delay,
+                    ..state
+                })
+            }
+            None if audio_track.is_some() || subtitle_track.is_some() => Some(StreamItemState {
+                subtitle_track,
+                subtitles_origin: None,
+                subtitle_delay,
+                audio_track,
+                audio_delay: None,
+                playback_speed: None,
+                player_type: None,
+            }),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn state(audio_language: Option<&str>, subtitle_language: Option<&str>) -> StreamItemState {
+        StreamItemState {
+            subtitle_track: subtitle_language.map(|language| SubtitleTrack {
+                id: "subtitles".to_owned(),
+                embedded: true,
+                language: Some(language.to_owned()),
+            }),
+            subtitles_origin: None,
+            subtitle_delay: Some(1500),
+            audio_track: audio_language.map(|language| AudioTrack {
+                id: "audio".to_owned(),
+                language: Some(language.to_owned()),
+            }),
+            audio_delay: None,
+            playback_speed: None,
+            player_type: None,
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let mtime = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let preference = LanguagePreference::new(&state(Some("jpn"), Some("eng")), None, mtime);
+        assert_eq!(preference.audio_language.as_deref(), Some("jpn"));
+        assert_eq!(preference.subtitle_language.as_deref(), Some("eng"));
+        assert_eq!(preference.subtitle_delay, Some(1500));
+
+        let next_preference = LanguagePreference::new(&state(None, None), Some(&preference), mtime);
+        assert_eq!(
+            next_preference.audio_language.as_deref(),
+            Some("jpn"),
+            "Audio language is retained when no audio track is chosen"
+        );
+        assert_eq!(next_preference.subtitle_language, None);
+        assert_eq!(next_preference.subtitle_delay, None);
+        assert!(!next_preference.is_same_preference(&preference));
+    }
+
+    #[test]
+    fn test_apply() {
+        let mtime = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let preference = LanguagePreference::new(&state(Some("jpn"), Some("eng")), None, mtime);
+
+        let applied = preference.apply(None).expect("Should have a state");
+        assert_eq!(
+            applied.audio_track,
+            Some(AudioTrack {
+                id: "".to_owned(),
+                language: Some("jpn".to_owned()),
+            }),
+            "Audio track is chosen by the language"
+        );
+        assert_eq!(
+            applied.subtitle_track,
+            Some(SubtitleTrack {
+                id: "".to_owned(),
+                embedded: true,
+                language: Some("eng".to_owned()),
+            }),
+            "Subtitle track is chosen by the language"
+        );
+        assert_eq!(applied.subtitle_delay, Some(1500));
+
+        let chosen = StreamItemState {
+            subtitle_delay: None,
+            ..state(Some("bul"), Some("bul"))
+        };
+        assert_eq!(
+            preference.apply(Some(chosen.to_owned())),
+            Some(chosen),
+            "Chosen tracks are left untouched"
+        );
+
+        let no_subtitles = LanguagePreference::new(&state(None, None), None, mtime);
+        assert_eq!(no_subtitles.apply(None), None);
+    }
+}
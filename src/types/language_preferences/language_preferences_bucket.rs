@@ -0,0 +1,22 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::language_preferences::LanguagePreference;
+use crate::types::profile::UID;
+
+/// The language preferences of the user, keyed by the id of the meta item.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguagePreferencesBucket {
+    pub uid: UID,
+    pub items: HashMap<String, LanguagePreference>,
+}
+
+impl LanguagePreferencesBucket {
+    pub fn new(uid: UID) -> Self {
+        LanguagePreferencesBucket {
+            uid,
+            items: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod language_preference;
+pub use language_preference::*;
+
+mod language_preferences_bucket;
+pub use language_preferences_bucket::*;
@@ -8,6 +8,7 @@ use stremio_watched_bitfield::{WatchedBitField, WatchedField};
 use url::Url;
 
 use crate::{
+    constants::WATCHED_THRESHOLD_COEF,
     runtime::Env,
     types::resource::{MetaItemBehaviorHints, MetaItemPreview, PosterShape, Video},
 };
@@ -106,6 +107,50 @@ impl LibraryItem {
             self.state.times_watched = 0;
         }
     }
+
+    /// Updates the progress once the video is played at `time` of its `duration`,
+    /// counting at most `max_time_watched` as watched since the previous update.
+    ///
+    /// Returns whether the video has just been watched.
+    pub fn update_progress<E: Env>(
+        &mut self,
+        video_id: Option<&str>,
+        time: u64,
+        duration: u64,
+        max_time_watched: u64,
+    ) -> bool {
+        self.state.last_watched = Some(E::now());
+        // if we've selected a new video (like the next episode)
+        if self.state.video_id.as_deref() != video_id {
+            self.state.video_id = video_id.map(ToOwned::to_owned);
+            self.state.overall_time_watched = self
+                .state
+                .overall_time_watched
+                .saturating_add(self.state.time_watched);
+            self.state.time_watched = 0;
+            self.state.flagged_watched = 0;
+        } else {
+            let time_watched = max_time_watched.min(time.saturating_sub(self.state.time_offset));
+            self.state.time_watched = self.state.time_watched.saturating_add(time_watched);
+            self.state.overall_time_watched =
+                self.state.overall_time_watched.saturating_add(time_watched);
+        };
+        self.state.time_offset = time;
+        self.state.duration = duration;
+        let just_watched = self.state.flagged_watched == 0
+            && self.state.time_watched as f64 > self.state.duration as f64 * WATCHED_THRESHOLD_COEF;
+        if just_watched {
+            self.state.flagged_watched = 1;
+            self.state.times_watched = self.state.times_watched.saturating_add(1);
+        };
+        if self.temp && self.state.times_watched == 0 {
+            self.removed = true;
+        };
+        if self.removed {
+            self.temp = true;
+        };
+        just_watched
+    }
 }
 
 impl<E: Env + 'static> From<(&MetaItemPreview, PhantomData<E>)> for LibraryItem {
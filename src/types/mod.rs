@@ -1,15 +1,22 @@
 pub mod addon;
+pub mod addons_history;
 pub mod api;
 pub mod events;
+pub mod intros;
+pub mod language_preferences;
 pub mod library;
 pub mod notifications;
+pub mod play_queue;
 pub mod player;
 pub mod profile;
 pub mod resource;
 pub mod search_history;
 pub mod streaming_server;
 pub mod streams;
+pub mod subtitles;
 pub mod torrent;
+pub mod watch_history;
+pub mod watch_party;
 
 // Re-export of stremio_watched_bitfield crate
 pub mod watched_bitfield {
@@ -0,0 +1,61 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::notifications::{NotificationItem, NotificationKind};
+use crate::types::resource::{SeriesInfo, Video, VideoId};
+
+/// The seasons and specials of a series which were released
+/// the last time its videos were loaded.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownSeasons {
+    pub seasons: BTreeSet<u32>,
+    pub specials: BTreeSet<VideoId>,
+}
+
+impl KnownSeasons {
+    /// Only the released videos are taken into account,
+    /// so an announced season is detected once its first episode is out.
+    pub fn new(videos: &[Video], now: DateTime<Utc>) -> Self {
+        videos
+            .iter()
+            .filter(|video| video.released.is_some_and(|released| released <= now))
+            .fold(Self::default(), |mut known, video| {
+                match video.series_info {
+                    Some(SeriesInfo { season: 0, .. }) => {
+                        known.specials.insert(video.id.to_owned());
+                    }
+                    Some(SeriesInfo { season, .. }) => {
+                        known.seasons.insert(season);
+                    }
+                    None => {}
+                };
+                known
+            })
+    }
+    /// The seasons and specials which are not in the `previous` ones.
+    pub fn added_since<'a>(&'a self, previous: &'a KnownSeasons) -> (Vec<u32>, Vec<&'a VideoId>) {
+        (
+            self.seasons
+                .difference(&previous.seasons)
+                .copied()
+                .collect(),
+            self.specials.difference(&previous.specials).collect(),
+        )
+    }
+    /// Forgets the season or the special of an added notification,
+    /// so that it's detected as added again the next time.
+    pub fn forget(&mut self, notif_item: &NotificationItem) {
+        match notif_item.kind {
+            NotificationKind::SeasonAdded { season } => {
+                self.seasons.remove(&season);
+            }
+            NotificationKind::SpecialAdded => {
+                self.specials.remove(&notif_item.video_id);
+            }
+            NotificationKind::Video => {}
+        }
+    }
+}
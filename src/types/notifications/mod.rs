@@ -3,3 +3,9 @@ pub use notifications_bucket::*;
 
 mod notification_item;
 pub use notification_item::*;
+
+mod notifications_export;
+pub use notifications_export::*;
+
+mod known_seasons;
+pub use known_seasons::*;
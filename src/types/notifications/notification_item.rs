@@ -10,4 +10,19 @@ pub struct NotificationItem {
     pub meta_id: MetaItemId,
     pub video_id: VideoId,
     pub video_released: DateTime<Utc>,
+    #[serde(default)]
+    pub kind: NotificationKind,
+}
+
+/// What the notification is about
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum NotificationKind {
+    /// A new video of the series
+    #[default]
+    Video,
+    /// A new season of the series, the video is its first episode
+    SeasonAdded { season: u32 },
+    /// A new special (season 0) video of the series
+    SpecialAdded,
 }
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     runtime::Env,
     types::{
-        notifications::NotificationItem,
+        notifications::{KnownSeasons, NotificationItem},
         profile::UID,
         resource::{MetaItemId, VideoId},
     },
@@ -29,6 +29,12 @@ pub struct NotificationsBucket {
     /// The last time notifications were pulled.
     #[serde(default)]
     pub last_updated: Option<DateTime<Utc>>,
+    /// The last time the new videos of each meta item were checked for.
+    #[serde(default)]
+    pub last_checked: HashMap<MetaItemId, DateTime<Utc>>,
+    /// The released seasons of each meta item, to detect the new ones.
+    #[serde(default)]
+    pub known_seasons: HashMap<MetaItemId, KnownSeasons>,
     /// The moment that the notification bucket was initialized.
     #[cfg_attr(test, derivative(Default(value = "Utc.timestamp_opt(0, 0).unwrap()")))]
     pub created: DateTime<Utc>,
@@ -52,6 +58,8 @@ impl NotificationsBucket {
                 acc
             }),
             last_updated: None,
+            last_checked: HashMap::new(),
+            known_seasons: HashMap::new(),
             created: E::now(),
         }
     }
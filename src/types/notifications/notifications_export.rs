@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    library::LibraryBucket,
+    notifications::NotificationsBucket,
+    resource::{MetaItemId, VideoId},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationsExportFormat {
+    /// iCalendar, for subscribing to the notifications in a calendar app
+    Ics,
+    Json,
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingNotification {
+    pub meta_id: MetaItemId,
+    pub video_id: VideoId,
+    /// The name of the library item, the meta id if it is not in the library
+    pub name: String,
+    pub video_released: DateTime<Utc>,
+}
+
+impl NotificationsBucket {
+    /// The notifications which are not dismissed yet, ordered by release date
+    pub fn pending(&self, library: &LibraryBucket) -> Vec<PendingNotification> {
+        self.items
+            .values()
+            .flat_map(|meta_notifs| meta_notifs.values())
+            .map(|item| PendingNotification {
+                meta_id: item.meta_id.to_owned(),
+                video_id: item.video_id.to_owned(),
+                name: library
+                    .items
+                    .get(&item.meta_id)
+                    .map(|library_item| library_item.name.to_owned())
+                    .unwrap_or_else(|| item.meta_id.to_owned()),
+                video_released: item.video_released,
+            })
+            .sorted_by(|a, b| {
+                a.video_released
+                    .cmp(&b.video_released)
+                    .then_with(|| a.meta_id.cmp(&b.meta_id))
+                    .then_with(|| a.video_id.cmp(&b.video_id))
+            })
+            .collect()
+    }
+    pub fn export(&self, library: &LibraryBucket, format: NotificationsExportFormat) -> String {
+        let pending = self.pending(library);
+        match format {
+            NotificationsExportFormat::Ics => {
+                let events = pending.iter().map(|notification| {
+                    let released = notification.video_released.format("%Y%m%dT%H%M%SZ");
+                    format!(
+                        "BEGIN:VEVENT\r\n\
+                         UID:{}\r\n\
+                         DTSTAMP:{released}\r\n\
+                         DTSTART:{released}\r\n\
+                         SUMMARY:{}\r\n\
+                         END:VEVENT\r\n",
+                        ics_escape(&format!(
+                            "{}/{}@stremio",
+                            notification.meta_id, notification.video_id
+                        )),
+                        ics_escape(&format!(
+                            "{} ({})",
+                            notification.name, notification.video_id
+                        )),
+                    )
+                });
+                format!(
+                    "BEGIN:VCALENDAR\r\n\
+                     VERSION:2.0\r\n\
+                     PRODID:-//Stremio//Notifications//EN\r\n\
+                     {}\
+                     END:VCALENDAR\r\n",
+                    events.collect::<String>()
+                )
+            }
+            NotificationsExportFormat::Json => {
+                serde_json::to_string(&pending).expect("Pending notifications to JSON")
+            }
+        }
+    }
+}
+
+/// Escapes a text value as defined in RFC 5545
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
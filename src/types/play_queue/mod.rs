@@ -0,0 +1,5 @@
+mod play_queue_item;
+pub use play_queue_item::*;
+
+mod play_queue_bucket;
+pub use play_queue_bucket::*;
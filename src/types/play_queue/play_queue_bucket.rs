@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::play_queue::PlayQueueItem;
+use crate::types::profile::UID;
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayQueueBucket {
+    pub uid: UID,
+    /// The queued videos in the order they will be played
+    pub items: Vec<PlayQueueItem>,
+}
+
+impl PlayQueueBucket {
+    pub fn new(uid: UID) -> Self {
+        PlayQueueBucket { uid, items: vec![] }
+    }
+    /// Appends the item to the end of the queue.
+    ///
+    /// Returns `false` if the video is already queued.
+    pub fn push(&mut self, item: PlayQueueItem) -> bool {
+        if self.position(&item.meta_id, &item.video_id).is_some() {
+            return false;
+        }
+        self.items.push(item);
+        true
+    }
+    /// Returns `false` if the video is not queued.
+    pub fn remove(&mut self, meta_id: &str, video_id: &str) -> bool {
+        match self.position(meta_id, video_id) {
+            Some(position) => {
+                self.items.remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+    /// Moves the item at `from` so it ends up at `to`.
+    ///
+    /// Returns `false` if any of the positions is out of bounds or they are the same.
+    pub fn move_item(&mut self, from: usize, to: usize) -> bool {
+        if from == to || from >= self.items.len() || to >= self.items.len() {
+            return false;
+        }
+        let item = self.items.remove(from);
+        self.items.insert(to, item);
+        true
+    }
+    /// The first queued video which is not the given one.
+    pub fn next(&self, meta_id: &str, video_id: &str) -> Option<&PlayQueueItem> {
+        self.items
+            .iter()
+            .find(|item| !item.is_same_video(meta_id, video_id))
+    }
+    fn position(&self, meta_id: &str, video_id: &str) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|item| item.is_same_video(meta_id, video_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(meta_id: &str, video_id: &str) -> PlayQueueItem {
+        PlayQueueItem {
+            r#type: "series".to_owned(),
+            meta_id: meta_id.to_owned(),
+            video_id: video_id.to_owned(),
+            meta_transport_url: "https://transport_url/manifest.json".parse().unwrap(),
+            name: meta_id.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_push_and_remove() {
+        let mut bucket = PlayQueueBucket::default();
+        assert!(bucket.push(item("tt1", "tt1:1:1")));
+        assert!(bucket.push(item("tt2", "tt2")));
+        assert!(
+            !bucket.push(item("tt1", "tt1:1:1")),
+            "A video is queued only once"
+        );
+        assert_eq!(bucket.items.len(), 2);
+
+        assert!(bucket.remove("tt1", "tt1:1:1"));
+        assert!(!bucket.remove("tt1", "tt1:1:1"));
+        assert_eq!(bucket.items, vec![item("tt2", "tt2")]);
+    }
+
+    #[test]
+    fn test_move_item() {
+        let mut bucket = PlayQueueBucket::default();
+        bucket.push(item("tt1", "tt1"));
+        bucket.push(item("tt2", "tt2"));
+        bucket.push(item("tt3", "tt3"));
+
+        assert!(bucket.move_item(2, 0));
+        assert_eq!(
+            bucket.items,
+            vec![item("tt3", "tt3"), item("tt1", "tt1"), item("tt2", "tt2")]
+        );
+        assert!(!bucket.move_item(0, 3), "Out of bounds");
+        assert!(!bucket.move_item(1, 1));
+    }
+
+    #[test]
+    fn test_next() {
+        let mut bucket = PlayQueueBucket::default();
+        bucket.push(item("tt1", "tt1"));
+        bucket.push(item("tt2", "tt2"));
+
+        assert_eq!(bucket.next("tt1", "tt1"), Some(&item("tt2", "tt2")));
+        assert_eq!(bucket.next("tt3", "tt3"), Some(&item("tt1", "tt1")));
+    }
+}
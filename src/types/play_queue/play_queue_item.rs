@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::constants::META_RESOURCE_NAME;
+use crate::types::addon::{ResourcePath, ResourceRequest};
+use crate::types::resource::{MetaItemId, VideoId};
+
+/// A video the user has queued to be played after the current one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayQueueItem {
+    pub r#type: String,
+    pub meta_id: MetaItemId,
+    pub video_id: VideoId,
+    /// The addon which the [`MetaItem`] of the video is fetched from.
+    ///
+    /// [`MetaItem`]: crate::types::resource::MetaItem
+    pub meta_transport_url: Url,
+    pub name: String,
+}
+
+impl PlayQueueItem {
+    pub fn is_same_video(&self, meta_id: &str, video_id: &str) -> bool {
+        self.meta_id == meta_id && self.video_id == video_id
+    }
+    pub fn meta_request(&self) -> ResourceRequest {
+        ResourceRequest::new(
+            self.meta_transport_url.to_owned(),
+            ResourcePath::without_extra(META_RESOURCE_NAME, &self.r#type, &self.meta_id),
+        )
+    }
+}
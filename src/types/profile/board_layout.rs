@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::types::addon::ResourceRequest;
+
+/// A catalog shown on the Board
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardCatalog {
+    pub transport_url: Url,
+    pub r#type: String,
+    pub id: String,
+}
+
+impl From<&ResourceRequest> for BoardCatalog {
+    fn from(request: &ResourceRequest) -> Self {
+        BoardCatalog {
+            transport_url: request.base.to_owned(),
+            r#type: request.path.r#type.to_owned(),
+            id: request.path.id.to_owned(),
+        }
+    }
+}
+
+/// The user's order and visibility of the Board catalogs,
+/// synced to the API with the addon collection.
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardLayout {
+    /// The catalogs which are not in the order are shown after the ordered ones,
+    /// in the order of the installed addons.
+    pub order: Vec<BoardCatalog>,
+    pub hidden: Vec<BoardCatalog>,
+}
+
+impl BoardLayout {
+    pub fn is_hidden(&self, catalog: &BoardCatalog) -> bool {
+        self.hidden.contains(catalog)
+    }
+    /// Sorts the items, given in the order of the installed addons, by the order of their catalogs.
+    pub fn sort_by_catalog<T>(&self, items: &mut [T], catalog: impl Fn(&T) -> BoardCatalog) {
+        items.sort_by_cached_key(|item| {
+            let catalog = catalog(item);
+            self.order
+                .iter()
+                .position(|ordered| *ordered == catalog)
+                .unwrap_or(usize::MAX)
+        });
+    }
+    /// Moves the catalog to the position `to` among the visible catalogs,
+    /// the hidden ones keep their positions.
+    ///
+    /// Returns `false` if the catalog is not one of the visible `catalogs`
+    /// or it's already at the position.
+    pub fn move_catalog(
+        &mut self,
+        catalogs: &[BoardCatalog],
+        catalog: &BoardCatalog,
+        to: usize,
+    ) -> bool {
+        let mut catalogs = catalogs.to_owned();
+        self.sort_by_catalog(&mut catalogs, Clone::clone);
+        let mut visible = catalogs
+            .iter()
+            .filter(|catalog| !self.is_hidden(catalog))
+            .cloned()
+            .collect::<Vec<_>>();
+        let from = match visible.iter().position(|visible| visible == catalog) {
+            Some(from) => from,
+            None => return false,
+        };
+        let to = to.min(visible.len() - 1);
+        if from == to {
+            return false;
+        }
+        let catalog = visible.remove(from);
+        visible.insert(to, catalog);
+        let mut visible = visible.into_iter();
+        self.order = catalogs
+            .into_iter()
+            .map(|catalog| match self.is_hidden(&catalog) {
+                true => catalog,
+                false => visible.next().expect("Visible catalog"),
+            })
+            .collect();
+        true
+    }
+    /// Returns `false` if the catalog is already hidden.
+    pub fn hide(&mut self, catalog: &BoardCatalog) -> bool {
+        if self.is_hidden(catalog) {
+            return false;
+        }
+        self.hidden.push(catalog.to_owned());
+        true
+    }
+    /// Returns `false` if the catalog is not hidden.
+    pub fn show(&mut self, catalog: &BoardCatalog) -> bool {
+        let hidden_len = self.hidden.len();
+        self.hidden.retain(|hidden| hidden != catalog);
+        self.hidden.len() != hidden_len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(id: &str) -> BoardCatalog {
+        BoardCatalog {
+            transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+            r#type: "movie".to_owned(),
+            id: id.to_owned(),
+        }
+    }
+
+    fn sorted(layout: &BoardLayout, catalogs: &[BoardCatalog]) -> Vec<String> {
+        let mut catalogs = catalogs.to_owned();
+        layout.sort_by_catalog(&mut catalogs, Clone::clone);
+        catalogs.into_iter().map(|catalog| catalog.id).collect()
+    }
+
+    #[test]
+    fn board_layout_move_catalog() {
+        let catalogs = ["a", "b", "c", "d"].map(catalog);
+        let mut layout = BoardLayout::default();
+        assert!(layout.hide(&catalog("b")));
+        assert!(!layout.hide(&catalog("b")));
+
+        assert!(layout.move_catalog(&catalogs, &catalog("d"), 0));
+        assert_eq!(
+            sorted(&layout, &catalogs),
+            vec!["d", "b", "a", "c"],
+            "The hidden catalog keeps its position"
+        );
+        assert!(!layout.move_catalog(&catalogs, &catalog("b"), 0));
+        assert!(!layout.move_catalog(&catalogs, &catalog("c"), 10));
+
+        assert!(layout.show(&catalog("b")));
+        assert!(!layout.show(&catalog("b")));
+        assert_eq!(
+            sorted(&layout, &[catalog("e"), catalog("a"), catalog("d")]),
+            vec!["d", "a", "e"],
+            "New catalogs are after the ordered ones"
+        );
+    }
+}
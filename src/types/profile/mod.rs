@@ -1,6 +1,9 @@
 mod auth;
 pub use auth::*;
 
+mod board_layout;
+pub use board_layout::*;
+
 mod profile;
 pub use profile::*;
 
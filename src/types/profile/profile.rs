@@ -1,7 +1,7 @@
 use crate::constants::OFFICIAL_ADDONS;
 use crate::runtime::Env;
 use crate::types::addon::Descriptor;
-use crate::types::profile::{Auth, AuthKey, Settings};
+use crate::types::profile::{Auth, AuthKey, BoardLayout, Settings};
 use crate::types::{UniqueVec, UniqueVecAdapter};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
@@ -26,6 +26,9 @@ pub struct Profile {
     #[serde(default)]
     pub addons_locked: bool,
     pub settings: Settings,
+    /// The order and visibility of the Board catalogs
+    #[serde(default)]
+    pub board_layout: BoardLayout,
 }
 
 impl Default for Profile {
@@ -35,6 +38,7 @@ impl Default for Profile {
             addons: OFFICIAL_ADDONS.to_owned(),
             addons_locked: false,
             settings: Settings::default(),
+            board_layout: BoardLayout::default(),
         }
     }
 }
@@ -1,6 +1,7 @@
 use crate::constants::STREAMING_SERVER_URL;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Timelike, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use url::Url;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -38,6 +39,14 @@ pub struct Settings {
     pub pause_on_minimize: bool,
     pub surround_sound: bool,
     pub streaming_server_warning_dismissed: Option<DateTime<Utc>>,
+    pub notification_rules: NotificationRules,
+    /// Whether the installed addons which are not protected should be upgraded
+    /// once a newer version of them is found.
+    pub auto_upgrade_addons: bool,
+    /// The priority of the addons per resource, e.g. `stream`, `subtitles` and `meta`
+    pub addon_priorities: HashMap<String, AddonResourcePriority>,
+    /// Whether the trailer is played automatically on the detail page
+    pub trailer_autoplay: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -47,6 +56,79 @@ pub enum FrameRateMatchingStrategy {
     FrameRateAndResolution,
 }
 
+/// The order in which the addons are requested for a resource
+/// and the addons which are not requested for it.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddonResourcePriority {
+    /// The transport urls of the addons from the highest priority,
+    /// the addons which are not in it follow in the installed order.
+    pub order: Vec<Url>,
+    /// The transport urls of the addons which resource is disabled
+    pub disabled: Vec<Url>,
+}
+
+impl AddonResourcePriority {
+    pub fn is_disabled(&self, transport_url: &Url) -> bool {
+        self.disabled.contains(transport_url)
+    }
+    pub fn position(&self, transport_url: &Url) -> usize {
+        self.order
+            .iter()
+            .position(|ordered| ordered == transport_url)
+            .unwrap_or(usize::MAX)
+    }
+}
+
+/// Global rules for the new videos which are notified about,
+/// on top of the notifications toggle of each library item.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRules {
+    /// Notify only about the series watched in the last number of days
+    pub watched_within_days: Option<u32>,
+    /// Notify only when all the videos released before the new ones are watched
+    pub only_caught_up: bool,
+    /// Notify only about the videos released in the last number of days
+    pub max_video_age_days: Option<u32>,
+    /// No new notifications are added during the quiet hours
+    pub quiet_hours: Option<QuietHours>,
+    /// The hours after which the new videos of a series are checked for again
+    pub check_ttl_hours: u32,
+}
+
+impl Default for NotificationRules {
+    fn default() -> Self {
+        NotificationRules {
+            watched_within_days: None,
+            only_caught_up: false,
+            max_video_age_days: None,
+            quiet_hours: None,
+            check_ttl_hours: 6,
+        }
+    }
+}
+
+/// The hours are in UTC, the apps convert them from the local time of the user.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// The hour (0-23) at which the quiet hours start
+    pub start: u8,
+    /// The hour (0-23) at which the quiet hours end, before `start` if they span midnight
+    pub end: u8,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: &DateTime<Utc>) -> bool {
+        let hour = time.hour() as u8;
+        if self.start <= self.end {
+            self.start <= hour && hour < self.end
+        } else {
+            self.start <= hour || hour < self.end
+        }
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Settings {
@@ -77,6 +159,10 @@ impl Default for Settings {
             pause_on_minimize: false,
             surround_sound: false,
             streaming_server_warning_dismissed: None,
+            notification_rules: NotificationRules::default(),
+            auto_upgrade_addons: false,
+            addon_priorities: HashMap::new(),
+            trailer_autoplay: false,
         }
     }
 }
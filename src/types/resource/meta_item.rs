@@ -265,6 +265,58 @@ impl MetaItem {
             Either::Right(self.videos.iter().rev())
         }
     }
+    /// Merges the meta item of the same title from another addon into this one.
+    ///
+    /// The missing fields are filled from the other item, the links and trailers are joined
+    /// and the videos are matched by id or series info, the ones which are missing are added.
+    pub fn merge(&mut self, other: &MetaItem) {
+        let preview = &mut self.preview;
+        let other_preview = &other.preview;
+        if preview.name.is_empty() {
+            other_preview.name.clone_into(&mut preview.name);
+        }
+        fill_missing(&mut preview.poster, &other_preview.poster);
+        fill_missing(&mut preview.background, &other_preview.background);
+        fill_missing(&mut preview.logo, &other_preview.logo);
+        fill_missing(&mut preview.description, &other_preview.description);
+        fill_missing(&mut preview.release_info, &other_preview.release_info);
+        fill_missing(&mut preview.runtime, &other_preview.runtime);
+        fill_missing(&mut preview.released, &other_preview.released);
+        join_missing(&mut preview.links, &other_preview.links);
+        join_missing(&mut preview.trailer_streams, &other_preview.trailer_streams);
+        for other_video in &other.videos {
+            let video = self.videos.iter_mut().find(|video| {
+                video.id == other_video.id
+                    || video.series_info.is_some() && video.series_info == other_video.series_info
+            });
+            match video {
+                Some(video) => {
+                    fill_missing(&mut video.released, &other_video.released);
+                    fill_missing(&mut video.overview, &other_video.overview);
+                    fill_missing(&mut video.thumbnail, &other_video.thumbnail);
+                    join_missing(&mut video.trailer_streams, &other_video.trailer_streams);
+                }
+                None => self.videos.push(other_video.to_owned()),
+            };
+        }
+        let is_series = VideoSortedVecAdapter::args(&self.videos);
+        self.videos
+            .sort_by(|a, b| VideoSortedVecAdapter::cmp(a, b, &is_series));
+    }
+}
+
+fn fill_missing<T: Clone>(value: &mut Option<T>, other: &Option<T>) {
+    if value.is_none() {
+        other.clone_into(value);
+    }
+}
+
+fn join_missing<T: Clone + PartialEq>(values: &mut Vec<T>, other: &[T]) {
+    for other in other {
+        if !values.contains(other) {
+            values.push(other.to_owned());
+        }
+    }
 }
 
 #[derive(Default, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -1,11 +1,17 @@
 #[cfg(test)]
 use derivative::Derivative;
+use futures::FutureExt;
+use http::Request;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::runtime::{Env, EnvError, EnvFutureExt, TryEnvFuture};
+use crate::types::subtitles::SubtitlesDocument;
+
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 #[cfg_attr(test, derive(Derivative))]
 #[cfg_attr(test, derivative(Default))]
+#[serde(rename_all = "camelCase")]
 pub struct Subtitles {
     pub lang: String,
     #[cfg_attr(
@@ -13,4 +19,33 @@ pub struct Subtitles {
         derivative(Default(value = "Url::parse(\"protocol://host\").unwrap()"))
     )]
     pub url: Url,
+    /// Hash of the video file these subtitles are made for,
+    /// see [`StreamBehaviorHints::video_hash`](crate::types::resource::StreamBehaviorHints::video_hash)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video_hash: Option<String>,
+    /// Size in bytes of the video file these subtitles are made for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video_size: Option<u64>,
+}
+
+impl Subtitles {
+    /// Fetches the subtitles in any of the supported formats and converts them to WebVTT.
+    ///
+    /// `delay` is in milliseconds, see [`StreamItemState::subtitle_delay`](crate::types::streams::StreamItemState::subtitle_delay).
+    pub fn fetch_webvtt<E: Env + 'static>(&self, delay: Option<i64>) -> TryEnvFuture<String> {
+        let request = Request::get(self.url.as_str())
+            .body(())
+            .expect("request builder failed");
+        E::fetch_bytes(request)
+            .map(move |result| {
+                let content = result?;
+                let mut subtitles = SubtitlesDocument::parse(&content)
+                    .map_err(|error| EnvError::Other(error.to_string()))?;
+                if let Some(delay) = delay {
+                    subtitles.shift(delay);
+                }
+                Ok(subtitles.to_webvtt())
+            })
+            .boxed_env()
+    }
 }
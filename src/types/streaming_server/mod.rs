@@ -7,6 +7,9 @@ pub use device_info::*;
 mod network_info;
 pub use network_info::*;
 
+mod playback;
+pub use playback::*;
+
 mod response;
 pub use response::*;
 
@@ -0,0 +1,436 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use url::{form_urlencoded, Url};
+
+use crate::types::resource::{Stream, StreamSource};
+
+/// Channel layouts in filenames, e.g. `DDP5.1`, `AAC2.0` or `7.1`.
+static CHANNEL_LAYOUT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|[^0-9])([1-9])[. ]([0-2])(?:[^0-9]|$)")
+        .expect("Channel layout regex failed to build")
+});
+
+/// The media formats the host (browser, TV, etc.) can play natively.
+///
+/// Containers and codecs use the names of the streaming server's probe,
+/// e.g. `mp4`, `matroska`, `h264`, `hevc`, `aac`, `eac3`.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaybackCapabilities {
+    pub containers: Vec<String>,
+    pub video_codecs: Vec<String>,
+    pub audio_codecs: Vec<String>,
+    pub max_audio_channels: u8,
+}
+
+impl PlaybackCapabilities {
+    pub fn supports_container(&self, container: &str) -> bool {
+        self.containers
+            .iter()
+            .any(|supported| supported == container)
+    }
+    pub fn supports_video_codec(&self, codec: &str) -> bool {
+        self.video_codecs.iter().any(|supported| supported == codec)
+    }
+    pub fn supports_audio_codec(&self, codec: &str) -> bool {
+        self.audio_codecs.iter().any(|supported| supported == codec)
+    }
+}
+
+/// The capabilities of a regular web browser.
+impl Default for PlaybackCapabilities {
+    fn default() -> Self {
+        Self {
+            containers: vec!["mp4".to_owned(), "webm".to_owned(), "hls".to_owned()],
+            video_codecs: vec!["h264".to_owned(), "vp8".to_owned(), "vp9".to_owned()],
+            audio_codecs: vec![
+                "aac".to_owned(),
+                "mp3".to_owned(),
+                "opus".to_owned(),
+                "vorbis".to_owned(),
+            ],
+            max_audio_channels: 2,
+        }
+    }
+}
+
+/// Container and codecs of a [`Stream`] as guessed from its filename.
+///
+/// `None` means the format could not be guessed.
+#[derive(Default, Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaHints {
+    pub container: Option<String>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    /// The number of audio channels, e.g. 6 for `5.1`
+    pub audio_channels: Option<u8>,
+}
+
+impl From<&Stream> for MediaHints {
+    fn from(stream: &Stream) -> Self {
+        let filename = match (&stream.behavior_hints.filename, &stream.source) {
+            (Some(filename), _) => filename.to_owned(),
+            (None, StreamSource::Url { url }) => url
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .map(|segment| {
+                    percent_encoding::percent_decode_str(segment)
+                        .decode_utf8_lossy()
+                        .into_owned()
+                })
+                .unwrap_or_default(),
+            _ => return MediaHints::default(),
+        };
+        let filename = filename.to_lowercase();
+        let container = filename
+            .rsplit_once('.')
+            .and_then(|(_, extension)| match extension {
+                "mp4" | "m4v" | "mov" => Some("mp4"),
+                "mkv" => Some("matroska"),
+                "webm" => Some("webm"),
+                "avi" => Some("avi"),
+                "ts" | "m2ts" => Some("mpegts"),
+                "m3u8" => Some("hls"),
+                _ => None,
+            })
+            .map(ToOwned::to_owned);
+        let audio_channels = CHANNEL_LAYOUT_REGEX
+            .captures(&filename)
+            .and_then(|captures| {
+                let main = captures.get(1)?.as_str().parse::<u8>().ok()?;
+                let lfe = captures.get(2)?.as_str().parse::<u8>().ok()?;
+                Some(main + lfe)
+            });
+        let tokens = filename
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '+')
+            .collect::<Vec<_>>();
+        let video_codec = tokens
+            .iter()
+            .find_map(|token| match *token {
+                "x264" | "h264" | "avc" => Some("h264"),
+                "x265" | "h265" | "hevc" => Some("hevc"),
+                "av1" => Some("av1"),
+                "vp9" => Some("vp9"),
+                "xvid" | "divx" => Some("mpeg4"),
+                _ => None,
+            })
+            .map(ToOwned::to_owned);
+        let audio_codec = tokens
+            .iter()
+            .find_map(|token| match *token {
+                "aac" => Some("aac"),
+                "ddp" | "ddp5" | "dd+" | "eac3" => Some("eac3"),
+                "ac3" | "dd" | "dd5" => Some("ac3"),
+                "dts" => Some("dts"),
+                "truehd" | "atmos" => Some("truehd"),
+                "flac" => Some("flac"),
+                "opus" => Some("opus"),
+                "mp3" => Some("mp3"),
+                _ => None,
+            })
+            .map(ToOwned::to_owned);
+        MediaHints {
+            container,
+            video_codec,
+            audio_codec,
+            audio_channels,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Debug)]
+pub enum PlaybackMethod {
+    /// The host plays the stream as it is.
+    DirectPlay,
+    /// The streaming server repackages the stream into HLS without touching the codecs.
+    Remux,
+    /// The streaming server re-encodes at least one of the codecs.
+    Transcode,
+}
+
+/// Query parameters of the streaming server's `hlsv2` endpoint.
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HlsParams {
+    #[serde(rename = "mediaURL")]
+    pub media_url: Url,
+    pub video_codecs: Vec<String>,
+    pub audio_codecs: Vec<String>,
+    pub max_audio_channels: u8,
+    /// The hardware acceleration the streaming server uses when transcoding.
+    pub hardware_acceleration: Option<String>,
+}
+
+/// How a [`Stream`] should be played on the host.
+///
+/// # Examples
+///
+/// ```
+/// use stremio_core::types::resource::Stream;
+/// use stremio_core::types::streaming_server::{PlaybackCapabilities, PlaybackMethod, StreamPlayback};
+/// use url::Url;
+///
+/// let stream = serde_json::from_value::<Stream>(serde_json::json!({
+///     "url": "https://example.com/Movie.2024.1080p.x265.DDP5.1.mkv",
+///     "behaviorHints": { "notWebReady": true }
+/// }))
+/// .unwrap();
+/// let streaming_server_url = Url::parse("http://127.0.0.1:11470").unwrap();
+///
+/// let playback = StreamPlayback::new(
+///     &stream,
+///     Some(&streaming_server_url),
+///     &PlaybackCapabilities::default(),
+///     None,
+/// )
+/// .expect("Should be playable");
+///
+/// assert_eq!(playback.method, PlaybackMethod::Transcode);
+/// assert!(playback.url.path().starts_with("/hlsv2/"));
+/// ```
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamPlayback {
+    pub method: PlaybackMethod,
+    pub url: Url,
+    pub hls_params: Option<HlsParams>,
+    /// The hardware acceleration the streaming server uses when transcoding.
+    pub hardware_acceleration: Option<String>,
+}
+
+impl StreamPlayback {
+    /// Returns `None` if the stream cannot be played on the host at all.
+    ///
+    /// `hardware_acceleration` is the server's `transcode_profile`, given only if it's
+    /// one of the `available_hardware_accelerations` of the server's device.
+    pub fn new(
+        stream: &Stream,
+        streaming_server_url: Option<&Url>,
+        capabilities: &PlaybackCapabilities,
+        hardware_acceleration: Option<&str>,
+    ) -> Option<Self> {
+        let media_url = stream.streaming_url(streaming_server_url)?;
+        let hints = MediaHints::from(stream);
+        let container_supported = hints
+            .container
+            .as_deref()
+            .map(|container| capabilities.supports_container(container));
+        let video_codec_supported = hints
+            .video_codec
+            .as_deref()
+            .map(|codec| capabilities.supports_video_codec(codec));
+        let audio_codec_supported = hints
+            .audio_codec
+            .as_deref()
+            .map(|codec| capabilities.supports_audio_codec(codec));
+        // the audio with more channels than the host can play has to be downmixed
+        let audio_codec_supported = match hints.audio_channels {
+            Some(channels) if channels > capabilities.max_audio_channels => Some(false),
+            _ => audio_codec_supported,
+        };
+        let codecs_supported =
+            video_codec_supported != Some(false) && audio_codec_supported != Some(false);
+        // a stream which is not web ready is played directly
+        // only when we know for sure that the host supports all of its formats
+        let direct_play = match container_supported {
+            Some(false) => false,
+            _ if stream.behavior_hints.not_web_ready => {
+                container_supported == Some(true)
+                    && video_codec_supported == Some(true)
+                    && audio_codec_supported == Some(true)
+            }
+            _ => codecs_supported,
+        };
+        let streaming_server_url = match streaming_server_url {
+            Some(streaming_server_url) if !direct_play => streaming_server_url,
+            // without a streaming server the best we can do is to try the stream as it is
+            _ => {
+                return Some(Self {
+                    method: PlaybackMethod::DirectPlay,
+                    url: media_url,
+                    hls_params: None,
+                    hardware_acceleration: None,
+                })
+            }
+        };
+        let method = match codecs_supported {
+            true => PlaybackMethod::Remux,
+            false => PlaybackMethod::Transcode,
+        };
+        let hardware_acceleration = match method {
+            PlaybackMethod::Transcode => hardware_acceleration.map(ToOwned::to_owned),
+            _ => None,
+        };
+        let hls_params = HlsParams {
+            media_url,
+            video_codecs: capabilities.video_codecs.to_owned(),
+            audio_codecs: capabilities.audio_codecs.to_owned(),
+            max_audio_channels: capabilities.max_audio_channels,
+            hardware_acceleration: hardware_acceleration.to_owned(),
+        };
+        let id = hex::encode(Sha1::digest(hls_params.media_url.as_str()));
+        let mut url = streaming_server_url
+            .join(&format!("hlsv2/{id}/master.m3u8"))
+            .ok()?;
+        url.set_query(Some(&hls_params.to_query()));
+        Some(Self {
+            method,
+            url,
+            hls_params: Some(hls_params),
+            hardware_acceleration,
+        })
+    }
+}
+
+impl HlsParams {
+    fn to_query(&self) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        query.append_pair("mediaURL", self.media_url.as_str());
+        query.extend_pairs(
+            self.video_codecs
+                .iter()
+                .map(|codec| ("videoCodecs", codec.as_str())),
+        );
+        query.extend_pairs(
+            self.audio_codecs
+                .iter()
+                .map(|codec| ("audioCodecs", codec.as_str())),
+        );
+        query.append_pair("maxAudioChannels", &self.max_audio_channels.to_string());
+        if let Some(hardware_acceleration) = &self.hardware_acceleration {
+            query.append_pair("hardwareAcceleration", hardware_acceleration);
+        }
+        query.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(json: serde_json::Value) -> Stream {
+        serde_json::from_value(json).expect("Stream deserialization")
+    }
+
+    #[test]
+    fn test_media_hints_from_filename() {
+        let stream = stream(serde_json::json!({
+            "url": "https://example.com/video",
+            "behaviorHints": { "filename": "The.Beekeeper.2024.1080p.WEBRip.x264-RZP.AAC.mkv" }
+        }));
+        assert_eq!(
+            MediaHints::from(&stream),
+            MediaHints {
+                container: Some("matroska".to_owned()),
+                video_codec: Some("h264".to_owned()),
+                audio_codec: Some("aac".to_owned()),
+                audio_channels: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_media_hints_audio_channels() {
+        let stream = stream(serde_json::json!({
+            "url": "https://example.com/Movie.2024.1080p.x265.DDP5.1.mkv"
+        }));
+        assert_eq!(
+            MediaHints::from(&stream),
+            MediaHints {
+                container: Some("matroska".to_owned()),
+                video_codec: Some("hevc".to_owned()),
+                audio_codec: Some("eac3".to_owned()),
+                audio_channels: Some(6),
+            }
+        );
+    }
+
+    #[test]
+    fn test_stream_playback_method() {
+        let streaming_server_url = Url::parse("http://127.0.0.1:11470").unwrap();
+        let capabilities = PlaybackCapabilities::default();
+
+        let direct = stream(serde_json::json!({ "url": "https://example.com/movie.x264.mp4" }));
+        let playback = StreamPlayback::new(
+            &direct,
+            Some(&streaming_server_url),
+            &capabilities,
+            Some("vaapi"),
+        )
+        .unwrap();
+        assert_eq!(playback.method, PlaybackMethod::DirectPlay);
+        assert_eq!(playback.url.as_str(), "https://example.com/movie.x264.mp4");
+
+        let remux = stream(serde_json::json!({
+            "url": "https://example.com/movie.x264.aac.mkv",
+            "behaviorHints": { "notWebReady": true }
+        }));
+        let playback = StreamPlayback::new(
+            &remux,
+            Some(&streaming_server_url),
+            &capabilities,
+            Some("vaapi"),
+        )
+        .unwrap();
+        assert_eq!(playback.method, PlaybackMethod::Remux);
+        assert_eq!(playback.hardware_acceleration, None);
+        assert_eq!(
+            playback.url.as_str(),
+            format!(
+                "http://127.0.0.1:11470/hlsv2/{}/master.m3u8?mediaURL=https%3A%2F%2Fexample.com%2Fmovie.x264.aac.mkv&videoCodecs=h264&videoCodecs=vp8&videoCodecs=vp9&audioCodecs=aac&audioCodecs=mp3&audioCodecs=opus&audioCodecs=vorbis&maxAudioChannels=2",
+                hex::encode(Sha1::digest("https://example.com/movie.x264.aac.mkv"))
+            )
+        );
+
+        let transcode = stream(serde_json::json!({
+            "url": "https://example.com/movie.hevc.mkv",
+            "behaviorHints": { "notWebReady": true }
+        }));
+        let playback = StreamPlayback::new(
+            &transcode,
+            Some(&streaming_server_url),
+            &capabilities,
+            Some("vaapi"),
+        )
+        .unwrap();
+        assert_eq!(playback.method, PlaybackMethod::Transcode);
+        assert_eq!(playback.hardware_acceleration, Some("vaapi".to_owned()));
+        assert_eq!(
+            playback
+                .url
+                .query_pairs()
+                .find(|(key, _)| key == "hardwareAcceleration")
+                .map(|(_, value)| value.into_owned()),
+            Some("vaapi".to_owned())
+        );
+
+        let surround = stream(serde_json::json!({
+            "url": "https://example.com/movie.x264.aac5.1.mp4"
+        }));
+        let playback =
+            StreamPlayback::new(&surround, Some(&streaming_server_url), &capabilities, None)
+                .unwrap();
+        assert_eq!(
+            playback.method,
+            PlaybackMethod::Transcode,
+            "The audio with more channels than the host supports is downmixed"
+        );
+        let playback = StreamPlayback::new(
+            &surround,
+            Some(&streaming_server_url),
+            &PlaybackCapabilities {
+                max_audio_channels: 6,
+                ..capabilities.to_owned()
+            },
+            None,
+        )
+        .unwrap();
+        assert_eq!(playback.method, PlaybackMethod::DirectPlay);
+
+        let playback = StreamPlayback::new(&transcode, None, &capabilities, None).unwrap();
+        assert_eq!(playback.method, PlaybackMethod::DirectPlay);
+    }
+}
@@ -24,6 +24,19 @@ pub struct OpensubtitlesParamsResponse {
     pub size: u64,
 }
 
+/// Status of the player on a casting device.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CastingStatus {
+    /// Current playback time in milliseconds
+    pub time: u64,
+    /// Duration of the media in milliseconds
+    pub duration: u64,
+    pub paused: bool,
+    /// Volume in the range `0.0..=1.0`
+    pub volume: f64,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -29,6 +29,10 @@ pub struct StreamsItem {
 pub struct StreamItemState {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subtitle_track: Option<SubtitleTrack>,
+    /// Transport url of the addon which provided the selected subtitle track,
+    /// used for finding the same subtitles when their url has changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitles_origin: Option<Url>,
     /// In milliseconds
     #[serde(skip_serializing_if = "Option::is_none")]
     pub subtitle_delay: Option<i64>,
@@ -98,6 +102,7 @@ impl StreamsItem {
             } else if is_binge_match {
                 return StreamItemState {
                     subtitle_track: state.subtitle_track.filter(|track| track.embedded),
+                    subtitles_origin: None,
                     subtitle_delay: None,
                     audio_delay: None,
                     ..state
@@ -105,6 +110,7 @@ impl StreamsItem {
             }
             StreamItemState {
                 subtitle_track: None,
+                subtitles_origin: None,
                 audio_track: None,
                 ..state
             }
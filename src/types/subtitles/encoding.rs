@@ -0,0 +1,113 @@
+/// Characters of the `0x80..=0x9F` range of Windows-1252,
+/// the rest of the upper half matches ISO-8859-1 and thus the unicode code points.
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubtitlesEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// Fallback for the legacy single byte encodings.
+    Windows1252,
+}
+
+impl SubtitlesEncoding {
+    /// Detects the encoding by the byte order mark or, if missing, by the content.
+    pub fn detect(bytes: &[u8]) -> Self {
+        match bytes {
+            [0xEF, 0xBB, 0xBF, ..] => SubtitlesEncoding::Utf8,
+            [0xFF, 0xFE, ..] => SubtitlesEncoding::Utf16Le,
+            [0xFE, 0xFF, ..] => SubtitlesEncoding::Utf16Be,
+            _ if std::str::from_utf8(bytes).is_ok() => SubtitlesEncoding::Utf8,
+            _ => {
+                // text in latin script encoded as UTF-16 has a zero byte in every code unit
+                let zeros_at = |parity: usize| {
+                    bytes
+                        .iter()
+                        .skip(parity)
+                        .step_by(2)
+                        .filter(|byte| **byte == 0)
+                        .count()
+                };
+                let code_units = bytes.len() / 2;
+                if code_units > 0 && zeros_at(1) > code_units / 2 {
+                    SubtitlesEncoding::Utf16Le
+                } else if code_units > 0 && zeros_at(0) > code_units / 2 {
+                    SubtitlesEncoding::Utf16Be
+                } else {
+                    SubtitlesEncoding::Windows1252
+                }
+            }
+        }
+    }
+    /// Decodes the bytes, skipping the byte order mark.
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            SubtitlesEncoding::Utf8 => {
+                String::from_utf8_lossy(bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes))
+                    .into_owned()
+            }
+            SubtitlesEncoding::Utf16Le => decode_utf16(
+                bytes.strip_prefix(&[0xFF, 0xFE]).unwrap_or(bytes),
+                u16::from_le_bytes,
+            ),
+            SubtitlesEncoding::Utf16Be => decode_utf16(
+                bytes.strip_prefix(&[0xFE, 0xFF]).unwrap_or(bytes),
+                u16::from_be_bytes,
+            ),
+            SubtitlesEncoding::Windows1252 => bytes
+                .iter()
+                .map(|byte| match byte {
+                    0x80..=0x9F => WINDOWS_1252[(byte - 0x80) as usize],
+                    _ => *byte as char,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    char::decode_utf16(
+        bytes
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]])),
+    )
+    .map(|result| result.unwrap_or(char::REPLACEMENT_CHARACTER))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_decode() {
+        let utf8 = "Ça va?".as_bytes();
+        assert_eq!(SubtitlesEncoding::detect(utf8), SubtitlesEncoding::Utf8);
+        assert_eq!(SubtitlesEncoding::Utf8.decode(utf8), "Ça va?");
+
+        let utf16le = [0xFF, 0xFE, b'h', 0, b'i', 0];
+        assert_eq!(
+            SubtitlesEncoding::detect(&utf16le),
+            SubtitlesEncoding::Utf16Le
+        );
+        assert_eq!(SubtitlesEncoding::Utf16Le.decode(&utf16le), "hi");
+
+        let utf16be_no_bom = [0, b'h', 0, b'i', 0, 0xE9];
+        assert_eq!(
+            SubtitlesEncoding::detect(&utf16be_no_bom),
+            SubtitlesEncoding::Utf16Be
+        );
+        assert_eq!(SubtitlesEncoding::Utf16Be.decode(&utf16be_no_bom), "hié");
+
+        let windows1252 = [0x93, b'C', 0xE0, 0x94];
+        assert_eq!(
+            SubtitlesEncoding::detect(&windows1252),
+            SubtitlesEncoding::Windows1252
+        );
+        assert_eq!(SubtitlesEncoding::Windows1252.decode(&windows1252), "“Cà”");
+    }
+}
@@ -0,0 +1,8 @@
+mod encoding;
+pub use encoding::*;
+
+mod subtitles_document;
+pub use subtitles_document::*;
+
+mod subtitles_track;
+pub use subtitles_track::*;
@@ -0,0 +1,481 @@
+use std::fmt::Write;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use thiserror::Error;
+
+use crate::types::subtitles::SubtitlesEncoding;
+
+static SRT_TIMING_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*((?:\d+:)?\d+:\d+(?:[.,]\d+)?)\s*-->\s*((?:\d+:)?\d+:\d+(?:[.,]\d+)?)")
+        .expect("SRT timing regex failed to build")
+});
+static SUB_VIEWER_TIMING_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(\d+:\d+:\d+(?:\.\d+)?)\s*,\s*(\d+:\d+:\d+(?:\.\d+)?)\s*$")
+        .expect("SubViewer timing regex failed to build")
+});
+static WEBVTT_MARKUP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?P<tag></?(?P<name>[a-zA-Z]+)[^<>]*>)|&(?:[a-zA-Z]+|#[0-9]+|#x[0-9a-fA-F]+);|[&<]",
+    )
+    .expect("WebVTT markup regex failed to build")
+});
+/// Tags of the WebVTT cue text, the timestamp tags are not supported
+const WEBVTT_TAGS: &[&str] = &["b", "c", "i", "lang", "ruby", "rt", "u", "v"];
+static SSA_OVERRIDE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{[^}]*\}").expect("SSA override regex failed to build"));
+
+#[derive(Error, Clone, PartialEq, Eq, Debug)]
+pub enum SubtitlesError {
+    #[error("Unknown subtitles format")]
+    UnknownFormat,
+    #[error("Subtitles do not contain any cues")]
+    Empty,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubtitlesFormat {
+    Srt,
+    SubViewer,
+    /// SubStation Alpha and Advanced SubStation Alpha
+    Ssa,
+    WebVtt,
+}
+
+impl SubtitlesFormat {
+    pub fn detect(content: &str) -> Option<Self> {
+        let content = content.trim_start();
+        if content.starts_with("WEBVTT") {
+            Some(SubtitlesFormat::WebVtt)
+        } else if content.contains("[Script Info]") || content.contains("[Events]") {
+            Some(SubtitlesFormat::Ssa)
+        } else if content.contains("[INFORMATION]")
+            || content
+                .lines()
+                .any(|line| SUB_VIEWER_TIMING_REGEX.is_match(line))
+        {
+            Some(SubtitlesFormat::SubViewer)
+        } else if content.lines().any(|line| SRT_TIMING_REGEX.is_match(line)) {
+            Some(SubtitlesFormat::Srt)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SubtitlesCue {
+    /// In milliseconds
+    pub start: u64,
+    /// In milliseconds
+    pub end: u64,
+    /// Lines of the cue, may contain WebVTT tags like `<i>`
+    pub text: String,
+}
+
+/// Subtitles parsed from any of the supported [`SubtitlesFormat`]s.
+///
+/// # Examples
+///
+/// ```
+/// use stremio_core::types::subtitles::SubtitlesDocument;
+///
+/// let srt = "1\r\n00:00:01,000 --> 00:00:02,500\r\nHello\r\n\r\n";
+/// let mut subtitles = SubtitlesDocument::parse(srt.as_bytes()).expect("Should parse");
+/// subtitles.shift(500);
+///
+/// assert_eq!(
+///     subtitles.to_webvtt(),
+///     "WEBVTT\n\n00:00:01.500 --> 00:00:03.000\nHello\n\n"
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SubtitlesDocument {
+    pub format: SubtitlesFormat,
+    pub encoding: SubtitlesEncoding,
+    pub cues: Vec<SubtitlesCue>,
+}
+
+impl SubtitlesDocument {
+    pub fn parse(bytes: &[u8]) -> Result<Self, SubtitlesError> {
+        let encoding = SubtitlesEncoding::detect(bytes);
+        let content = encoding
+            .decode(bytes)
+            .replace("\r\n", "\n")
+            .replace('\r', "\n");
+        let format = SubtitlesFormat::detect(&content).ok_or(SubtitlesError::UnknownFormat)?;
+        let mut cues = match format {
+            SubtitlesFormat::Srt | SubtitlesFormat::WebVtt => parse_srt(&content, format),
+            SubtitlesFormat::SubViewer => parse_sub_viewer(&content),
+            SubtitlesFormat::Ssa => parse_ssa(&content),
+        };
+        if cues.is_empty() {
+            return Err(SubtitlesError::Empty);
+        }
+        cues.sort_by_key(|cue| cue.start);
+        Ok(Self {
+            format,
+            encoding,
+            cues,
+        })
+    }
+    /// Shifts the cues by the given offset in milliseconds,
+    /// positive values show the subtitles later.
+    ///
+    /// Cues which end before the start of the video are removed.
+    pub fn shift(&mut self, offset: i64) {
+        let shift = |time: u64| {
+            u64::try_from(
+                i64::try_from(time)
+                    .unwrap_or(i64::MAX)
+                    .saturating_add(offset),
+            )
+            .unwrap_or(0)
+        };
+        self.cues.retain_mut(|cue| {
+            cue.start = shift(cue.start);
+            cue.end = shift(cue.end);
+            cue.end > 0
+        });
+    }
+    /// Re-times subtitles made for a video with a different framerate,
+    /// e.g. from `23.976` to `25` frames per second.
+    pub fn convert_framerate(&mut self, from: f64, to: f64) {
+        if from <= 0.0 || to <= 0.0 {
+            return;
+        }
+        let ratio = from / to;
+        for cue in self.cues.iter_mut() {
+            cue.start = (cue.start as f64 * ratio).round() as u64;
+            cue.end = (cue.end as f64 * ratio).round() as u64;
+        }
+    }
+    pub fn to_webvtt(&self) -> String {
+        self.cues
+            .iter()
+            .fold(String::from("WEBVTT\n\n"), |mut webvtt, cue| {
+                let _ = write!(
+                    webvtt,
+                    "{} --> {}\n{}\n\n",
+                    format_timestamp(cue.start),
+                    format_timestamp(cue.end),
+                    escape_webvtt_text(&cue.text),
+                );
+                webvtt
+            })
+    }
+}
+
+/// Escapes `&` and `<` which are not a part of an entity or a WebVTT tag,
+/// other tags (e.g. `<font>` of SRT) are removed.
+///
+/// Empty lines are removed as well, as they would end the cue.
+fn escape_webvtt_text(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let line = WEBVTT_MARKUP_REGEX.replace_all(line, |captures: &Captures| {
+                match (captures.name("tag"), captures.name("name")) {
+                    (Some(tag), Some(name))
+                        if WEBVTT_TAGS.contains(&name.as_str().to_lowercase().as_str()) =>
+                    {
+                        tag.as_str().to_owned()
+                    }
+                    (Some(_), _) => String::new(),
+                    (None, _) => match &captures[0] {
+                        "&" => "&amp;".to_owned(),
+                        "<" => "&lt;".to_owned(),
+                        entity => entity.to_owned(),
+                    },
+                }
+            });
+            line.into_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses `h:mm:ss.fff`, `mm:ss.fff` and the variants with `,`
+/// or less fraction digits (`h:mm:ss.cc` in SSA) into milliseconds.
+fn parse_timestamp(timestamp: &str) -> Option<u64> {
+    let (time, fraction) = match timestamp.trim().split_once(['.', ',']) {
+        Some((time, fraction)) => (time, fraction),
+        None => (timestamp.trim(), ""),
+    };
+    let seconds = time.split(':').try_fold(0_u64, |seconds, part| {
+        part.parse::<u64>()
+            .ok()
+            .and_then(|part| seconds.checked_mul(60)?.checked_add(part))
+    })?;
+    let fraction = fraction.get(..fraction.len().min(3)).unwrap_or_default();
+    let millis = match fraction.len() {
+        0 => 0,
+        len => fraction.parse::<u64>().ok()? * 10_u64.pow(3 - len as u32),
+    };
+    seconds.checked_mul(1000)?.checked_add(millis)
+}
+
+fn format_timestamp(time: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        time / 3_600_000,
+        time / 60_000 % 60,
+        time / 1000 % 60,
+        time % 1000
+    )
+}
+
+/// Parses SRT and WebVTT, which differ mostly in the header and the timestamp separator.
+///
+/// Cues of SRT may contain empty lines, so they end only where the next cue starts.
+fn parse_srt(content: &str, format: SubtitlesFormat) -> Vec<SubtitlesCue> {
+    let lines = content.lines().collect::<Vec<_>>();
+    let timings = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| SRT_TIMING_REGEX.is_match(line))
+        .map(|(position, _)| position)
+        .collect::<Vec<_>>();
+    timings
+        .iter()
+        .enumerate()
+        .filter_map(|(index, &position)| {
+            let captures = SRT_TIMING_REGEX.captures(lines[position])?;
+            let start = parse_timestamp(captures.get(1)?.as_str())?;
+            let end = parse_timestamp(captures.get(2)?.as_str())?;
+            let next_position = timings.get(index + 1).copied().unwrap_or(lines.len());
+            let text_lines = match (format, &lines[position + 1..next_position]) {
+                (SubtitlesFormat::WebVtt, text_lines) => text_lines
+                    .split(|line| line.trim().is_empty())
+                    .next()
+                    .unwrap_or_default(),
+                // the number of the next cue
+                (SubtitlesFormat::Srt, [text_lines @ .., empty, number])
+                    if empty.trim().is_empty() && number.trim().parse::<u64>().is_ok() =>
+                {
+                    text_lines
+                }
+                (_, text_lines) => text_lines,
+            };
+            let text = text_lines
+                .iter()
+                .map(|line| {
+                    SSA_OVERRIDE_REGEX
+                        .replace_all(line, "")
+                        .trim_end()
+                        .to_owned()
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim_matches('\n')
+                .to_owned();
+            Some(SubtitlesCue { start, end, text })
+        })
+        .filter(|cue| !cue.text.is_empty())
+        .collect()
+}
+
+fn parse_sub_viewer(content: &str) -> Vec<SubtitlesCue> {
+    let mut lines = content.lines();
+    let mut cues = vec![];
+    while let Some(line) = lines.next() {
+        let captures = match SUB_VIEWER_TIMING_REGEX.captures(line) {
+            Some(captures) => captures,
+            None => continue,
+        };
+        let timing = captures
+            .get(1)
+            .zip(captures.get(2))
+            .and_then(|(start, end)| {
+                parse_timestamp(start.as_str()).zip(parse_timestamp(end.as_str()))
+            });
+        if let (Some((start, end)), Some(text)) = (timing, lines.next()) {
+            let text = text.replace("[br]", "\n").replace("[BR]", "\n");
+            if !text.trim().is_empty() {
+                cues.push(SubtitlesCue { start, end, text });
+            }
+        }
+    }
+    cues
+}
+
+fn parse_ssa(content: &str) -> Vec<SubtitlesCue> {
+    let events = content
+        .lines()
+        .skip_while(|line| !line.trim().eq_ignore_ascii_case("[Events]"))
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['));
+    let mut format = vec![];
+    let mut cues = vec![];
+    for line in events {
+        let (kind, value) = match line.split_once(':') {
+            Some((kind, value)) => (kind.trim(), value.trim_start()),
+            None => continue,
+        };
+        match kind {
+            "Format" => {
+                format = value
+                    .split(',')
+                    .map(|field| field.trim().to_lowercase())
+                    .collect();
+            }
+            "Dialogue" if !format.is_empty() => {
+                // the text is the last field and may contain commas
+                let fields = value.splitn(format.len(), ',').collect::<Vec<_>>();
+                let field = |name: &str| {
+                    format
+                        .iter()
+                        .position(|field| field == name)
+                        .and_then(|position| fields.get(position))
+                };
+                let cue = field("start")
+                    .and_then(|start| parse_timestamp(start))
+                    .zip(field("end").and_then(|end| parse_timestamp(end)))
+                    .zip(field("text"))
+                    .map(|((start, end), text)| SubtitlesCue {
+                        start,
+                        end,
+                        text: SSA_OVERRIDE_REGEX
+                            .replace_all(text, "")
+                            .replace("\\N", "\n")
+                            .replace("\\n", "\n")
+                            .replace("\\h", " ")
+                            .trim()
+                            .to_owned(),
+                    });
+                if let Some(cue) = cue.filter(|cue| !cue.text.is_empty()) {
+                    cues.push(cue);
+                }
+            }
+            _ => {}
+        }
+    }
+    cues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(start: u64, end: u64, text: &str) -> SubtitlesCue {
+        SubtitlesCue {
+            start,
+            end,
+            text: text.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("01:02:03,456"), Some(3_723_456));
+        assert_eq!(parse_timestamp("02:03.456"), Some(123_456));
+        assert_eq!(parse_timestamp("0:00:01.50"), Some(1_500));
+        assert_eq!(parse_timestamp("00:00:05"), Some(5_000));
+        assert_eq!(parse_timestamp("invalid"), None);
+        assert_eq!(parse_timestamp("18446744073709551:00:00.000"), None);
+        assert_eq!(parse_timestamp("18446744073709551.616"), None);
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\n{\\an8}<i>Hello</i>\nworld\n\n\n2\n00:00:03,000 --> 00:00:04,000\nBye\n";
+        let subtitles = SubtitlesDocument::parse(srt.as_bytes()).unwrap();
+        assert_eq!(subtitles.format, SubtitlesFormat::Srt);
+        assert_eq!(
+            subtitles.cues,
+            vec![
+                cue(1_000, 2_000, "<i>Hello</i>\nworld"),
+                cue(3_000, 4_000, "Bye")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_srt_empty_lines() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\nHello\n\nworld\n\n2\n00:00:03,000 --> 00:00:04,000\nBye\n\n";
+        let subtitles = SubtitlesDocument::parse(srt.as_bytes()).unwrap();
+        assert_eq!(
+            subtitles.cues,
+            vec![
+                cue(1_000, 2_000, "Hello\n\nworld"),
+                cue(3_000, 4_000, "Bye")
+            ]
+        );
+        assert_eq!(
+            subtitles.to_webvtt(),
+            "WEBVTT\n\n00:00:01.000 --> 00:00:02.000\nHello\nworld\n\n00:00:03.000 --> 00:00:04.000\nBye\n\n"
+        );
+    }
+
+    #[test]
+    fn test_to_webvtt_escapes_text() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\n<font color=\"red\">Tom & Jerry</font>\n<i>1 < 2</i> &amp;\n";
+        let subtitles = SubtitlesDocument::parse(srt.as_bytes()).unwrap();
+        assert_eq!(
+            subtitles.to_webvtt(),
+            "WEBVTT\n\n00:00:01.000 --> 00:00:02.000\nTom &amp; Jerry\n<i>1 &lt; 2</i> &amp;\n\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_webvtt() {
+        let webvtt =
+            "WEBVTT\n\nNOTE a comment\n\nintro\n00:01.000 --> 00:02.000 align:start\nHello\n";
+        let subtitles = SubtitlesDocument::parse(webvtt.as_bytes()).unwrap();
+        assert_eq!(subtitles.format, SubtitlesFormat::WebVtt);
+        assert_eq!(subtitles.cues, vec![cue(1_000, 2_000, "Hello")]);
+    }
+
+    #[test]
+    fn test_parse_sub_viewer() {
+        let sub = "[INFORMATION]\n[TITLE]Title\n[END INFORMATION]\n\n00:00:01.50,00:00:03.00\nHello[br]world\n\n00:00:04.00,00:00:05.00\nBye\n";
+        let subtitles = SubtitlesDocument::parse(sub.as_bytes()).unwrap();
+        assert_eq!(subtitles.format, SubtitlesFormat::SubViewer);
+        assert_eq!(
+            subtitles.cues,
+            vec![cue(1_500, 3_000, "Hello\nworld"), cue(4_000, 5_000, "Bye")]
+        );
+    }
+
+    #[test]
+    fn test_parse_ssa() {
+        let ass = "[Script Info]\nTitle: Title\n\n[V4+ Styles]\nFormat: Name, Fontname\nStyle: Default,Arial\n\n[Events]\nFormat: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\nDialogue: 0,0:00:02.00,0:00:03.50,Default,,0,0,0,,{\\i1}Hello,\\Nworld\nComment: 0,0:00:04.00,0:00:05.00,Default,,0,0,0,,Ignored\nDialogue: 0,0:00:01.00,0:00:01.50,Default,,0,0,0,,First\n";
+        let subtitles = SubtitlesDocument::parse(ass.as_bytes()).unwrap();
+        assert_eq!(subtitles.format, SubtitlesFormat::Ssa);
+        assert_eq!(
+            subtitles.cues,
+            vec![
+                cue(1_000, 1_500, "First"),
+                cue(2_000, 3_500, "Hello,\nworld")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shift_and_convert_framerate() {
+        let srt =
+            "1\n00:00:01,000 --> 00:00:02,000\nFirst\n\n2\n00:00:25,000 --> 00:00:26,000\nSecond\n";
+        let mut subtitles = SubtitlesDocument::parse(srt.as_bytes()).unwrap();
+        subtitles.shift(-1_500);
+        assert_eq!(
+            subtitles.cues,
+            vec![cue(0, 500, "First"), cue(23_500, 24_500, "Second")]
+        );
+        subtitles.shift(-600);
+        assert_eq!(subtitles.cues, vec![cue(22_900, 23_900, "Second")]);
+        subtitles.convert_framerate(25.0, 23.976);
+        assert_eq!(subtitles.cues, vec![cue(23_878, 24_921, "Second")]);
+        assert_eq!(
+            subtitles.to_webvtt(),
+            "WEBVTT\n\n00:00:23.878 --> 00:00:24.921\nSecond\n\n"
+        );
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        assert_eq!(
+            SubtitlesDocument::parse(b"just some text"),
+            Err(SubtitlesError::UnknownFormat)
+        );
+    }
+}
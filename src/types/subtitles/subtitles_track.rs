@@ -0,0 +1,347 @@
+use serde::Serialize;
+use url::Url;
+
+use crate::types::profile::Settings;
+use crate::types::resource::{Stream, Subtitles};
+use crate::types::streams::StreamItemState;
+
+/// Subtitles from the stream itself or from an addon,
+/// merged and ranked with [`SubtitlesTrack::merge`].
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtitlesTrack {
+    /// Id of the track: `{transport_url}_{position}` for the subtitles of an addon
+    /// and `stream_{position}` for the subtitles of the stream,
+    /// where `position` is the index in the subtitles returned by the addon or in the stream.
+    ///
+    /// This is the id which should be used for the [`SubtitleTrack`](crate::types::streams::SubtitleTrack)
+    /// in the [`StreamItemState`].
+    pub id: String,
+    pub lang: String,
+    pub url: Url,
+    /// Transport url of the addon which returned the subtitles,
+    /// `None` for the subtitles of the [`Stream`].
+    pub origin: Option<Url>,
+    /// Whether the subtitles are known to be made for this exact video file,
+    /// i.e. the `videoHash`/`videoSize` hints of the stream or of the subtitles match the played video.
+    pub exact_match: bool,
+}
+
+impl SubtitlesTrack {
+    /// Merges the subtitles of the stream with the subtitles of the addons
+    /// (in the order of the addons), removes the duplicates
+    /// and ranks them by the subtitles languages in the settings.
+    ///
+    /// `video_hash` and `video_size` are the params of the played video.
+    pub fn merge<'a>(
+        stream: &Stream,
+        addons_subtitles: impl IntoIterator<Item = (&'a Url, &'a [Subtitles])>,
+        video_hash: Option<&str>,
+        video_size: Option<u64>,
+        settings: &Settings,
+    ) -> Vec<SubtitlesTrack> {
+        let stream_subtitles = stream
+            .subtitles
+            .iter()
+            .enumerate()
+            .map(|(position, subtitles)| {
+                let exact_match = is_exact_match(
+                    stream.behavior_hints.video_hash.as_deref(),
+                    stream.behavior_hints.video_size,
+                    video_hash,
+                    video_size,
+                );
+                (format!("stream_{position}"), None, subtitles, exact_match)
+            });
+        let addons_subtitles =
+            addons_subtitles
+                .into_iter()
+                .flat_map(|(transport_url, subtitles)| {
+                    subtitles
+                        .iter()
+                        .enumerate()
+                        .map(move |(position, subtitles)| {
+                            let exact_match = is_exact_match(
+                                subtitles.video_hash.as_deref(),
+                                subtitles.video_size,
+                                video_hash,
+                                video_size,
+                            );
+                            (
+                                format!("{transport_url}_{position}"),
+                                Some(transport_url),
+                                subtitles,
+                                exact_match,
+                            )
+                        })
+                });
+        let mut tracks = stream_subtitles.chain(addons_subtitles).fold(
+            Vec::<SubtitlesTrack>::new(),
+            |mut tracks, (id, origin, subtitles, exact_match)| {
+                if !tracks.iter().any(|track| track.url == subtitles.url) {
+                    tracks.push(SubtitlesTrack {
+                        id,
+                        lang: subtitles.lang.to_owned(),
+                        url: subtitles.url.to_owned(),
+                        origin: origin.cloned(),
+                        exact_match,
+                    });
+                };
+                tracks
+            },
+        );
+        // stable sort, so the order of the addons is kept for equally ranked tracks
+        tracks.sort_by_key(|track| (track.language_rank(settings), !track.exact_match));
+        tracks
+    }
+    /// Finds the track remembered in the state of the stream.
+    ///
+    /// Tries the same track first, then a track in the same language from the same addon
+    /// (the url may change between requests) and then any track in the same language.
+    pub fn select<'a>(
+        tracks: &'a [SubtitlesTrack],
+        state: &StreamItemState,
+    ) -> Option<&'a SubtitlesTrack> {
+        let subtitle_track = state
+            .subtitle_track
+            .as_ref()
+            .filter(|subtitle_track| !subtitle_track.embedded)?;
+        let is_same_language = |track: &&SubtitlesTrack| {
+            subtitle_track
+                .language
+                .as_ref()
+                .is_some_and(|language| is_same_language(language, &track.lang))
+        };
+        tracks
+            .iter()
+            .find(|track| track.id == subtitle_track.id)
+            .or_else(|| {
+                tracks
+                    .iter()
+                    .filter(|track| {
+                        state.subtitles_origin.is_some() && track.origin == state.subtitles_origin
+                    })
+                    .find(is_same_language)
+            })
+            .or_else(|| tracks.iter().find(is_same_language))
+    }
+    /// Finds the best ranked track in the given language.
+    pub fn select_by_language<'a>(
+        tracks: &'a [SubtitlesTrack],
+        language: &str,
+    ) -> Option<&'a SubtitlesTrack> {
+        tracks
+            .iter()
+            .find(|track| is_same_language(language, &track.lang))
+    }
+    fn language_rank(&self, settings: &Settings) -> u8 {
+        let is_language = |language: &Option<String>| {
+            language
+                .as_ref()
+                .is_some_and(|language| is_same_language(language, &self.lang))
+        };
+        if is_language(&settings.subtitles_language) {
+            0
+        } else if is_language(&settings.secondary_subtitles_language) {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+/// The hash is compared if both of the hashes are known, otherwise the size.
+fn is_exact_match(
+    subtitles_video_hash: Option<&str>,
+    subtitles_video_size: Option<u64>,
+    video_hash: Option<&str>,
+    video_size: Option<u64>,
+) -> bool {
+    match (subtitles_video_hash.zip(video_hash), video_size) {
+        (Some((subtitles_video_hash, video_hash)), _) => subtitles_video_hash == video_hash,
+        (None, Some(video_size)) => subtitles_video_size == Some(video_size),
+        _ => false,
+    }
+}
+
+fn is_same_language(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::resource::StreamSource;
+    use crate::types::streams::SubtitleTrack;
+
+    fn subtitles(lang: &str, url: &str) -> Subtitles {
+        Subtitles {
+            lang: lang.to_owned(),
+            url: url.parse().unwrap(),
+            video_hash: None,
+            video_size: None,
+        }
+    }
+
+    fn stream(subtitles: Vec<Subtitles>) -> Stream {
+        Stream {
+            source: StreamSource::Url {
+                url: "https://example.com/video.mp4".parse().unwrap(),
+            },
+            name: None,
+            description: None,
+            thumbnail: None,
+            subtitles,
+            behavior_hints: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_merge_dedupes_and_ranks() {
+        let addon_a: Url = "https://addon-a.com/manifest.json".parse().unwrap();
+        let addon_b: Url = "https://addon-b.com/manifest.json".parse().unwrap();
+        let addon_a_subtitles = vec![
+            subtitles("fre", "https://subs.com/1.srt"),
+            subtitles("eng", "https://subs.com/2.srt"),
+        ];
+        let addon_b_subtitles = vec![
+            subtitles("eng", "https://subs.com/2.srt"),
+            subtitles("bul", "https://subs.com/3.srt"),
+            subtitles("eng", "https://subs.com/4.srt"),
+        ];
+        let settings = Settings {
+            subtitles_language: Some("eng".to_owned()),
+            secondary_subtitles_language: Some("bul".to_owned()),
+            ..Default::default()
+        };
+        let tracks = SubtitlesTrack::merge(
+            &stream(vec![]),
+            [
+                (&addon_a, addon_a_subtitles.as_slice()),
+                (&addon_b, addon_b_subtitles.as_slice()),
+            ],
+            None,
+            None,
+            &settings,
+        );
+        assert_eq!(
+            tracks
+                .iter()
+                .map(|track| (track.id.as_str(), track.origin.as_ref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("https://addon-a.com/manifest.json_1", Some(&addon_a)),
+                ("https://addon-b.com/manifest.json_2", Some(&addon_b)),
+                ("https://addon-b.com/manifest.json_1", Some(&addon_b)),
+                ("https://addon-a.com/manifest.json_0", Some(&addon_a)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_exact_match() {
+        let mut stream = stream(vec![subtitles("eng", "https://subs.com/stream.srt")]);
+        stream.behavior_hints.video_hash = Some("hash".to_owned());
+        let addon: Url = "https://addon.com/manifest.json".parse().unwrap();
+        let addon_subtitles = vec![subtitles("eng", "https://subs.com/addon.srt")];
+        let settings = Settings::default();
+
+        let tracks = SubtitlesTrack::merge(
+            &stream,
+            [(&addon, addon_subtitles.as_slice())],
+            Some("hash"),
+            None,
+            &settings,
+        );
+        assert!(tracks[0].exact_match);
+        assert_eq!(tracks[0].origin, None);
+
+        let tracks = SubtitlesTrack::merge(
+            &stream,
+            [(&addon, addon_subtitles.as_slice())],
+            Some("other_hash"),
+            None,
+            &settings,
+        );
+        assert!(!tracks[0].exact_match);
+
+        let addon_subtitles = vec![
+            subtitles("eng", "https://subs.com/other.srt"),
+            Subtitles {
+                video_size: Some(1024),
+                ..subtitles("eng", "https://subs.com/size.srt")
+            },
+            Subtitles {
+                video_hash: Some("other_hash".to_owned()),
+                video_size: Some(1024),
+                ..subtitles("eng", "https://subs.com/hash.srt")
+            },
+        ];
+        let tracks = SubtitlesTrack::merge(
+            &stream,
+            [(&addon, addon_subtitles.as_slice())],
+            Some("other_hash"),
+            Some(1024),
+            &settings,
+        );
+        assert_eq!(
+            tracks
+                .iter()
+                .filter(|track| track.exact_match)
+                .map(|track| track.url.as_str())
+                .collect::<Vec<_>>(),
+            vec!["https://subs.com/size.srt", "https://subs.com/hash.srt"],
+            "Subtitles of the addons are matched by their hints"
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        let addon: Url = "https://addon.com/manifest.json".parse().unwrap();
+        let tracks = vec![
+            SubtitlesTrack {
+                id: "stream_0".to_owned(),
+                lang: "eng".to_owned(),
+                url: "https://subs.com/1.srt".parse().unwrap(),
+                origin: None,
+                exact_match: false,
+            },
+            SubtitlesTrack {
+                id: "https://addon.com/manifest.json_0".to_owned(),
+                lang: "eng".to_owned(),
+                url: "https://subs.com/2.srt?token=new".parse().unwrap(),
+                origin: Some(addon.to_owned()),
+                exact_match: false,
+            },
+        ];
+        let state = |id: &str, embedded: bool| StreamItemState {
+            subtitle_track: Some(SubtitleTrack {
+                id: id.to_owned(),
+                embedded,
+                language: Some("eng".to_owned()),
+            }),
+            subtitle_delay: None,
+            subtitles_origin: Some(addon.to_owned()),
+            audio_track: None,
+            audio_delay: None,
+            playback_speed: None,
+            player_type: None,
+        };
+
+        assert_eq!(
+            SubtitlesTrack::select(&tracks, &state("stream_0", false)),
+            Some(&tracks[0]),
+            "Same track is selected"
+        );
+        assert_eq!(
+            SubtitlesTrack::select(&tracks, &state("https://addon.com/manifest.json_3", false)),
+            Some(&tracks[1]),
+            "Track in the same language from the same addon is selected"
+        );
+        assert_eq!(
+            SubtitlesTrack::select(&tracks, &state("1", true)),
+            None,
+            "Embedded tracks are selected by the player"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::profile::UID;
+use crate::types::watch_history::WatchHistoryItem;
+
+/// Sessions in which less than this was watched are not recorded, in milliseconds
+const MIN_TIME_WATCHED: u64 = 60_000;
+const MAX_ITEMS: usize = 1000;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchHistoryBucket {
+    pub uid: UID,
+    /// The sessions, the most recent first
+    pub items: Vec<WatchHistoryItem>,
+}
+
+impl WatchHistoryBucket {
+    pub fn new(uid: UID) -> Self {
+        WatchHistoryBucket { uid, items: vec![] }
+    }
+    /// Records the session, dropping the oldest ones once there are too many.
+    ///
+    /// Returns `false` if too little was watched in it.
+    pub fn add(&mut self, item: WatchHistoryItem) -> bool {
+        if item.time_watched < MIN_TIME_WATCHED {
+            return false;
+        }
+        self.items.retain(|other| other.id != item.id);
+        let position = self
+            .items
+            .iter()
+            .position(|other| other.start <= item.start)
+            .unwrap_or(self.items.len());
+        self.items.insert(position, item);
+        self.items.truncate(MAX_ITEMS);
+        true
+    }
+    /// Returns `false` if there's no such session.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let len = self.items.len();
+        self.items.retain(|item| item.id != id);
+        self.items.len() != len
+    }
+    /// Removes all the sessions of a library item.
+    ///
+    /// Returns `false` if there are none.
+    pub fn remove_meta(&mut self, meta_id: &str) -> bool {
+        let len = self.items.len();
+        self.items.retain(|item| item.meta_id != meta_id);
+        self.items.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::*;
+    use crate::types::resource::StreamSource;
+
+    fn item(id: &str, meta_id: &str, hours: i64, time_watched: u64) -> WatchHistoryItem {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::hours(hours);
+        WatchHistoryItem {
+            id: id.to_owned(),
+            meta_id: meta_id.to_owned(),
+            r#type: "series".to_owned(),
+            name: "name".to_owned(),
+            poster: None,
+            video_id: format!("{meta_id}:1:1"),
+            stream_source: StreamSource::Url {
+                url: "https://source_url".parse().unwrap(),
+            },
+            start,
+            end: start + Duration::minutes(30),
+            from: 0,
+            to: time_watched,
+            time_watched,
+            duration: 2_400_000,
+            device: "web".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_add() {
+        let mut bucket = WatchHistoryBucket::default();
+        assert!(bucket.add(item("1", "tt1", 0, 600_000)));
+        assert!(bucket.add(item("3", "tt2", 2, 600_000)));
+        assert!(bucket.add(item("2", "tt1", 1, 600_000)));
+        assert!(
+            !bucket.add(item("4", "tt1", 3, 10_000)),
+            "Short sessions are not recorded"
+        );
+        assert_eq!(
+            bucket
+                .items
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["3", "2", "1"],
+            "Most recent sessions are first"
+        );
+
+        for hours in 0..MAX_ITEMS as i64 {
+            bucket.add(item(&format!("new{hours}"), "tt3", 10 + hours, 600_000));
+        }
+        assert_eq!(bucket.items.len(), MAX_ITEMS);
+        assert!(
+            !bucket.items.iter().any(|item| item.id == "1"),
+            "Oldest sessions are dropped"
+        );
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut bucket = WatchHistoryBucket::default();
+        bucket.add(item("1", "tt1", 0, 600_000));
+        bucket.add(item("2", "tt1", 1, 600_000));
+        bucket.add(item("3", "tt2", 2, 600_000));
+        assert!(bucket.remove("3"));
+        assert!(!bucket.remove("3"));
+        assert!(bucket.remove_meta("tt1"));
+        assert!(bucket.items.is_empty());
+    }
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::types::library::LibraryItemId;
+use crate::types::resource::{StreamSource, VideoId};
+
+/// A single viewing session of a video in the player.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchHistoryItem {
+    /// Unique id of the session
+    pub id: String,
+    pub meta_id: LibraryItemId,
+    pub r#type: String,
+    pub name: String,
+    pub poster: Option<Url>,
+    pub video_id: VideoId,
+    pub stream_source: StreamSource,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// The time of the video the session started at, in milliseconds
+    pub from: u64,
+    /// The time of the video the session ended at, in milliseconds
+    pub to: u64,
+    /// In milliseconds
+    pub time_watched: u64,
+    /// In milliseconds
+    pub duration: u64,
+    pub device: String,
+}
+
+impl WatchHistoryItem {
+    pub fn is_same_video(&self, meta_id: &str, video_id: &str) -> bool {
+        self.meta_id == meta_id && self.video_id == video_id
+    }
+}
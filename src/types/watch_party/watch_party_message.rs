@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::addon::ResourceRequest;
+use crate::types::resource::Stream;
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WatchPartyCommand {
+    Join,
+    Leave,
+    /// Sent periodically by every member to stay in the party,
+    /// the playback of the leader is used for drift correction.
+    #[serde(rename_all = "camelCase")]
+    State {
+        /// In milliseconds, `None` when nothing is played
+        time: Option<u64>,
+        paused: bool,
+    },
+    /// In milliseconds
+    Play {
+        time: u64,
+    },
+    /// In milliseconds
+    Pause {
+        time: u64,
+    },
+    /// In milliseconds
+    Seek {
+        time: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    StreamChanged {
+        stream: Box<Stream>,
+        stream_request: Option<ResourceRequest>,
+        meta_request: Option<ResourceRequest>,
+    },
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchPartyMessage {
+    pub member_id: String,
+    pub sent: DateTime<Utc>,
+    pub command: WatchPartyCommand,
+}
+
+/// A message ordered by the transport, which all the members receive in the same order.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchPartyEnvelope {
+    pub seq: u64,
+    pub message: WatchPartyMessage,
+}
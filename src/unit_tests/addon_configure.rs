@@ -0,0 +1,194 @@
+use std::{any::Any, collections::HashMap};
+
+use futures::future;
+use semver::Version;
+use stremio_derive::Model;
+use url::Url;
+
+use crate::{
+    models::{
+        addon_configure::{AddonConfigure, ConfigValueError, Selected},
+        common::Loadable,
+        ctx::Ctx,
+    },
+    runtime::{
+        msg::{Action, ActionAddonConfigure, ActionLoad},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{Descriptor, Manifest, ManifestBehaviorHints, ManifestConfig, ManifestConfigType},
+        profile::Profile,
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS},
+};
+
+const CONFIGURED_TRANSPORT_URL: &str =
+    "https://addon.com/%7B%22apiKey%22%3A%22old%22%7D/manifest.json";
+
+fn manifest() -> Manifest {
+    let config = |key: &str, r#type, default: Option<&str>, required| ManifestConfig {
+        key: key.to_owned(),
+        r#type,
+        title: None,
+        options: match r#type {
+            ManifestConfigType::Select => vec!["hd".to_owned(), "sd".to_owned()],
+            _ => vec![],
+        },
+        default: default.map(ToOwned::to_owned),
+        required,
+    };
+    Manifest {
+        id: "addon".to_owned(),
+        version: Version::new(0, 0, 1),
+        name: "Addon".to_owned(),
+        contact_email: None,
+        description: None,
+        logo: None,
+        background: None,
+        types: vec!["movie".to_owned()],
+        resources: vec![],
+        id_prefixes: None,
+        catalogs: vec![],
+        addon_catalogs: vec![],
+        behavior_hints: ManifestBehaviorHints {
+            configurable: true,
+            configuration_required: true,
+            ..Default::default()
+        },
+        config: vec![
+            config("apiKey", ManifestConfigType::Password, None, true),
+            config("limit", ManifestConfigType::Number, Some("10"), false),
+            config("quality", ManifestConfigType::Select, Some("hd"), false),
+            config("adult", ManifestConfigType::Checkbox, None, false),
+        ],
+    }
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    if request.url.starts_with("https://addon.com/") && request.url.ends_with("/manifest.json") {
+        return future::ok(Box::new(manifest()) as Box<dyn Any + Send>).boxed_env();
+    }
+    default_fetch_handler(request)
+}
+
+fn values(values: &[(&str, &str)]) -> HashMap<String, String> {
+    values
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn addon_configure() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        addon_configure: AddonConfigure,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let configured_addon = Descriptor {
+        manifest: manifest(),
+        transport_url: Url::parse(CONFIGURED_TRANSPORT_URL).unwrap(),
+        flags: Default::default(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![configured_addon],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            addon_configure: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::AddonConfigure(Selected {
+                transport_url: Url::parse(CONFIGURED_TRANSPORT_URL).unwrap(),
+            })),
+        })
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap()[0].url,
+        "https://addon.com/manifest.json",
+        "The unconfigured addon is requested"
+    );
+    assert_eq!(
+        runtime.model().unwrap().addon_configure.values,
+        values(&[("apiKey", "old"), ("limit", "10"), ("quality", "hd")]),
+        "The values are the current configuration and the defaults"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::AddonConfigure(ActionAddonConfigure::Configure(values(&[
+                ("apiKey", " "),
+                ("limit", "ten"),
+                ("quality", "4k"),
+            ]))),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().addon_configure.errors,
+        HashMap::from([
+            ("apiKey".to_owned(), ConfigValueError::Required),
+            ("limit".to_owned(), ConfigValueError::InvalidNumber),
+            ("quality".to_owned(), ConfigValueError::InvalidOption),
+        ])
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "Invalid values are not submitted"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::AddonConfigure(ActionAddonConfigure::Configure(values(&[
+                ("apiKey", "new"),
+                ("limit", "20"),
+                ("quality", "sd"),
+                ("adult", "checked"),
+            ]))),
+        })
+    });
+    let configured_transport_url = "https://addon.com/%7B%22adult%22%3A%22checked%22%2C%22apiKey%22%3A%22new%22%2C%22limit%22%3A%2220%22%2C%22quality%22%3A%22sd%22%7D/manifest.json";
+    assert_eq!(REQUESTS.read().unwrap()[1].url, configured_transport_url);
+    let model = runtime.model().unwrap();
+    assert!(model.addon_configure.errors.is_empty());
+    assert!(matches!(
+        model
+            .addon_configure
+            .configured_addon
+            .as_ref()
+            .map(|addon| &addon.content),
+        Some(Loadable::Ready(_))
+    ));
+    assert_eq!(
+        model
+            .ctx
+            .profile
+            .addons
+            .iter()
+            .map(|addon| addon.transport_url.as_str())
+            .collect::<Vec<_>>(),
+        vec![configured_transport_url],
+        "The configured addon replaces the previous configuration"
+    );
+    assert!(
+        !model.ctx.profile.addons[0]
+            .manifest
+            .behavior_hints
+            .configuration_required
+    );
+}
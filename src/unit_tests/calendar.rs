@@ -0,0 +1,257 @@
+use std::any::Any;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use futures::future;
+use semver::Version;
+use stremio_derive::Model;
+use url::Url;
+
+use crate::{
+    constants::{CATALOG_RESOURCE_NAME, LAST_VIDEOS_IDS_EXTRA_PROP},
+    models::{
+        calendar::{Calendar, CalendarRange, Selectable, Selected},
+        ctx::Ctx,
+    },
+    runtime::{
+        msg::{Action, ActionLoad},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{Descriptor, Manifest, ManifestCatalog, ManifestExtra, ResourceResponse},
+        library::{LibraryBucket, LibraryItem},
+        profile::Profile,
+        resource::{MetaItem, MetaItemBehaviorHints, MetaItemPreview, SeriesInfo, Video},
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS},
+};
+
+fn video(season: u32, episode: u32, released: (i32, u32, u32)) -> Video {
+    Video {
+        id: format!("tt1:{season}:{episode}"),
+        released: Some(
+            Utc.with_ymd_and_hms(released.0, released.1, released.2, 20, 0, 0)
+                .unwrap(),
+        ),
+        series_info: Some(SeriesInfo { season, episode }),
+        ..Default::default()
+    }
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request.url.as_str() {
+        "https://addon.com/catalog/series/lastVideosIds/lastVideosIds=tt1.json" => {
+            future::ok(Box::new(ResourceResponse::MetasDetailed {
+                metas_detailed: vec![MetaItem {
+                    preview: MetaItemPreview {
+                        id: "tt1".to_owned(),
+                        r#type: "series".to_owned(),
+                        name: "Series".to_owned(),
+                        ..Default::default()
+                    },
+                    videos: vec![
+                        video(1, 1, (2024, 5, 10)),
+                        video(1, 2, (2024, 5, 20)),
+                        // specials are not shown
+                        video(0, 1, (2024, 5, 21)),
+                        video(1, 3, (2024, 6, 3)),
+                    ],
+                }],
+            }) as Box<dyn Any + Send>)
+            .boxed_env()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn library_item(id: &str, has_scheduled_videos: bool) -> LibraryItem {
+    LibraryItem {
+        id: id.to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        state: Default::default(),
+        name: id.to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: MetaItemBehaviorHints {
+            has_scheduled_videos,
+            ..Default::default()
+        },
+    }
+}
+
+#[test]
+fn calendar() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        calendar: Calendar,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2024, 5, 15, 12, 0, 0).unwrap();
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "addon".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "Addon".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["series".to_owned()],
+            resources: vec![CATALOG_RESOURCE_NAME.into()],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![ManifestCatalog {
+                id: "lastVideosIds".to_owned(),
+                r#type: "series".to_owned(),
+                name: None,
+                extra: ManifestExtra::Full {
+                    props: vec![LAST_VIDEOS_IDS_EXTRA_PROP.to_owned()],
+                },
+            }],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+            config: vec![],
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(
+                    None,
+                    vec![library_item("tt1", true), library_item("tt2", false)],
+                ),
+                ..Default::default()
+            },
+            calendar: Calendar::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Calendar(Selected {
+                range: CalendarRange::Month {
+                    year: 2024,
+                    month: 5,
+                },
+            })),
+        })
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "Only the series with scheduled videos are requested"
+    );
+    {
+        let calendar = &runtime.model().unwrap().calendar;
+        assert_eq!(
+            calendar.selectable,
+            Selectable {
+                previous: Some(Selected {
+                    range: CalendarRange::Month {
+                        year: 2024,
+                        month: 4,
+                    },
+                }),
+                next: Some(Selected {
+                    range: CalendarRange::Month {
+                        year: 2024,
+                        month: 6,
+                    },
+                }),
+            }
+        );
+        assert_eq!(calendar.days.len(), 31, "Every day of the month is listed");
+        let days_with_items = calendar
+            .days
+            .iter()
+            .filter(|day| !day.items.is_empty())
+            .map(|day| {
+                (
+                    day.date,
+                    day.items
+                        .iter()
+                        .map(|item| item.video.id.as_str())
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            days_with_items,
+            vec![
+                (
+                    NaiveDate::from_ymd_opt(2024, 5, 10).unwrap(),
+                    vec!["tt1:1:1"]
+                ),
+                (
+                    NaiveDate::from_ymd_opt(2024, 5, 20).unwrap(),
+                    vec!["tt1:1:2"]
+                ),
+            ],
+            "Videos are grouped by day of release"
+        );
+        assert_eq!(
+            calendar.days[19].items[0].request.base.as_str(),
+            "https://addon.com/manifest.json"
+        );
+        assert_eq!(calendar.days[19].items[0].request.path.resource, "meta");
+    }
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Calendar(Selected {
+                range: CalendarRange::Week {
+                    year: 2024,
+                    week: 23,
+                },
+            })),
+        })
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "Changing the range does not request the catalogs again"
+    );
+    {
+        let calendar = &runtime.model().unwrap().calendar;
+        assert_eq!(calendar.days.len(), 7);
+        assert_eq!(
+            calendar.days[0].date,
+            NaiveDate::from_ymd_opt(2024, 6, 3).unwrap(),
+            "Weeks start on Monday"
+        );
+        assert_eq!(calendar.days[0].items[0].video.id, "tt1:1:3");
+        assert_eq!(
+            calendar.selectable.previous,
+            Some(Selected {
+                range: CalendarRange::Week {
+                    year: 2024,
+                    week: 22,
+                },
+            })
+        );
+    }
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Unload,
+        })
+    });
+    let calendar = &runtime.model().unwrap().calendar;
+    assert!(calendar.selected.is_none());
+    assert!(calendar.days.is_empty());
+    assert!(calendar.catalogs.is_empty());
+}
@@ -0,0 +1,176 @@
+use std::any::Any;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{Duration, TimeZone, Utc};
+use futures::future;
+use stremio_derive::Model;
+
+use crate::{
+    models::{
+        cast_session::{CastSession, Selected},
+        common::Loadable,
+        ctx::Ctx,
+    },
+    runtime::{
+        msg::{Action, ActionCastSession, ActionLoad},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addons_history::AddonsHistoryBucket,
+        events::DismissedEventsBucket,
+        intros::IntrosBucket,
+        language_preferences::LanguagePreferencesBucket,
+        library::{LibraryBucket, LibraryItem, LibraryItemState},
+        notifications::NotificationsBucket,
+        play_queue::PlayQueueBucket,
+        profile::Profile,
+        search_history::SearchHistoryBucket,
+        streaming_server::CastingStatus,
+        streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS},
+};
+
+static STATUS_TIME: AtomicU64 = AtomicU64::new(0);
+
+#[test]
+fn cast_session_library_item_progress() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        cast_session: CastSession,
+    }
+
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, method, .. }
+                if method == "POST" && url == "http://127.0.0.1:11470/casting/device/player" =>
+            {
+                future::ok(Box::new(serde_json::Value::Null) as Box<dyn Any + Send>).boxed_env()
+            }
+            Request { url, method, .. }
+                if method == "GET" && url == "http://127.0.0.1:11470/casting/device/player" =>
+            {
+                future::ok(Box::new(CastingStatus {
+                    time: STATUS_TIME.load(Ordering::SeqCst),
+                    duration: 100_000,
+                    paused: false,
+                    volume: 1.0,
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            video_id: Some("tt1:1:1".to_owned()),
+            time_offset: 10_000,
+            duration: 100_000,
+            ..Default::default()
+        },
+        behavior_hints: Default::default(),
+    };
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    STATUS_TIME.store(12_000, Ordering::SeqCst);
+
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::new(
+                Profile::default(),
+                LibraryBucket::new(None, vec![library_item]),
+                StreamsBucket::default(),
+                NotificationsBucket::new::<TestEnv>(None, vec![]),
+                SearchHistoryBucket::default(),
+                DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
+            ),
+            cast_session: CastSession::default(),
+        },
+        vec![],
+        1000,
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::CastSession(Selected {
+                device: "device".to_owned(),
+                source: "http://127.0.0.1:11470/video.mp4".parse().unwrap(),
+                library_item_id: Some("tt1".to_owned()),
+                video_id: Some("tt1:1:1".to_owned()),
+            })),
+        })
+    });
+    {
+        let requests = REQUESTS.read().unwrap();
+        assert_eq!(requests.len(), 2, "Two requests have been sent");
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&requests[0].body).unwrap(),
+            serde_json::json!({
+                "source": "http://127.0.0.1:11470/video.mp4",
+                "time": 10_000,
+            }),
+            "Playback is resumed from the library item time offset"
+        );
+        assert_eq!(requests[1].method, "GET");
+    }
+    {
+        let model = runtime.model().unwrap();
+        assert!(
+            matches!(
+                model.cast_session.status,
+                Some(Loadable::Ready(CastingStatus { time: 12_000, .. }))
+            ),
+            "Status is ready"
+        );
+        assert_eq!(
+            model
+                .cast_session
+                .library_item
+                .as_ref()
+                .unwrap()
+                .state
+                .time_offset,
+            12_000,
+            "Library item time offset is updated"
+        );
+    }
+
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 5).unwrap();
+    STATUS_TIME.store(40_000, Ordering::SeqCst);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::CastSession(ActionCastSession::GetStatus),
+        })
+    });
+    let model = runtime.model().unwrap();
+    let state = &model.cast_session.library_item.as_ref().unwrap().state;
+    assert_eq!(state.time_offset, 40_000);
+    assert_eq!(
+        state.time_watched,
+        Duration::seconds(5).num_milliseconds() as u64,
+        "Watched time is limited by the time elapsed between the polls"
+    );
+}
@@ -0,0 +1 @@
+mod library_item_progress;
@@ -4,13 +4,18 @@ use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionLoad};
 use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, RuntimeEvent, TryEnvFuture};
 use crate::types::addon::{ExtraValue, ResourcePath, ResourceRequest, ResourceResponse};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::events::DismissedEventsBucket;
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::play_queue::PlayQueueBucket;
 use crate::types::profile::Profile;
 use crate::types::resource::MetaItemPreview;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, EVENTS, FETCH_HANDLER, REQUESTS, STATES,
 };
@@ -53,6 +58,11 @@ fn default_catalog() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        LanguagePreferencesBucket::default(),
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
     );
     let (discover, effects) = CatalogWithFilters::<MetaItemPreview>::new(&ctx.profile);
     let (runtime, rx) = Runtime::<TestEnv, _>::new(
@@ -154,6 +164,11 @@ fn search_catalog() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        LanguagePreferencesBucket::default(),
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
     );
     let (discover, effects) = CatalogWithFilters::<MetaItemPreview>::new(&ctx.profile);
     let (runtime, rx) = Runtime::<TestEnv, _>::new(
@@ -0,0 +1,264 @@
+use std::any::Any;
+
+use futures::future;
+use stremio_derive::Model;
+use url::Url;
+
+use crate::{
+    models::{
+        catalogs_with_extra::{CatalogsWithExtra, Selected},
+        ctx::Ctx,
+    },
+    runtime::{
+        msg::{Action, ActionCtx, ActionLoad},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{
+            Descriptor, ExtraProp, ExtraValue, Manifest, ManifestCatalog, ManifestExtra,
+            ManifestResource,
+        },
+        api::{APIResult, SuccessResponse},
+        profile::{Auth, AuthKey, BoardCatalog, BoardLayout, Profile, Settings, User},
+        True,
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS},
+};
+
+const TRANSPORT_URL: &str = "https://addon.com/manifest.json";
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request {
+        Request { url, .. } if url == "https://api.strem.io/api/addonCollectionSet" => future::ok(
+            Box::new(APIResult::Ok(SuccessResponse { success: True {} })) as Box<dyn Any + Send>,
+        )
+        .boxed_env(),
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn board_catalog(id: &str) -> BoardCatalog {
+    BoardCatalog {
+        transport_url: Url::parse(TRANSPORT_URL).unwrap(),
+        r#type: "movie".to_owned(),
+        id: id.to_owned(),
+    }
+}
+
+fn catalog_ids(catalogs_with_extra: &CatalogsWithExtra) -> Vec<&str> {
+    catalogs_with_extra
+        .catalogs
+        .iter()
+        .map(|catalog| catalog[0].request.path.id.as_str())
+        .collect()
+}
+
+#[test]
+fn catalogs_with_extra_board_layout() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        board: CatalogsWithExtra,
+        search: CatalogsWithExtra,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "addon".to_owned(),
+            types: vec!["movie".to_owned()],
+            resources: vec![ManifestResource::Short("catalog".to_owned())],
+            catalogs: ["top", "new", "popular"]
+                .iter()
+                .map(|id| ManifestCatalog {
+                    id: id.to_string(),
+                    r#type: "movie".to_owned(),
+                    name: None,
+                    extra: ManifestExtra::Short {
+                        required: vec![],
+                        supported: vec!["search".to_owned()],
+                    },
+                })
+                .collect(),
+            ..Default::default()
+        },
+        transport_url: Url::parse(TRANSPORT_URL).unwrap(),
+        flags: Default::default(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User::default(),
+                    }),
+                    addons: vec![addon],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            board: Default::default(),
+            search: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::Board),
+            action: Action::Load(ActionLoad::CatalogsWithExtra(Selected {
+                r#type: None,
+                extra: vec![],
+            })),
+        });
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::Search),
+            action: Action::Load(ActionLoad::CatalogsWithExtra(Selected {
+                r#type: None,
+                extra: vec![ExtraValue {
+                    name: "search".to_owned(),
+                    value: "query".to_owned(),
+                }],
+            })),
+        });
+    });
+    assert_eq!(
+        catalog_ids(&runtime.model().unwrap().board),
+        vec!["top", "new", "popular"],
+        "The catalogs are in the order of the installed addons by default"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::HideBoardCatalog(board_catalog("new"))),
+        });
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::MoveBoardCatalog {
+                catalog: board_catalog("popular"),
+                to: 0,
+            }),
+        });
+    });
+    let model = runtime.model().unwrap();
+    assert_eq!(
+        model.ctx.profile.board_layout,
+        BoardLayout {
+            order: vec![
+                board_catalog("popular"),
+                board_catalog("new"),
+                board_catalog("top"),
+            ],
+            hidden: vec![board_catalog("new")],
+        }
+    );
+    assert_eq!(catalog_ids(&model.board), vec!["popular", "top"]);
+    assert_eq!(
+        catalog_ids(&model.search),
+        vec!["top", "new", "popular"],
+        "The board layout is not applied to the search results"
+    );
+    let requests = REQUESTS.read().unwrap();
+    assert_eq!(requests.len(), 2, "Each layout change is pushed to the API");
+    assert_eq!(
+        requests[1].url,
+        "https://api.strem.io/api/addonCollectionSet"
+    );
+    assert!(requests[1].body.contains(&format!(
+        "\"boardLayout\":{}",
+        serde_json::to_string(&model.ctx.profile.board_layout).unwrap()
+    )));
+}
+
+#[test]
+fn catalogs_with_extra_language() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        board: CatalogsWithExtra,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let catalog = |id: &str, options: &[&str]| ManifestCatalog {
+        id: id.to_owned(),
+        r#type: "movie".to_owned(),
+        name: None,
+        extra: ManifestExtra::Full {
+            props: vec![ExtraProp {
+                name: "language".to_owned(),
+                is_required: false,
+                options: options.iter().map(|option| option.to_string()).collect(),
+                options_limit: Default::default(),
+            }],
+        },
+    };
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "addon".to_owned(),
+            types: vec!["movie".to_owned()],
+            resources: vec![ManifestResource::Short("catalog".to_owned())],
+            catalogs: vec![
+                catalog("any", &[]),
+                catalog("iso639_1", &["en", "bg"]),
+                catalog("iso639_2", &["eng", "bul"]),
+            ],
+            ..Default::default()
+        },
+        transport_url: Url::parse(TRANSPORT_URL).unwrap(),
+        flags: Default::default(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    settings: Settings {
+                        interface_language: "bul".to_owned(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            board: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::CatalogsWithExtra(Selected {
+                r#type: None,
+                extra: vec![],
+            })),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .board
+            .catalogs
+            .iter()
+            .map(|catalog| {
+                let path = &catalog[0].request.path;
+                (
+                    path.id.to_owned(),
+                    path.get_extra_first_value("language").cloned(),
+                )
+            })
+            .collect::<Vec<_>>(),
+        vec![
+            ("any".to_owned(), Some("bul".to_owned())),
+            ("iso639_1".to_owned(), None),
+            ("iso639_2".to_owned(), Some("bul".to_owned())),
+        ],
+        "The language is passed only if it's one of the options of the extra"
+    );
+}
@@ -2,14 +2,19 @@ use crate::constants::{LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY};
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::api::{APIResult, SuccessResponse};
 use crate::types::events::DismissedEventsBucket;
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
 use crate::types::notifications::NotificationsBucket;
+use crate::types::play_queue::PlayQueueBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::resource::{MetaItemBehaviorHints, MetaItemPreview, PosterShape};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS, STORAGE,
@@ -109,6 +114,11 @@ fn actionctx_addtolibrary() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -246,6 +256,11 @@ fn actionctx_addtolibrary_already_added() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -1,4 +1,6 @@
 use crate::types::events::DismissedEventsBucket;
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::notifications::NotificationsBucket;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
@@ -10,9 +12,12 @@ use crate::{
         Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
     },
     types::{
+        addons_history::AddonsHistoryBucket,
         api::{APIResult, AuthRequest, AuthResponse, CollectionResponse, LibraryItemsResponse},
         library::LibraryBucket,
+        play_queue::PlayQueueBucket,
         profile::{Auth, AuthKey, GDPRConsent, Profile, User},
+        watch_history::WatchHistoryBucket,
     },
     unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE},
 };
@@ -85,6 +90,7 @@ fn actionctx_authenticate_login() {
                     CollectionResponse {
                         addons: vec![],
                         last_modified: TestEnv::now(),
+                        board_layout: None,
                     },
                 )) as Box<dyn Any + Send>).boxed_env()
             }
@@ -110,6 +116,11 @@ fn actionctx_authenticate_login() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        LanguagePreferencesBucket::default(),
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
@@ -228,6 +239,7 @@ fn actionctx_authenticate_login_with_token() {
                     CollectionResponse {
                         addons: vec![],
                         last_modified: TestEnv::now(),
+                        board_layout: None,
                     },)
                 ) as Box<dyn Any + Send>).boxed_env()
             }
@@ -251,6 +263,11 @@ fn actionctx_authenticate_login_with_token() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        LanguagePreferencesBucket::default(),
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
@@ -368,6 +385,7 @@ fn actionctx_authenticate_facebook() {
                     CollectionResponse {
                         addons: vec![],
                         last_modified: TestEnv::now(),
+                        board_layout: None,
                     },)
                 ) as Box<dyn Any + Send>).boxed_env()
             }
@@ -391,6 +409,11 @@ fn actionctx_authenticate_facebook() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        LanguagePreferencesBucket::default(),
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
@@ -507,6 +530,7 @@ fn actionctx_authenticate_register() {
                     CollectionResponse {
                         addons: vec![],
                         last_modified: TestEnv::now(),
+                        board_layout: None,
                     },
                 )) as Box<dyn Any + Send>).boxed_env()
             }
@@ -531,6 +555,11 @@ fn actionctx_authenticate_register() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        LanguagePreferencesBucket::default(),
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
     );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, vec![], 1000);
     TestEnv::run(|| {
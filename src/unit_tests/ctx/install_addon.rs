@@ -3,13 +3,18 @@ use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
 use crate::types::addon::{Descriptor, Manifest};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::api::{APIResult, SuccessResponse};
 use crate::types::events::DismissedEventsBucket;
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::play_queue::PlayQueueBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
@@ -42,6 +47,7 @@ fn actionctx_installaddon_install() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: Default::default(),
@@ -59,6 +65,11 @@ fn actionctx_installaddon_install() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -104,7 +115,7 @@ fn actionctx_installaddon_install_with_user() {
                 url, method, body, ..
             } if url == "https://api.strem.io/api/addonCollectionSet"
                 && method == "POST"
-                && body == "{\"type\":\"AddonCollectionSet\",\"authKey\":\"auth_key\",\"addons\":[{\"manifest\":{\"id\":\"id\",\"version\":\"0.0.1\",\"name\":\"name\",\"contactEmail\":null,\"description\":null,\"logo\":null,\"background\":null,\"types\":[],\"resources\":[],\"idPrefixes\":null,\"catalogs\":[],\"addonCatalogs\":[],\"behaviorHints\":{\"adult\":false,\"p2p\":false,\"configurable\":false,\"configurationRequired\":false}},\"transportUrl\":\"https://transport_url/\",\"flags\":{\"official\":false,\"protected\":false}}]}" =>
+                && body == "{\"type\":\"AddonCollectionSet\",\"authKey\":\"auth_key\",\"addons\":[{\"manifest\":{\"id\":\"id\",\"version\":\"0.0.1\",\"name\":\"name\",\"contactEmail\":null,\"description\":null,\"logo\":null,\"background\":null,\"types\":[],\"resources\":[],\"idPrefixes\":null,\"catalogs\":[],\"addonCatalogs\":[],\"behaviorHints\":{\"adult\":false,\"p2p\":false,\"configurable\":false,\"configurationRequired\":false}},\"transportUrl\":\"https://transport_url/\",\"flags\":{\"official\":false,\"protected\":false}}],\"boardLayout\":{\"order\":[],\"hidden\":[]}}" =>
             {
                 future::ok(Box::new(APIResult::Ok( SuccessResponse { success: True {} },
                 )) as Box<dyn Any + Send>).boxed_env()
@@ -127,6 +138,7 @@ fn actionctx_installaddon_install_with_user() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: Default::default(),
@@ -164,6 +176,11 @@ fn actionctx_installaddon_install_with_user() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -200,7 +217,7 @@ fn actionctx_installaddon_install_with_user() {
         Request {
             url: "https://api.strem.io/api/addonCollectionSet".to_owned(),
             method: "POST".to_owned(),
-            body: "{\"type\":\"AddonCollectionSet\",\"authKey\":\"auth_key\",\"addons\":[{\"manifest\":{\"id\":\"id\",\"version\":\"0.0.1\",\"name\":\"name\",\"contactEmail\":null,\"description\":null,\"logo\":null,\"background\":null,\"types\":[],\"resources\":[],\"idPrefixes\":null,\"catalogs\":[],\"addonCatalogs\":[],\"behaviorHints\":{\"adult\":false,\"p2p\":false,\"configurable\":false,\"configurationRequired\":false}},\"transportUrl\":\"https://transport_url/\",\"flags\":{\"official\":false,\"protected\":false}}]}"
+            body: "{\"type\":\"AddonCollectionSet\",\"authKey\":\"auth_key\",\"addons\":[{\"manifest\":{\"id\":\"id\",\"version\":\"0.0.1\",\"name\":\"name\",\"contactEmail\":null,\"description\":null,\"logo\":null,\"background\":null,\"types\":[],\"resources\":[],\"idPrefixes\":null,\"catalogs\":[],\"addonCatalogs\":[],\"behaviorHints\":{\"adult\":false,\"p2p\":false,\"configurable\":false,\"configurationRequired\":false}},\"transportUrl\":\"https://transport_url/\",\"flags\":{\"official\":false,\"protected\":false}}],\"boardLayout\":{\"order\":[],\"hidden\":[]}}"
                 .to_owned(),
             ..Default::default()
         },
@@ -230,6 +247,7 @@ fn actionctx_installaddon_update() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url1").unwrap(),
         flags: Default::default(),
@@ -249,6 +267,7 @@ fn actionctx_installaddon_update() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url2").unwrap(),
         flags: Default::default(),
@@ -274,6 +293,7 @@ fn actionctx_installaddon_update() {
                                 catalogs: vec![],
                                 addon_catalogs: vec![],
                                 behavior_hints: Default::default(),
+                                config: vec![],
                             },
                             transport_url: Url::parse("https://transport_url1").unwrap(),
                             flags: Default::default(),
@@ -287,6 +307,11 @@ fn actionctx_installaddon_update() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -342,6 +367,7 @@ fn actionctx_installaddon_already_installed() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: Default::default(),
@@ -364,6 +390,11 @@ fn actionctx_installaddon_already_installed() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -3,15 +3,21 @@ mod authenticate;
 mod install_addon;
 mod logout;
 mod update_events;
+mod update_language_preferences;
 mod notifications {
+    mod notification_rules;
+    mod pull_notifications_batches;
+    mod season_added;
     mod update_notifications;
 }
 mod pull_addons_from_api;
 mod push_addons_to_api;
 mod remove_from_library;
+mod restore_addons_snapshot;
 mod rewind_library_item;
 mod sync_library_with_api;
 mod uninstall_addon;
 mod update_search_history;
 mod update_settings;
+mod update_watch_history;
 mod upgrade_addon;
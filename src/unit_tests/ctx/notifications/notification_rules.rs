@@ -0,0 +1,310 @@
+use std::{any::Any, collections::HashSet};
+
+use chrono::{TimeZone, Utc};
+use futures::future;
+use semver::Version;
+use stremio_derive::Model;
+use stremio_watched_bitfield::WatchedBitField;
+use url::Url;
+
+use crate::{
+    constants::{CATALOG_RESOURCE_NAME, LAST_VIDEOS_IDS_EXTRA_PROP},
+    models::ctx::Ctx,
+    runtime::{
+        msg::{Action, ActionCtx},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{Descriptor, Manifest, ManifestCatalog, ManifestExtra, ResourceResponse},
+        library::{LibraryBucket, LibraryItem, LibraryItemState},
+        notifications::{
+            NotificationItem, NotificationKind, NotificationsBucket, NotificationsExportFormat,
+        },
+        profile::{NotificationRules, Profile, QuietHours, Settings},
+        resource::{MetaItem, MetaItemPreview, SeriesInfo, Video},
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW},
+};
+
+#[derive(Model, Clone, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+}
+
+fn videos() -> Vec<Video> {
+    [
+        (4, 2019, 12, 20),
+        (5, 2020, 1, 1),
+        (6, 2020, 1, 5),
+        (7, 2020, 1, 15),
+    ]
+    .into_iter()
+    .map(|(episode, year, month, day)| Video {
+        id: format!("tt1:1:{episode}"),
+        released: Some(Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()),
+        series_info: Some(SeriesInfo { season: 1, episode }),
+        ..Default::default()
+    })
+    .collect()
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request.url.as_str() {
+        "https://addon.com/catalog/series/lastVideosIds/lastVideosIds=tt1.json" => {
+            future::ok(Box::new(ResourceResponse::MetasDetailed {
+                metas_detailed: vec![MetaItem {
+                    preview: MetaItemPreview {
+                        id: "tt1".to_owned(),
+                        r#type: "series".to_owned(),
+                        ..Default::default()
+                    },
+                    videos: videos(),
+                }],
+            }) as Box<dyn Any + Send>)
+            .boxed_env()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn addon() -> Descriptor {
+    Descriptor {
+        manifest: Manifest {
+            id: "addon".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "Addon".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["series".to_owned()],
+            resources: vec![CATALOG_RESOURCE_NAME.into()],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![ManifestCatalog {
+                id: "lastVideosIds".to_owned(),
+                r#type: "series".to_owned(),
+                name: None,
+                extra: ManifestExtra::Full {
+                    props: vec![LAST_VIDEOS_IDS_EXTRA_PROP.to_owned()],
+                },
+            }],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+            config: vec![],
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    }
+}
+
+/// Pulls the notifications of a series which episode 5 was last watched
+/// and returns the ids of the videos which are notified about.
+fn pull_notifications(
+    rules: NotificationRules,
+    watched: &[bool],
+    notification_items: Vec<NotificationItem>,
+) -> HashSet<String> {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 1, 20, 12, 0, 0).unwrap();
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        name: "Series".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        removed: false,
+        temp: false,
+        ctime: None,
+        mtime: Utc.with_ymd_and_hms(2020, 1, 1, 20, 0, 0).unwrap(),
+        state: LibraryItemState {
+            last_watched: Some(Utc.with_ymd_and_hms(2020, 1, 1, 20, 0, 0).unwrap()),
+            watched: Some(
+                WatchedBitField::construct_from_array(
+                    watched.to_vec(),
+                    videos().into_iter().map(|video| video.id).collect(),
+                )
+                .into(),
+            ),
+            ..Default::default()
+        },
+        behavior_hints: Default::default(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon()],
+                    settings: Settings {
+                        notification_rules: rules,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![library_item]),
+                notifications: NotificationsBucket {
+                    items: NotificationsBucket::new::<TestEnv>(None, notification_items).items,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PullNotifications),
+        })
+    });
+    let model = runtime.model().unwrap();
+    model
+        .ctx
+        .notifications
+        .items
+        .values()
+        .flat_map(|meta_notifs| meta_notifs.keys().cloned())
+        .collect()
+}
+
+fn ids(ids: &[&str]) -> HashSet<String> {
+    ids.iter().map(|id| id.to_string()).collect()
+}
+
+#[test]
+fn notification_rules() {
+    assert_eq!(
+        pull_notifications(NotificationRules::default(), &[false, true], vec![]),
+        ids(&["tt1:1:6", "tt1:1:7"]),
+        "Without rules all the videos released after the last watched are notified about"
+    );
+    assert_eq!(
+        pull_notifications(
+            NotificationRules {
+                max_video_age_days: Some(10),
+                ..Default::default()
+            },
+            &[false, true],
+            vec![],
+        ),
+        ids(&["tt1:1:7"]),
+        "Videos older than the max age are skipped"
+    );
+    assert_eq!(
+        pull_notifications(
+            NotificationRules {
+                watched_within_days: Some(7),
+                ..Default::default()
+            },
+            &[false, true],
+            vec![],
+        ),
+        ids(&[]),
+        "Series which are not watched recently are skipped"
+    );
+    assert_eq!(
+        pull_notifications(
+            NotificationRules {
+                only_caught_up: true,
+                ..Default::default()
+            },
+            &[false, true],
+            vec![],
+        ),
+        ids(&[]),
+        "Series with older videos not watched are skipped"
+    );
+    assert_eq!(
+        pull_notifications(
+            NotificationRules {
+                only_caught_up: true,
+                ..Default::default()
+            },
+            &[true, true],
+            vec![],
+        ),
+        ids(&["tt1:1:6", "tt1:1:7"]),
+        "Caught up series are notified about"
+    );
+    assert_eq!(
+        pull_notifications(
+            NotificationRules {
+                quiet_hours: Some(QuietHours { start: 22, end: 13 }),
+                ..Default::default()
+            },
+            &[false, true],
+            vec![NotificationItem {
+                meta_id: "tt1".to_owned(),
+                video_id: "tt1:1:6".to_owned(),
+                video_released: Utc.with_ymd_and_hms(2020, 1, 5, 0, 0, 0).unwrap(),
+                kind: NotificationKind::Video,
+            }],
+        ),
+        ids(&["tt1:1:6"]),
+        "Only the existing notifications are kept during the quiet hours"
+    );
+}
+
+#[test]
+fn export_notifications() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let notifications = NotificationsBucket::new::<TestEnv>(
+        None,
+        vec![
+            NotificationItem {
+                meta_id: "tt1".to_owned(),
+                video_id: "tt1:1:7".to_owned(),
+                video_released: Utc.with_ymd_and_hms(2020, 1, 15, 0, 0, 0).unwrap(),
+                kind: NotificationKind::Video,
+            },
+            NotificationItem {
+                meta_id: "tt2".to_owned(),
+                video_id: "tt2:2:1".to_owned(),
+                video_released: Utc.with_ymd_and_hms(2020, 1, 5, 0, 0, 0).unwrap(),
+                kind: NotificationKind::Video,
+            },
+        ],
+    );
+    let library = LibraryBucket::new(
+        None,
+        vec![LibraryItem {
+            id: "tt1".to_owned(),
+            name: "Name; with, special chars".to_owned(),
+            r#type: "series".to_owned(),
+            poster: None,
+            poster_shape: Default::default(),
+            removed: false,
+            temp: false,
+            ctime: None,
+            mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+            state: Default::default(),
+            behavior_hints: Default::default(),
+        }],
+    );
+    assert_eq!(
+        notifications.export(&library, NotificationsExportFormat::Ics),
+        "BEGIN:VCALENDAR\r\n\
+         VERSION:2.0\r\n\
+         PRODID:-//Stremio//Notifications//EN\r\n\
+         BEGIN:VEVENT\r\n\
+         UID:tt2/tt2:2:1@stremio\r\n\
+         DTSTAMP:20200105T000000Z\r\n\
+         DTSTART:20200105T000000Z\r\n\
+         SUMMARY:tt2 (tt2:2:1)\r\n\
+         END:VEVENT\r\n\
+         BEGIN:VEVENT\r\n\
+         UID:tt1/tt1:1:7@stremio\r\n\
+         DTSTAMP:20200115T000000Z\r\n\
+         DTSTART:20200115T000000Z\r\n\
+         SUMMARY:Name\\; with\\, special chars (tt1:1:7)\r\n\
+         END:VEVENT\r\n\
+         END:VCALENDAR\r\n",
+        "Notifications are exported ordered by release date"
+    );
+    assert_eq!(
+        notifications.export(&library, NotificationsExportFormat::Json),
+        r#"[{"metaId":"tt2","videoId":"tt2:2:1","name":"tt2","videoReleased":"2020-01-05T00:00:00Z"},{"metaId":"tt1","videoId":"tt1:1:7","name":"Name; with, special chars","videoReleased":"2020-01-15T00:00:00Z"}]"#
+    );
+}
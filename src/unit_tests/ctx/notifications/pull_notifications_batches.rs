@@ -0,0 +1,277 @@
+use std::{any::Any, collections::HashMap};
+
+use chrono::{Duration, TimeZone, Utc};
+use futures::future;
+use semver::Version;
+use stremio_derive::Model;
+use url::Url;
+
+use crate::{
+    constants::{CATALOG_RESOURCE_NAME, LAST_VIDEOS_IDS_EXTRA_PROP},
+    models::ctx::Ctx,
+    runtime::{
+        msg::{Action, ActionCtx},
+        Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{
+            Descriptor, ExtraProp, Manifest, ManifestCatalog, ManifestExtra, OptionsLimit,
+            ResourceResponse,
+        },
+        library::{LibraryBucket, LibraryItem, LibraryItemState},
+        notifications::{KnownSeasons, NotificationsBucket},
+        profile::Profile,
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS},
+};
+
+#[derive(Model, Clone, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    if request.url.contains("/catalog/series/lastVideosIds/") {
+        return future::ok(Box::new(ResourceResponse::MetasDetailed {
+            metas_detailed: vec![],
+        }) as Box<dyn Any + Send>)
+        .boxed_env();
+    }
+    default_fetch_handler(request)
+}
+
+fn addon(id: &str) -> Descriptor {
+    Descriptor {
+        manifest: Manifest {
+            id: id.to_owned(),
+            version: Version::new(0, 0, 1),
+            name: id.to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["series".to_owned()],
+            resources: vec![CATALOG_RESOURCE_NAME.into()],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![ManifestCatalog {
+                id: "lastVideosIds".to_owned(),
+                r#type: "series".to_owned(),
+                name: None,
+                extra: ManifestExtra::Full {
+                    props: vec![ExtraProp {
+                        options_limit: OptionsLimit(100),
+                        ..LAST_VIDEOS_IDS_EXTRA_PROP.to_owned()
+                    }],
+                },
+            }],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+            config: vec![],
+        },
+        transport_url: Url::parse(&format!("https://{id}.com/manifest.json")).unwrap(),
+        flags: Default::default(),
+    }
+}
+
+/// The series `tt0` to `tt{count - 1}`, the ones with a greater number watched more recently
+fn library_items(count: i64) -> Vec<LibraryItem> {
+    (0..count)
+        .map(|index| LibraryItem {
+            id: format!("tt{index}"),
+            name: format!("Series {index}"),
+            r#type: "series".to_owned(),
+            poster: None,
+            poster_shape: Default::default(),
+            removed: false,
+            temp: false,
+            ctime: None,
+            mtime: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            state: LibraryItemState {
+                last_watched: Some(
+                    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::days(index),
+                ),
+                ..Default::default()
+            },
+            behavior_hints: Default::default(),
+        })
+        .collect()
+}
+
+#[test]
+fn pull_notifications_batches() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon("addon_1"), addon("addon_2")],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, library_items(31)),
+                notifications: NotificationsBucket {
+                    // checked an hour ago
+                    last_checked: HashMap::from([
+                        ("tt0".to_owned(), TestEnv::now() - Duration::hours(1)),
+                        ("tt100".to_owned(), TestEnv::now() - Duration::hours(1)),
+                    ]),
+                    known_seasons: HashMap::from([("tt100".to_owned(), KnownSeasons::default())]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    let pull_notifications = || {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::PullNotifications),
+            })
+        })
+    };
+    let requested_ids = |index: usize| {
+        let requests = REQUESTS.read().unwrap();
+        let url = Url::parse(&requests[index].url).unwrap();
+        let ids = url
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .and_then(|segment| segment.strip_prefix("lastVideosIds="))
+            .and_then(|ids| ids.strip_suffix(".json"))
+            .unwrap()
+            .replace("%2C", ",");
+        (
+            url.host_str().unwrap().to_owned(),
+            ids.split(',').map(ToOwned::to_owned).collect::<Vec<_>>(),
+        )
+    };
+
+    pull_notifications();
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        4,
+        "The ids are requested in two batches from each addon"
+    );
+    let (first_host, first_ids) = requested_ids(0);
+    let (second_host, second_ids) = requested_ids(1);
+    assert_ne!(
+        first_host, second_host,
+        "The first batches of all addons are requested first"
+    );
+    assert_eq!(first_ids.len(), 25);
+    assert_eq!(first_ids, second_ids);
+    assert!(
+        first_ids.contains(&"tt30".to_owned()) && !first_ids.contains(&"tt5".to_owned()),
+        "The most recently watched series are requested first"
+    );
+    assert!(
+        (0..4).all(|index| !requested_ids(index).1.contains(&"tt0".to_owned())),
+        "Series checked within the TTL are not requested"
+    );
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .notifications
+            .last_checked
+            .len(),
+        31,
+        "The requested series are marked as checked"
+    );
+    assert!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .notifications
+            .known_seasons
+            .is_empty(),
+        "The series which are not in the library are removed"
+    );
+
+    pull_notifications();
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        4,
+        "Nothing is requested while all the checks are fresh"
+    );
+
+    *NOW.write().unwrap() = TestEnv::now() + Duration::hours(6);
+    pull_notifications();
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        8,
+        "Series are requested again once the TTL has passed"
+    );
+    assert!(
+        (4..8).any(|index| requested_ids(index).1.contains(&"tt0".to_owned())),
+        "All series are requested once the TTL has passed"
+    );
+}
+
+#[test]
+fn pull_notifications_limit() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon("addon_1")],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, library_items(130)),
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    let pull_notifications = || {
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::PullNotifications),
+            })
+        })
+    };
+    let checked = || {
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .notifications
+            .last_checked
+            .len()
+    };
+
+    pull_notifications();
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        4,
+        "The most recently watched series up to the limit are requested"
+    );
+    assert_eq!(checked(), 100);
+
+    pull_notifications();
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        4,
+        "Nothing is requested before the pull interval has passed"
+    );
+
+    *NOW.write().unwrap() = TestEnv::now() + Duration::minutes(10);
+    pull_notifications();
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        6,
+        "The rest of the series are requested on the next pull"
+    );
+    assert_eq!(checked(), 130);
+}
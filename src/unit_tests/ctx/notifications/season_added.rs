@@ -0,0 +1,269 @@
+use std::{any::Any, collections::HashMap};
+
+use chrono::{TimeZone, Utc};
+use futures::future;
+use semver::Version;
+use stremio_derive::Model;
+use url::Url;
+
+use crate::{
+    constants::{CATALOG_RESOURCE_NAME, LAST_VIDEOS_IDS_EXTRA_PROP},
+    models::{continue_watching_preview::ContinueWatchingPreview, ctx::Ctx},
+    runtime::{
+        msg::{Action, ActionCtx},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{Descriptor, Manifest, ManifestCatalog, ManifestExtra, ResourceResponse},
+        library::{LibraryBucket, LibraryItem, LibraryItemState},
+        notifications::{KnownSeasons, NotificationKind, NotificationsBucket},
+        profile::{NotificationRules, Profile, QuietHours, Settings},
+        resource::{MetaItem, MetaItemPreview, SeriesInfo, Video},
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW},
+};
+
+#[derive(Model, Clone, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    continue_watching_preview: ContinueWatchingPreview,
+}
+
+fn video(season: u32, episode: u32, released: (i32, u32, u32)) -> Video {
+    Video {
+        id: format!("tt1:{season}:{episode}"),
+        released: Some(
+            Utc.with_ymd_and_hms(released.0, released.1, released.2, 0, 0, 0)
+                .unwrap(),
+        ),
+        series_info: Some(SeriesInfo { season, episode }),
+        ..Default::default()
+    }
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request.url.as_str() {
+        "https://addon.com/catalog/series/lastVideosIds/lastVideosIds=tt1.json" => {
+            future::ok(Box::new(ResourceResponse::MetasDetailed {
+                metas_detailed: vec![MetaItem {
+                    preview: MetaItemPreview {
+                        id: "tt1".to_owned(),
+                        r#type: "series".to_owned(),
+                        ..Default::default()
+                    },
+                    videos: vec![
+                        video(1, 1, (2020, 1, 1)),
+                        video(1, 2, (2020, 1, 8)),
+                        video(0, 1, (2020, 3, 1)),
+                        video(2, 1, (2020, 3, 5)),
+                        video(2, 2, (2020, 3, 12)),
+                        // announced but not released yet
+                        video(3, 1, (2021, 1, 1)),
+                    ],
+                }],
+            }) as Box<dyn Any + Send>)
+            .boxed_env()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+/// Pulls the notifications of a series which season 1 was watched
+/// at each of the given hours of a day and returns the model.
+fn pull_notifications(
+    known_seasons: HashMap<String, KnownSeasons>,
+    notification_rules: NotificationRules,
+    hours: &[u32],
+) -> TestModel {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "addon".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "Addon".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["series".to_owned()],
+            resources: vec![CATALOG_RESOURCE_NAME.into()],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![ManifestCatalog {
+                id: "lastVideosIds".to_owned(),
+                r#type: "series".to_owned(),
+                name: None,
+                extra: ManifestExtra::Full {
+                    props: vec![LAST_VIDEOS_IDS_EXTRA_PROP.to_owned()],
+                },
+            }],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+            config: vec![],
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        name: "Series".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        removed: false,
+        temp: false,
+        ctime: None,
+        mtime: Utc.with_ymd_and_hms(2020, 1, 10, 0, 0, 0).unwrap(),
+        state: LibraryItemState {
+            last_watched: Some(Utc.with_ymd_and_hms(2020, 1, 10, 0, 0, 0).unwrap()),
+            ..Default::default()
+        },
+        behavior_hints: Default::default(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    settings: Settings {
+                        notification_rules,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![library_item]),
+                notifications: NotificationsBucket {
+                    known_seasons,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            continue_watching_preview: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    for hour in hours {
+        *NOW.write().unwrap() = Utc.with_ymd_and_hms(2020, 3, 15, *hour, 0, 0).unwrap();
+        TestEnv::run(|| {
+            runtime.dispatch(RuntimeAction {
+                field: None,
+                action: Action::Ctx(ActionCtx::PullNotifications),
+            })
+        });
+    }
+    let model = runtime.model().unwrap();
+    model.to_owned()
+}
+
+fn season_1_known() -> HashMap<String, KnownSeasons> {
+    HashMap::from([(
+        "tt1".to_owned(),
+        KnownSeasons {
+            seasons: [1].into(),
+            specials: Default::default(),
+        },
+    )])
+}
+
+fn notification_kinds(model: &TestModel) -> HashMap<String, NotificationKind> {
+    model
+        .ctx
+        .notifications
+        .items
+        .get("tt1")
+        .map(|meta_notifs| {
+            meta_notifs
+                .values()
+                .map(|item| (item.video_id.to_owned(), item.kind.to_owned()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[test]
+fn season_added() {
+    let model = pull_notifications(season_1_known(), Default::default(), &[12]);
+    assert_eq!(
+        notification_kinds(&model),
+        HashMap::from([
+            (
+                "tt1:2:1".to_owned(),
+                NotificationKind::SeasonAdded { season: 2 }
+            ),
+            ("tt1:2:2".to_owned(), NotificationKind::Video),
+            ("tt1:0:1".to_owned(), NotificationKind::SpecialAdded),
+        ]),
+        "The first episode of the new season and the new special are notified about"
+    );
+    assert_eq!(
+        model.ctx.notifications.known_seasons.get("tt1"),
+        Some(&KnownSeasons {
+            seasons: [1, 2].into(),
+            specials: ["tt1:0:1".to_owned()].into(),
+        }),
+        "Only the released seasons are known"
+    );
+    assert_eq!(
+        model.continue_watching_preview.items[0].season_added,
+        Some(2),
+        "The new season is surfaced in continue watching"
+    );
+}
+
+#[test]
+fn season_added_first_load() {
+    let model = pull_notifications(HashMap::new(), Default::default(), &[12]);
+    assert_eq!(
+        notification_kinds(&model),
+        HashMap::from([
+            ("tt1:2:1".to_owned(), NotificationKind::Video),
+            ("tt1:2:2".to_owned(), NotificationKind::Video),
+        ]),
+        "Nothing is detected as added the first time the videos are loaded"
+    );
+    assert!(model.ctx.notifications.known_seasons.contains_key("tt1"));
+    assert_eq!(model.continue_watching_preview.items[0].season_added, None);
+}
+
+#[test]
+fn season_added_after_quiet_hours() {
+    let notification_rules = NotificationRules {
+        quiet_hours: Some(QuietHours { start: 12, end: 14 }),
+        ..Default::default()
+    };
+    let model = pull_notifications(season_1_known(), notification_rules.to_owned(), &[12]);
+    assert!(
+        notification_kinds(&model).is_empty(),
+        "Nothing is notified about during the quiet hours"
+    );
+    assert_eq!(
+        model.ctx.notifications.known_seasons.get("tt1"),
+        Some(&KnownSeasons {
+            seasons: [1].into(),
+            specials: Default::default(),
+        }),
+        "The added seasons are not known until they are notified about"
+    );
+    let model = pull_notifications(season_1_known(), notification_rules, &[12, 14]);
+    assert_eq!(
+        notification_kinds(&model),
+        HashMap::from([
+            (
+                "tt1:2:1".to_owned(),
+                NotificationKind::SeasonAdded { season: 2 }
+            ),
+            ("tt1:2:2".to_owned(), NotificationKind::Video),
+            ("tt1:0:1".to_owned(), NotificationKind::SpecialAdded),
+        ]),
+        "The added season is notified about once the quiet hours end"
+    );
+    assert_eq!(
+        model.ctx.notifications.known_seasons.get("tt1"),
+        Some(&KnownSeasons {
+            seasons: [1, 2].into(),
+            specials: ["tt1:0:1".to_owned()].into(),
+        })
+    );
+}
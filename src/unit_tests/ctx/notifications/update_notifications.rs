@@ -30,9 +30,13 @@ use crate::{
             Descriptor, Manifest, ManifestCatalog, ManifestExtra, ResourcePath, ResourceRequest,
             ResourceResponse,
         },
+        addons_history::AddonsHistoryBucket,
         events::DismissedEventsBucket,
+        intros::IntrosBucket,
+        language_preferences::LanguagePreferencesBucket,
         library::{LibraryBucket, LibraryItem, LibraryItemState},
-        notifications::{NotificationItem, NotificationsBucket},
+        notifications::{NotificationItem, NotificationKind, NotificationsBucket},
+        play_queue::PlayQueueBucket,
         profile::Profile,
         resource::{
             MetaItem, MetaItemId, MetaItemPreview, PosterShape, SeriesInfo, Stream, StreamSource,
@@ -40,6 +44,7 @@ use crate::{
         },
         search_history::SearchHistoryBucket,
         streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
     },
     unit_tests::{
         default_fetch_handler, Request, TestEnv, EVENTS, FETCH_HANDLER, NOW, REQUESTS, STATES,
@@ -89,6 +94,7 @@ fn test_pull_notifications_and_play_in_player() {
             }],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://addon_1.com/manifest.json").unwrap(),
         flags: Default::default(),
@@ -242,6 +248,11 @@ fn test_pull_notifications_and_play_in_player() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
             player: Default::default(),
         },
@@ -391,6 +402,11 @@ fn test_pull_notifications_test_cases() {
                     NotificationsBucket::new::<TestEnv>(None, test.notification_items),
                     SearchHistoryBucket::default(),
                     DismissedEventsBucket::default(),
+                    LanguagePreferencesBucket::default(),
+                    IntrosBucket::default(),
+                    PlayQueueBucket::default(),
+                    WatchHistoryBucket::default(),
+                    AddonsHistoryBucket::default(),
                 ),
             },
             vec![],
@@ -492,16 +508,23 @@ fn test_dismiss_notification() {
                             meta_id: "tt1".to_string(),
                             video_id: "tt1:2".to_string(),
                             video_released: Utc.with_ymd_and_hms(2023, 7, 10, 0, 0, 0).unwrap(),
+                            kind: NotificationKind::Video,
                         },
                         NotificationItem {
                             meta_id: "tt2".to_string(),
                             video_id: "tt2:10".to_string(),
                             video_released: Utc.with_ymd_and_hms(2023, 8, 14, 0, 0, 0).unwrap(),
+                            kind: NotificationKind::Video,
                         },
                     ],
                 ),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
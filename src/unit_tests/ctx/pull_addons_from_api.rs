@@ -3,13 +3,18 @@ use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
 use crate::types::addon::{Descriptor, Manifest};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::api::{APIResult, CollectionResponse};
 use crate::types::events::DismissedEventsBucket;
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::play_queue::PlayQueueBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
 };
@@ -47,6 +52,11 @@ fn actionctx_pulladdonsfromapi() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -98,6 +108,7 @@ fn actionctx_pulladdonsfromapi_with_user() {
                 future::ok(Box::new(APIResult::Ok(CollectionResponse {
                         addons: OFFICIAL_ADDONS.to_owned(),
                         last_modified: TestEnv::now(),
+                        board_layout: None,
                     },
                 )) as Box<dyn Any + Send>).boxed_env()
             }
@@ -144,6 +155,7 @@ fn actionctx_pulladdonsfromapi_with_user() {
                             catalogs: vec![],
                             addon_catalogs: vec![],
                             behavior_hints: Default::default(),
+                            config: vec![],
                         },
                         transport_url: Url::parse("https://transport_url").unwrap(),
                         flags: Default::default(),
@@ -155,6 +167,11 @@ fn actionctx_pulladdonsfromapi_with_user() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
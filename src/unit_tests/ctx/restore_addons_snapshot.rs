@@ -0,0 +1,129 @@
+use std::any::Any;
+
+use futures::future;
+use stremio_derive::Model;
+use url::Url;
+
+use crate::constants::{ADDONS_HISTORY_STORAGE_KEY, OFFICIAL_ADDONS};
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::Descriptor;
+use crate::types::addons_history::AddonsHistoryBucket;
+use crate::types::api::{APIResult, CollectionResponse, SuccessResponse};
+use crate::types::profile::{Auth, AuthKey, Profile, User};
+use crate::types::True;
+use crate::unit_tests::{
+    default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
+};
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request {
+        Request { url, .. } if url == "https://api.strem.io/api/addonCollectionGet" => {
+            future::ok(Box::new(APIResult::Ok(CollectionResponse {
+                addons: OFFICIAL_ADDONS.to_owned(),
+                last_modified: TestEnv::now(),
+                board_layout: None,
+            })) as Box<dyn Any + Send>)
+            .boxed_env()
+        }
+        Request { url, .. } if url == "https://api.strem.io/api/addonCollectionSet" => future::ok(
+            Box::new(APIResult::Ok(SuccessResponse { success: True {} })) as Box<dyn Any + Send>,
+        )
+        .boxed_env(),
+        _ => default_fetch_handler(request),
+    }
+}
+
+#[test]
+fn actionctx_restoreaddonssnapshot() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let addon = Descriptor {
+        manifest: Default::default(),
+        transport_url: Url::parse("https://transport_url/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User::default(),
+                    }),
+                    addons: vec![addon.to_owned()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PullAddonsFromAPI),
+        })
+    });
+    {
+        let model = runtime.model().unwrap();
+        let snapshots = &model.ctx.addons_history.snapshots;
+        assert_eq!(snapshots.len(), 2, "Both collections are recorded");
+        assert_eq!(snapshots[0].addons, *OFFICIAL_ADDONS);
+        assert_eq!(snapshots[0].last_modified, Some(TestEnv::now()));
+        assert_eq!(snapshots[1].addons, vec![addon.to_owned()]);
+        assert_eq!(snapshots[1].last_modified, None);
+    }
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RestoreAddonsSnapshot(0)),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.addons,
+        vec![addon.to_owned()],
+        "The snapshot is restored"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().last().unwrap().url,
+        "https://api.strem.io/api/addonCollectionSet",
+        "The restored addons are pushed to the API"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(ADDONS_HISTORY_STORAGE_KEY)
+            .is_some_and(|data| {
+                serde_json::from_str::<AddonsHistoryBucket>(data)
+                    .unwrap()
+                    .snapshots
+                    .len()
+                    == 3
+            }),
+        "The restore is recorded in storage as well"
+    );
+
+    let requests_len = REQUESTS.read().unwrap().len();
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RestoreAddonsSnapshot(42)),
+        })
+    });
+    assert_eq!(runtime.model().unwrap().ctx.profile.addons, vec![addon]);
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        requests_len,
+        "Unknown snapshots are not restored"
+    );
+}
@@ -3,14 +3,19 @@ use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Env, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
 use crate::types::addon::{Descriptor, DescriptorFlags, Manifest};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::api::{APIResult, SuccessResponse};
 use crate::types::events::DismissedEventsBucket;
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::play_queue::PlayQueueBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
 use crate::types::resource::{Stream, StreamBehaviorHints, StreamSource};
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::{StreamsBucket, StreamsItem, StreamsItemKey};
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
@@ -37,6 +42,7 @@ fn create_addon_descriptor(transport_url: &str) -> Descriptor {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse(transport_url).unwrap(),
         flags: Default::default(),
@@ -89,6 +95,7 @@ fn actionctx_uninstalladdon() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: Default::default(),
@@ -111,6 +118,11 @@ fn actionctx_uninstalladdon() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -158,7 +170,7 @@ fn actionctx_uninstalladdon_with_user() {
                 url, method, body, ..
             } if url == "https://api.strem.io/api/addonCollectionSet"
                 && method == "POST"
-                && body == "{\"type\":\"AddonCollectionSet\",\"authKey\":\"auth_key\",\"addons\":[]}" =>
+                && body == "{\"type\":\"AddonCollectionSet\",\"authKey\":\"auth_key\",\"addons\":[],\"boardLayout\":{\"order\":[],\"hidden\":[]}}" =>
             {
                 future::ok(Box::new(APIResult::Ok(
                     SuccessResponse { success: True {} },
@@ -182,6 +194,7 @@ fn actionctx_uninstalladdon_with_user() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: Default::default(),
@@ -224,6 +237,11 @@ fn actionctx_uninstalladdon_with_user() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -262,7 +280,7 @@ fn actionctx_uninstalladdon_with_user() {
         Request {
             url: "https://api.strem.io/api/addonCollectionSet".to_owned(),
             method: "POST".to_owned(),
-            body: "{\"type\":\"AddonCollectionSet\",\"authKey\":\"auth_key\",\"addons\":[]}"
+            body: "{\"type\":\"AddonCollectionSet\",\"authKey\":\"auth_key\",\"addons\":[],\"boardLayout\":{\"order\":[],\"hidden\":[]}}"
                 .to_owned(),
             ..Default::default()
         },
@@ -292,6 +310,7 @@ fn actionctx_uninstalladdon_protected() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: DescriptorFlags {
@@ -317,6 +336,11 @@ fn actionctx_uninstalladdon_protected() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -371,6 +395,7 @@ fn actionctx_uninstalladdon_not_installed() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: Default::default(),
@@ -393,6 +418,11 @@ fn actionctx_uninstalladdon_not_installed() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -477,6 +507,11 @@ fn actionctx_uninstalladdon_streams_bucket() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
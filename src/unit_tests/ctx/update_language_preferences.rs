@@ -0,0 +1,283 @@
+use std::any::Any;
+
+use futures::future;
+use stremio_derive::Model;
+
+use crate::{
+    constants::LANGUAGE_PREFERENCES_STORAGE_KEY,
+    models::{
+        ctx::Ctx,
+        player::{Player, Selected},
+    },
+    runtime::{
+        msg::{Action, ActionLoad, ActionPlayer},
+        Env, EnvError, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest, ResourceResponse},
+        addons_history::AddonsHistoryBucket,
+        events::DismissedEventsBucket,
+        intros::IntrosBucket,
+        language_preferences::{LanguagePreference, LanguagePreferencesBucket},
+        library::LibraryBucket,
+        notifications::NotificationsBucket,
+        play_queue::PlayQueueBucket,
+        profile::Profile,
+        resource::{MetaItem, MetaItemPreview, Stream, StreamSource, Video},
+        search_history::SearchHistoryBucket,
+        streams::{
+            AudioTrack, StreamItemState, StreamsBucket, StreamsItem, StreamsItemKey, SubtitleTrack,
+        },
+        watch_history::WatchHistoryBucket,
+    },
+    unit_tests::{Request, TestEnv, FETCH_HANDLER, STORAGE},
+};
+
+#[derive(Model, Clone, Debug)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    player: Player,
+}
+
+fn create_stream(url: &str) -> Stream {
+    Stream {
+        source: StreamSource::Url {
+            url: url.parse().unwrap(),
+        },
+        name: None,
+        description: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: Default::default(),
+    }
+}
+
+fn create_selected(video_id: &str, stream_url: &str) -> Selected {
+    Selected {
+        stream: create_stream(stream_url),
+        stream_request: Some(ResourceRequest {
+            base: "https://transport_url/manifest.json".parse().unwrap(),
+            path: ResourcePath::without_extra("stream", "series", video_id),
+        }),
+        meta_request: Some(ResourceRequest {
+            base: "https://transport_url/manifest.json".parse().unwrap(),
+            path: ResourcePath::without_extra("meta", "series", "tt123456"),
+        }),
+        subtitles_path: None,
+    }
+}
+
+fn create_ctx(streams: StreamsBucket, language_preferences: LanguagePreferencesBucket) -> Ctx {
+    Ctx::new(
+        Profile::default(),
+        LibraryBucket::default(),
+        streams,
+        NotificationsBucket::new::<TestEnv>(None, vec![]),
+        SearchHistoryBucket::default(),
+        DismissedEventsBucket::default(),
+        language_preferences,
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
+    )
+}
+
+#[test]
+fn learn_language_preference() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: create_ctx(
+                StreamsBucket::default(),
+                LanguagePreferencesBucket::default(),
+            ),
+            player: Player {
+                selected: Some(create_selected("tt123456:1:1", "https://source_url")),
+                ..Default::default()
+            },
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Player(ActionPlayer::StreamStateChanged {
+                state: StreamItemState {
+                    subtitle_track: Some(SubtitleTrack {
+                        id: "2".to_owned(),
+                        embedded: true,
+                        language: Some("eng".to_owned()),
+                    }),
+                    subtitles_origin: None,
+                    subtitle_delay: Some(1500),
+                    audio_track: Some(AudioTrack {
+                        id: "1".to_owned(),
+                        language: Some("jpn".to_owned()),
+                    }),
+                    audio_delay: None,
+                    playback_speed: None,
+                    player_type: None,
+                },
+            }),
+        })
+    });
+    let expected_preference = LanguagePreference {
+        audio_language: Some("jpn".to_owned()),
+        subtitle_language: Some("eng".to_owned()),
+        subtitle_delay: Some(1500),
+        mtime: TestEnv::now(),
+    };
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .language_preferences
+            .items
+            .get("tt123456"),
+        Some(&expected_preference),
+        "Language preference learned"
+    );
+    assert_eq!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(LANGUAGE_PREFERENCES_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<LanguagePreferencesBucket>(data).unwrap())
+            .and_then(|bucket| bucket.items.get("tt123456").cloned()),
+        Some(expected_preference),
+        "Language preference persisted"
+    );
+}
+
+#[test]
+fn apply_language_preference() {
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://transport_url/meta/series/tt123456.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt123456".to_owned(),
+                            r#type: "series".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![
+                            Video {
+                                id: "tt123456:1:1".to_owned(),
+                                ..Default::default()
+                            },
+                            Video {
+                                id: "tt123456:1:2".to_owned(),
+                                ..Default::default()
+                            },
+                        ],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => future::err(EnvError::Fetch("Unhandled fetch request".to_owned())).boxed_env(),
+        }
+    }
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let previous_key = StreamsItemKey {
+        meta_id: "tt123456".to_owned(),
+        video_id: "tt123456:1:1".to_owned(),
+    };
+    let streams = StreamsBucket {
+        uid: None,
+        items: [(
+            previous_key,
+            StreamsItem {
+                stream: create_stream("https://other_source_url"),
+                r#type: "series".to_owned(),
+                meta_id: "tt123456".to_owned(),
+                video_id: "tt123456:1:1".to_owned(),
+                meta_transport_url: "https://transport_url/manifest.json".parse().unwrap(),
+                stream_transport_url: "https://transport_url/manifest.json".parse().unwrap(),
+                state: Some(StreamItemState {
+                    subtitle_track: None,
+                    subtitles_origin: None,
+                    subtitle_delay: None,
+                    audio_track: None,
+                    audio_delay: None,
+                    playback_speed: Some(1.5),
+                    player_type: None,
+                }),
+                mtime: TestEnv::now(),
+            },
+        )]
+        .into(),
+    };
+    let language_preferences = LanguagePreferencesBucket {
+        uid: None,
+        items: [(
+            "tt123456".to_owned(),
+            LanguagePreference {
+                audio_language: Some("jpn".to_owned()),
+                subtitle_language: Some("eng".to_owned()),
+                subtitle_delay: Some(1500),
+                mtime: TestEnv::now(),
+            },
+        )]
+        .into(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: create_ctx(streams, language_preferences),
+            player: Player::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Box::new(create_selected(
+                "tt123456:1:2",
+                "https://source_url",
+            )))),
+        })
+    });
+    let model = runtime.model().unwrap();
+    assert_eq!(
+        model
+            .player
+            .language_preference
+            .as_ref()
+            .and_then(|preference| preference.audio_language.as_deref()),
+        Some("jpn"),
+        "Language preference of the series is loaded"
+    );
+    let stream_state = model
+        .player
+        .stream_state
+        .as_ref()
+        .expect("Should have a stream state");
+    assert_eq!(
+        stream_state.subtitle_delay,
+        Some(1500),
+        "Subtitle delay of the series is applied"
+    );
+    assert_eq!(
+        stream_state
+            .audio_track
+            .as_ref()
+            .and_then(|audio_track| audio_track.language.as_deref()),
+        Some("jpn"),
+        "Audio track is chosen by the language of the series"
+    );
+    assert_eq!(
+        stream_state
+            .subtitle_track
+            .as_ref()
+            .and_then(|subtitle_track| subtitle_track.language.as_deref()),
+        Some("eng"),
+        "Subtitle track is chosen by the language of the series"
+    );
+    assert_eq!(stream_state.playback_speed, Some(1.5));
+}
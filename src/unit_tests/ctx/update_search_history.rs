@@ -11,9 +11,11 @@ use crate::{
         Env, Runtime, RuntimeAction,
     },
     types::{
-        addon::ExtraValue, events::DismissedEventsBucket, library::LibraryBucket,
-        notifications::NotificationsBucket, profile::Profile, search_history::SearchHistoryBucket,
-        streams::StreamsBucket,
+        addon::ExtraValue, addons_history::AddonsHistoryBucket, events::DismissedEventsBucket,
+        intros::IntrosBucket, language_preferences::LanguagePreferencesBucket,
+        library::LibraryBucket, notifications::NotificationsBucket, play_queue::PlayQueueBucket,
+        profile::Profile, search_history::SearchHistoryBucket, streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
     },
     unit_tests::{TestEnv, STORAGE},
 };
@@ -36,6 +38,11 @@ fn test_search_history_update() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        LanguagePreferencesBucket::default(),
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
     );
 
     let catalogs_with_extra = CatalogsWithExtra::default();
@@ -109,6 +116,11 @@ fn test_search_history_clear_items() {
         NotificationsBucket::new::<TestEnv>(None, vec![]),
         SearchHistoryBucket::default(),
         DismissedEventsBucket::default(),
+        LanguagePreferencesBucket::default(),
+        IntrosBucket::default(),
+        PlayQueueBucket::default(),
+        WatchHistoryBucket::default(),
+        AddonsHistoryBucket::default(),
     );
 
     let catalogs_with_extra = CatalogsWithExtra::default();
@@ -0,0 +1,147 @@
+use std::any::Any;
+
+use futures::future;
+use stremio_derive::Model;
+
+use crate::{
+    constants::WATCH_HISTORY_STORAGE_KEY,
+    models::{
+        ctx::Ctx,
+        player::{Player, Selected},
+        watch_history::WatchHistory,
+    },
+    runtime::{
+        msg::{Action, ActionCtx, ActionLoad, ActionPlayer},
+        Env, EnvError, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest, ResourceResponse},
+        resource::{MetaItem, MetaItemPreview, Stream, StreamSource, Video},
+        watch_history::WatchHistoryBucket,
+    },
+    unit_tests::{Request, TestEnv, FETCH_HANDLER, STORAGE},
+};
+
+#[derive(Model, Default, Clone, Debug)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    player: Player,
+    watch_history: WatchHistory,
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request {
+        Request { url, .. } if url == "https://transport_url/meta/series/tt123456.json" => {
+            future::ok(Box::new(ResourceResponse::Meta {
+                meta: MetaItem {
+                    preview: MetaItemPreview {
+                        id: "tt123456".to_owned(),
+                        r#type: "series".to_owned(),
+                        name: "Series".to_owned(),
+                        ..Default::default()
+                    },
+                    videos: vec![Video {
+                        id: "tt123456:1:1".to_owned(),
+                        ..Default::default()
+                    }],
+                },
+            }) as Box<dyn Any + Send>)
+            .boxed_env()
+        }
+        _ => future::err(EnvError::Fetch("Unhandled fetch request".to_owned())).boxed_env(),
+    }
+}
+
+fn dispatch(runtime: &Runtime<TestEnv, TestModel>, action: Action) {
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action,
+        })
+    });
+}
+
+#[test]
+fn record_watch_history() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    let source = StreamSource::Url {
+        url: "https://source_url".parse().unwrap(),
+    };
+    dispatch(
+        &runtime,
+        Action::Load(ActionLoad::Player(Box::new(Selected {
+            stream: Stream {
+                source: source.to_owned(),
+                name: None,
+                description: None,
+                thumbnail: None,
+                subtitles: vec![],
+                behavior_hints: Default::default(),
+            },
+            stream_request: Some(ResourceRequest {
+                base: "https://transport_url/manifest.json".parse().unwrap(),
+                path: ResourcePath::without_extra("stream", "series", "tt123456:1:1"),
+            }),
+            meta_request: Some(ResourceRequest {
+                base: "https://transport_url/manifest.json".parse().unwrap(),
+                path: ResourcePath::without_extra("meta", "series", "tt123456"),
+            }),
+            subtitles_path: None,
+        }))),
+    );
+    for time in (10_000..=100_000).step_by(1000) {
+        dispatch(
+            &runtime,
+            Action::Player(ActionPlayer::TimeChanged {
+                time,
+                duration: 2_400_000,
+                device: "web".to_owned(),
+            }),
+        );
+    }
+    assert!(
+        runtime.model().unwrap().ctx.watch_history.items.is_empty(),
+        "Session is recorded once it ends"
+    );
+    dispatch(&runtime, Action::Unload);
+
+    let model = runtime.model().unwrap().to_owned();
+    assert_eq!(model.ctx.watch_history.items.len(), 1, "Session recorded");
+    let item = &model.ctx.watch_history.items[0];
+    assert_eq!(item.meta_id, "tt123456");
+    assert_eq!(item.name, "Series");
+    assert_eq!(item.video_id, "tt123456:1:1");
+    assert_eq!(item.stream_source, source);
+    assert_eq!((item.from, item.to), (10_000, 100_000));
+    assert_eq!(item.time_watched, 90_000);
+    assert_eq!(item.device, "web");
+    assert_eq!(
+        model.watch_history.days.len(),
+        1,
+        "Sessions are grouped by day"
+    );
+    assert_eq!(
+        model.watch_history.days[0].date,
+        TestEnv::now().date_naive()
+    );
+    assert_eq!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(WATCH_HISTORY_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<WatchHistoryBucket>(data).unwrap()),
+        Some(model.ctx.watch_history.to_owned()),
+        "Watch history persisted"
+    );
+
+    dispatch(
+        &runtime,
+        Action::Ctx(ActionCtx::RemoveFromWatchHistory(item.id.to_owned())),
+    );
+    let model = runtime.model().unwrap();
+    assert!(model.ctx.watch_history.items.is_empty(), "Session removed");
+    assert!(model.watch_history.days.is_empty());
+}
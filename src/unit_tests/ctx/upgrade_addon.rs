@@ -3,12 +3,17 @@ use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Runtime, RuntimeAction};
 use crate::types::addon::{Descriptor, Manifest};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::events::DismissedEventsBucket;
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::play_queue::PlayQueueBucket;
 use crate::types::profile::Profile;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::unit_tests::{TestEnv, REQUESTS, STORAGE};
 use semver::Version;
 use stremio_derive::Model;
@@ -36,6 +41,7 @@ fn actionctx_addon_upgrade() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: Default::default(),
@@ -55,6 +61,7 @@ fn actionctx_addon_upgrade() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url").unwrap(),
         flags: Default::default(),
@@ -74,6 +81,7 @@ fn actionctx_addon_upgrade() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url_other").unwrap(),
         flags: Default::default(),
@@ -91,6 +99,11 @@ fn actionctx_addon_upgrade() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
@@ -147,6 +160,7 @@ fn actionctx_addon_upgrade_fail_due_to_different_url() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url1").unwrap(),
         flags: Default::default(),
@@ -166,6 +180,7 @@ fn actionctx_addon_upgrade_fail_due_to_different_url() {
             catalogs: vec![],
             addon_catalogs: vec![],
             behavior_hints: Default::default(),
+            config: vec![],
         },
         transport_url: Url::parse("https://transport_url2").unwrap(),
         flags: Default::default(),
@@ -183,6 +198,11 @@ fn actionctx_addon_upgrade_fail_due_to_different_url() {
                 NotificationsBucket::new::<TestEnv>(None, vec![]),
                 SearchHistoryBucket::default(),
                 DismissedEventsBucket::default(),
+                LanguagePreferencesBucket::default(),
+                IntrosBucket::default(),
+                PlayQueueBucket::default(),
+                WatchHistoryBucket::default(),
+                AddonsHistoryBucket::default(),
             ),
         },
         vec![],
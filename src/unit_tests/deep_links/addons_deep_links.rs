@@ -1,6 +1,6 @@
 use crate::deep_links::AddonsDeepLinks;
 use crate::models::installed_addons_with_filters::InstalledAddonsRequest;
-use crate::types::addon::{ResourcePath, ResourceRequest};
+use crate::types::addon::{Descriptor, ResourcePath, ResourceRequest};
 use std::str::FromStr;
 use url::Url;
 
@@ -33,3 +33,17 @@ fn addons_deep_links_request() {
             .to_string()
     );
 }
+
+#[test]
+fn addons_deep_links_configure() {
+    let addon = Descriptor {
+        manifest: Default::default(),
+        transport_url: Url::from_str("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let adl = AddonsDeepLinks::from(&addon);
+    assert_eq!(
+        adl.addons,
+        "stremio:///addons/configure/https%3A%2F%2Faddon.com%2Fmanifest.json".to_string()
+    );
+}
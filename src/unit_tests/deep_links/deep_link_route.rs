@@ -0,0 +1,313 @@
+use std::str::FromStr;
+
+use strum::IntoEnumIterator;
+use url::Url;
+
+use crate::deep_links::{
+    AddonsDeepLinks, DeepLinkRoute, DiscoverDeepLinks, LibraryDeepLinks, MetaItemDeepLinks,
+    SearchHistoryItemDeepLinks, StreamDeepLinks, VideoDeepLinks,
+};
+use crate::models::installed_addons_with_filters::InstalledAddonsRequest;
+use crate::models::library_with_filters::{LibraryRequest, Sort};
+use crate::runtime::msg::ActionLoad;
+use crate::types::addon::{Descriptor, ExtraValue, ResourcePath, ResourceRequest};
+use crate::types::profile::Settings;
+use crate::types::resource::{Stream, StreamSource, Video};
+
+const IDS: &[&str] = &[
+    "tt1254207",
+    "kitsu:1:2",
+    "id with spaces",
+    "id/with?reserved&chars=#",
+    "ünïcödé",
+];
+const TYPES: &[&str] = &["movie", "series", "other type"];
+const BASES: &[&str] = &[
+    "https://addon.com/manifest.json",
+    "http://127.0.0.1:11470/path/manifest.json?query=1",
+];
+
+fn parse(deep_link: &str) -> DeepLinkRoute {
+    let route = DeepLinkRoute::from_str(deep_link).expect("Valid deep link");
+    assert_eq!(
+        DeepLinkRoute::from_str(&deep_link.replace("stremio://", "#")).unwrap(),
+        route,
+        "Web deep links are parsed to the same route"
+    );
+    route
+}
+
+fn stream() -> Stream {
+    Stream {
+        source: StreamSource::Url {
+            url: Url::parse("http://domain.root/some/path?param=some&foo=bar").unwrap(),
+        },
+        name: Some("Stream".to_owned()),
+        description: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: Default::default(),
+    }
+}
+
+#[test]
+fn deep_link_route_meta_details() {
+    for r#type in TYPES {
+        for id in IDS {
+            let meta_path = ResourcePath::without_extra("meta", r#type, id);
+            let deep_links = MetaItemDeepLinks::from(&meta_path);
+            match parse(deep_links.meta_details_videos.as_ref().unwrap()) {
+                DeepLinkRoute::MetaDetails(selected) => {
+                    assert_eq!(selected.meta_path, meta_path);
+                    assert_eq!(selected.stream_path, None);
+                    assert_eq!(MetaItemDeepLinks::from(&selected.meta_path), deep_links);
+                }
+                route => panic!("Unexpected route {route:?}"),
+            }
+
+            let video = Video {
+                id: format!("{id}:1:1"),
+                ..Default::default()
+            };
+            let request = ResourceRequest::new(Url::parse(BASES[0]).unwrap(), meta_path.clone());
+            let deep_links = VideoDeepLinks::from((&video, &request, &None, &Settings::default()));
+            match parse(&deep_links.meta_details_streams) {
+                DeepLinkRoute::MetaDetails(selected) => {
+                    assert_eq!(selected.meta_path, meta_path);
+                    assert_eq!(
+                        selected.stream_path,
+                        Some(ResourcePath::without_extra("stream", r#type, &video.id))
+                    );
+                }
+                route => panic!("Unexpected route {route:?}"),
+            }
+        }
+    }
+}
+
+#[test]
+fn deep_link_route_player() {
+    let settings = Settings::default();
+    let deep_links = StreamDeepLinks::from((&stream(), &None, &settings));
+    match parse(&deep_links.player) {
+        DeepLinkRoute::Player(selected) => {
+            assert_eq!(selected.stream, stream());
+            assert_eq!(selected.stream_request, None);
+            assert_eq!(selected.meta_request, None);
+        }
+        route => panic!("Unexpected route {route:?}"),
+    }
+
+    for (stream_base, meta_base) in BASES.iter().zip(BASES.iter().rev()) {
+        for id in IDS {
+            let video_id = format!("{id}:1:1");
+            let stream_request = ResourceRequest::new(
+                Url::parse(stream_base).unwrap(),
+                ResourcePath::without_extra("stream", "series", &video_id),
+            );
+            let meta_request = ResourceRequest::new(
+                Url::parse(meta_base).unwrap(),
+                ResourcePath::without_extra("meta", "series", id),
+            );
+            let deep_links = StreamDeepLinks::from((
+                &stream(),
+                &stream_request,
+                &meta_request,
+                &None,
+                &settings,
+            ));
+            match parse(&deep_links.player) {
+                DeepLinkRoute::Player(selected) => {
+                    assert_eq!(selected.stream, stream());
+                    assert_eq!(selected.stream_request.as_ref(), Some(&stream_request));
+                    assert_eq!(selected.meta_request.as_ref(), Some(&meta_request));
+                    assert_eq!(
+                        selected.subtitles_path,
+                        Some(ResourcePath::without_extra(
+                            "subtitles",
+                            "series",
+                            &video_id
+                        ))
+                    );
+                    assert_eq!(
+                        StreamDeepLinks::from((
+                            &selected.stream,
+                            selected.stream_request.as_ref().unwrap(),
+                            selected.meta_request.as_ref().unwrap(),
+                            &None,
+                            &settings,
+                        ))
+                        .player,
+                        deep_links.player
+                    );
+                }
+                route => panic!("Unexpected route {route:?}"),
+            }
+        }
+    }
+}
+
+#[test]
+fn deep_link_route_discover() {
+    let extras = [
+        vec![],
+        vec![ExtraValue {
+            name: "genre".to_owned(),
+            value: "Science Fiction & Fantasy".to_owned(),
+        }],
+        vec![
+            ExtraValue {
+                name: "genre".to_owned(),
+                value: "Action".to_owned(),
+            },
+            ExtraValue {
+                name: "skip".to_owned(),
+                value: "100".to_owned(),
+            },
+        ],
+    ];
+    for base in BASES {
+        for r#type in TYPES {
+            for extra in &extras {
+                let request = ResourceRequest::new(
+                    Url::parse(base).unwrap(),
+                    ResourcePath::with_extra("catalog", r#type, "top", extra),
+                );
+                let deep_links = DiscoverDeepLinks::from(&request);
+                let route = parse(&deep_links.discover);
+                match &route {
+                    DeepLinkRoute::Discover(selected) => {
+                        assert_eq!(selected.request, request);
+                        assert_eq!(
+                            DiscoverDeepLinks::from(&selected.request).discover,
+                            deep_links.discover
+                        );
+                    }
+                    route => panic!("Unexpected route {route:?}"),
+                }
+                assert!(matches!(
+                    ActionLoad::from(route),
+                    ActionLoad::CatalogWithFilters(Some(_))
+                ));
+            }
+        }
+    }
+}
+
+#[test]
+fn deep_link_route_addons() {
+    for base in BASES {
+        for r#type in TYPES {
+            let request = ResourceRequest::new(
+                Url::parse(base).unwrap(),
+                ResourcePath::without_extra("addon_catalog", r#type, "community"),
+            );
+            let deep_links = AddonsDeepLinks::from(&request);
+            match parse(&deep_links.addons) {
+                DeepLinkRoute::Addons(selected) => assert_eq!(selected.request, request),
+                route => panic!("Unexpected route {route:?}"),
+            }
+        }
+    }
+
+    for base in BASES {
+        let addon = Descriptor {
+            manifest: Default::default(),
+            transport_url: Url::parse(base).unwrap(),
+            flags: Default::default(),
+        };
+        let deep_links = AddonsDeepLinks::from(&addon);
+        match parse(&deep_links.addons) {
+            DeepLinkRoute::AddonConfigure(selected) => {
+                assert_eq!(selected.transport_url, addon.transport_url)
+            }
+            route => panic!("Unexpected route {route:?}"),
+        }
+    }
+
+    for r#type in TYPES
+        .iter()
+        .map(|r#type| Some(r#type.to_string()))
+        .chain([None])
+    {
+        let request = InstalledAddonsRequest { r#type };
+        let deep_links = AddonsDeepLinks::from(&request);
+        match parse(&deep_links.addons) {
+            DeepLinkRoute::InstalledAddons(selected) => assert_eq!(selected.request, request),
+            route => panic!("Unexpected route {route:?}"),
+        }
+    }
+}
+
+#[test]
+fn deep_link_route_library() {
+    for root in ["library", "continuewatching"] {
+        for r#type in TYPES
+            .iter()
+            .map(|r#type| Some(r#type.to_string()))
+            .chain([None])
+        {
+            for sort in Sort::iter() {
+                let request = LibraryRequest {
+                    r#type: r#type.to_owned(),
+                    sort,
+                    page: Default::default(),
+                };
+                let deep_links = LibraryDeepLinks::from((&root.to_owned(), &request));
+                match (root, parse(&deep_links.library)) {
+                    ("library", DeepLinkRoute::Library(selected))
+                    | ("continuewatching", DeepLinkRoute::ContinueWatching(selected)) => {
+                        assert_eq!(selected.request, request)
+                    }
+                    (_, route) => panic!("Unexpected route {route:?}"),
+                }
+            }
+        }
+
+        match parse(&LibraryDeepLinks::from(&root.to_owned()).library) {
+            DeepLinkRoute::Library(selected) | DeepLinkRoute::ContinueWatching(selected) => {
+                assert_eq!(selected.request.r#type, None);
+                assert_eq!(selected.request.sort, Sort::default());
+            }
+            route => panic!("Unexpected route {route:?}"),
+        }
+    }
+}
+
+#[test]
+fn deep_link_route_search() {
+    for query in IDS {
+        let deep_links = SearchHistoryItemDeepLinks::from(&query.to_string());
+        match parse(&deep_links.search) {
+            DeepLinkRoute::Search(selected) => {
+                assert_eq!(selected.r#type, None);
+                assert_eq!(
+                    selected.extra,
+                    vec![ExtraValue {
+                        name: "search".to_owned(),
+                        value: query.to_string(),
+                    }]
+                );
+            }
+            route => panic!("Unexpected route {route:?}"),
+        }
+    }
+}
+
+#[test]
+fn deep_link_route_invalid() {
+    for deep_link in [
+        "https://www.stremio.com/detail/movie/tt1",
+        "stremio:///unknown/route",
+        "stremio:///detail/movie",
+        "stremio:///player/not-a-stream",
+        "stremio:///discover/not-a-url/movie/top",
+        "stremio:///library?sort=unknown",
+        "stremio:///search",
+    ] {
+        assert!(
+            DeepLinkRoute::from_str(deep_link).is_err(),
+            "{deep_link} is not a valid deep link"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod addons_deep_links;
+mod deep_link_route;
 mod discover_deep_links;
 mod external_player_link;
 mod library_deep_links;
@@ -10,10 +10,13 @@ use enclose::enclose;
 use futures::{channel::mpsc::Receiver, future, Future, StreamExt, TryFutureExt};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 use crate::{
     models::{ctx::Ctx, streaming_server::StreamingServer},
     runtime::{Env, EnvFuture, EnvFutureExt, Model, Runtime, RuntimeEvent, TryEnvFuture},
+    unit_tests::{WatchPartyLoopbackTransport, PARTIES},
+    watch_party_transport::WatchPartyTransport,
 };
 
 pub static FETCH_HANDLER: Lazy<RwLock<FetchHandler>> =
@@ -66,6 +69,7 @@ impl TestEnv {
         *EVENTS.write().unwrap() = vec![];
         *STATES.write().unwrap() = vec![];
         *NOW.write().unwrap() = Utc::now();
+        *PARTIES.write().unwrap() = HashMap::new();
         env_mutex
     }
     pub fn run<F: FnOnce()>(runnable: F) {
@@ -119,6 +123,9 @@ impl Env for TestEnv {
             })
             .boxed_env()
     }
+    fn fetch_bytes(request: http::Request<()>) -> TryEnvFuture<Vec<u8>> {
+        Self::fetch(request)
+    }
     fn get_storage<T: for<'de> Deserialize<'de> + 'static>(key: &str) -> TryEnvFuture<Option<T>> {
         future::ok(
             STORAGE
@@ -146,6 +153,9 @@ impl Env for TestEnv {
     fn now() -> DateTime<Utc> {
         *NOW.read().unwrap()
     }
+    fn watch_party_transport(party_url: &Url) -> Box<dyn WatchPartyTransport> {
+        Box::new(WatchPartyLoopbackTransport::new(party_url.to_owned()))
+    }
     fn flush_analytics() -> EnvFuture<'static, ()> {
         future::ready(()).boxed_env()
     }
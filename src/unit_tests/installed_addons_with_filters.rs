@@ -0,0 +1,227 @@
+use std::any::Any;
+
+use futures::future;
+use semver::Version;
+use stremio_derive::Model;
+use url::Url;
+
+use crate::{
+    models::{
+        ctx::Ctx,
+        installed_addons_with_filters::{
+            InstalledAddonsRequest, InstalledAddonsWithFilters, Selected,
+        },
+    },
+    runtime::{
+        msg::{Action, ActionLoad},
+        EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{Descriptor, DescriptorFlags, Manifest, ManifestCatalog, ManifestResource},
+        profile::{Profile, Settings},
+    },
+    unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS},
+};
+
+fn manifest(id: &str, version: Version, types: &[&str], catalogs: &[&str]) -> Manifest {
+    Manifest {
+        id: id.to_owned(),
+        version,
+        types: types.iter().map(|r#type| r#type.to_string()).collect(),
+        resources: vec![ManifestResource::Short("catalog".to_owned())],
+        catalogs: catalogs
+            .iter()
+            .map(|id| ManifestCatalog {
+                id: id.to_string(),
+                r#type: "movie".to_owned(),
+                name: None,
+                extra: Default::default(),
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    let manifest = match request.url.as_str() {
+        "https://addon.com/manifest.json" => Manifest {
+            resources: vec![
+                ManifestResource::Short("catalog".to_owned()),
+                ManifestResource::Short("stream".to_owned()),
+            ],
+            ..manifest(
+                "addon",
+                Version::new(0, 0, 2),
+                &["movie", "series"],
+                &["top", "new"],
+            )
+        },
+        "https://protected.com/manifest.json" => {
+            manifest("protected", Version::new(0, 0, 2), &["movie"], &[])
+        }
+        "https://latest.com/manifest.json" => {
+            manifest("latest", Version::new(0, 0, 1), &["movie"], &[])
+        }
+        _ => return default_fetch_handler(request),
+    };
+    future::ok(Box::new(manifest) as Box<dyn Any + Send>).boxed_env()
+}
+
+#[test]
+fn installed_addons_upgrades() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        installed_addons: InstalledAddonsWithFilters,
+    }
+
+    let descriptor = |host: &str, manifest: Manifest, protected: bool| Descriptor {
+        manifest,
+        transport_url: Url::parse(&format!("https://{host}/manifest.json")).unwrap(),
+        flags: DescriptorFlags {
+            official: false,
+            protected,
+        },
+    };
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![
+                        descriptor(
+                            "addon.com",
+                            manifest("addon", Version::new(0, 0, 1), &["movie"], &["top", "old"]),
+                            false,
+                        ),
+                        descriptor(
+                            "protected.com",
+                            manifest("protected", Version::new(0, 0, 1), &["movie"], &[]),
+                            true,
+                        ),
+                        descriptor(
+                            "latest.com",
+                            manifest("latest", Version::new(0, 0, 1), &["movie"], &[]),
+                            false,
+                        ),
+                    ],
+                    settings: Settings {
+                        auto_upgrade_addons: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            installed_addons: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::InstalledAddonsWithFilters(Selected {
+                request: InstalledAddonsRequest { r#type: None },
+            })),
+        })
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        3,
+        "The manifests of all installed addons are requested"
+    );
+    let model = runtime.model().unwrap();
+    assert_eq!(
+        model
+            .ctx
+            .profile
+            .addons
+            .iter()
+            .map(|addon| addon.manifest.version.to_string())
+            .collect::<Vec<_>>(),
+        vec!["0.0.2", "0.0.1", "0.0.1"],
+        "Only the addon which is not protected is upgraded"
+    );
+    assert_eq!(model.ctx.profile.addons[0].manifest.types.len(), 2);
+    let upgrades = &model.installed_addons.upgrades;
+    assert_eq!(upgrades.len(), 1, "Upgraded addons are not reported");
+    assert_eq!(upgrades[0].addon.manifest.id, "protected");
+    assert_eq!(upgrades[0].installed_version, Version::new(0, 0, 1));
+    assert!(upgrades[0].addon.flags.protected);
+}
+
+#[test]
+fn installed_addons_upgrade_changes() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        installed_addons: InstalledAddonsWithFilters,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![Descriptor {
+                        manifest: manifest(
+                            "addon",
+                            Version::new(0, 0, 1),
+                            &["movie"],
+                            &["top", "old"],
+                        ),
+                        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+                        flags: Default::default(),
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            installed_addons: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::InstalledAddonsWithFilters(Selected {
+                request: InstalledAddonsRequest { r#type: None },
+            })),
+        })
+    });
+    let model = runtime.model().unwrap();
+    assert_eq!(
+        model.ctx.profile.addons[0].manifest.version,
+        Version::new(0, 0, 1),
+        "Addons are not upgraded automatically by default"
+    );
+    let upgrades = &model.installed_addons.upgrades;
+    assert_eq!(upgrades.len(), 1);
+    assert_eq!(upgrades[0].addon.manifest.version, Version::new(0, 0, 2));
+    assert_eq!(upgrades[0].added_resources, vec!["stream".to_owned()]);
+    assert!(upgrades[0].removed_resources.is_empty());
+    assert_eq!(upgrades[0].added_types, vec!["series".to_owned()]);
+    assert!(upgrades[0].removed_types.is_empty());
+    assert_eq!(
+        upgrades[0]
+            .added_catalogs
+            .iter()
+            .map(|catalog| catalog.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["new"]
+    );
+    assert_eq!(
+        upgrades[0]
+            .removed_catalogs
+            .iter()
+            .map(|catalog| catalog.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["old"]
+    );
+}
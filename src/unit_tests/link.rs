@@ -3,13 +3,18 @@ use crate::models::ctx::Ctx;
 use crate::models::link::Link;
 use crate::runtime::msg::{Action, ActionLink, ActionLoad};
 use crate::runtime::{EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addons_history::AddonsHistoryBucket;
 use crate::types::api::{APIResult, LinkAuthKey, LinkCodeResponse, LinkDataResponse};
 use crate::types::events::DismissedEventsBucket;
+use crate::types::intros::IntrosBucket;
+use crate::types::language_preferences::LanguagePreferencesBucket;
 use crate::types::library::LibraryBucket;
 use crate::types::notifications::NotificationsBucket;
+use crate::types::play_queue::PlayQueueBucket;
 use crate::types::profile::Profile;
 use crate::types::search_history::SearchHistoryBucket;
 use crate::types::streams::StreamsBucket;
+use crate::types::watch_history::WatchHistoryBucket;
 use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS};
 use futures::future;
 use std::any::Any;
@@ -64,6 +69,11 @@ fn create_link_code() {
             NotificationsBucket::new::<TestEnv>(None, vec![]),
             SearchHistoryBucket::default(),
             DismissedEventsBucket::default(),
+            LanguagePreferencesBucket::default(),
+            IntrosBucket::default(),
+            PlayQueueBucket::default(),
+            WatchHistoryBucket::default(),
+            AddonsHistoryBucket::default(),
         ),
         link: Link::default(),
     };
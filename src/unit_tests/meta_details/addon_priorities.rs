@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use url::Url;
+
+use super::{addon, fetch_handler, TestModel};
+use crate::constants::{META_RESOURCE_NAME, STREAM_RESOURCE_NAME};
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::Selected;
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::addon::ResourcePath;
+use crate::types::profile::{AddonResourcePriority, Profile, Settings};
+use crate::unit_tests::{TestEnv, FETCH_HANDLER, REQUESTS};
+
+#[test]
+fn addon_priorities_streams() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = fetch_handler(vec![]);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![
+                        addon("https://a.com/manifest.json", "movie", STREAM_RESOURCE_NAME),
+                        addon("https://b.com/manifest.json", "movie", STREAM_RESOURCE_NAME),
+                        addon("https://c.com/manifest.json", "movie", STREAM_RESOURCE_NAME),
+                        addon("https://d.com/manifest.json", "movie", STREAM_RESOURCE_NAME),
+                    ],
+                    settings: Settings {
+                        addon_priorities: HashMap::from([(
+                            STREAM_RESOURCE_NAME.to_owned(),
+                            AddonResourcePriority {
+                                order: vec![
+                                    Url::parse("https://c.com/manifest.json").unwrap(),
+                                    Url::parse("https://a.com/manifest.json").unwrap(),
+                                ],
+                                disabled: vec![Url::parse("https://b.com/manifest.json").unwrap()],
+                            },
+                        )]),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            meta_details: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::MetaDetails(Selected {
+                meta_path: ResourcePath::without_extra(META_RESOURCE_NAME, "movie", "tt1"),
+                stream_path: Some(ResourcePath::without_extra(
+                    STREAM_RESOURCE_NAME,
+                    "movie",
+                    "tt1",
+                )),
+                guess_stream: false,
+            })),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .meta_details
+            .streams
+            .iter()
+            .map(|streams| streams.request.base.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "https://c.com/manifest.json",
+            "https://a.com/manifest.json",
+            "https://d.com/manifest.json",
+        ],
+        "The streams are ordered by priority, without the disabled addon"
+    );
+    assert_eq!(
+        REQUESTS
+            .read()
+            .unwrap()
+            .iter()
+            .map(|request| request.url.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "https://c.com/stream/movie/tt1.json",
+            "https://a.com/stream/movie/tt1.json",
+            "https://d.com/stream/movie/tt1.json",
+        ]
+    );
+}
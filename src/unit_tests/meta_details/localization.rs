@@ -0,0 +1,109 @@
+use serde_json::json;
+
+use super::{addon, fetch_handler, TestModel};
+use crate::constants::META_RESOURCE_NAME;
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::Selected;
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::addon::{ManifestCatalog, ManifestExtra, ResourcePath};
+use crate::types::profile::{Profile, Settings};
+use crate::unit_tests::{TestEnv, FETCH_HANDLER, REQUESTS};
+
+#[test]
+fn localized_merged_meta_item() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = fetch_handler(vec![
+        (
+            "https://localized.com/meta/movie/tt1.json",
+            json!({ "id": "tt1", "type": "movie", "name": "", "releaseInfo": "2020" }),
+        ),
+        (
+            "https://default.com/meta/movie/tt1.json",
+            json!({
+                "id": "tt1",
+                "type": "movie",
+                "name": "Movie",
+                "description": "Description",
+                "releaseInfo": "2020",
+            }),
+        ),
+    ]);
+    let mut localized_addon = addon(
+        "https://localized.com/manifest.json",
+        "movie",
+        META_RESOURCE_NAME,
+    );
+    localized_addon.manifest.catalogs = vec![ManifestCatalog {
+        id: "top".to_owned(),
+        r#type: "movie".to_owned(),
+        name: None,
+        extra: ManifestExtra::Short {
+            required: vec![],
+            supported: vec!["language".to_owned()],
+        },
+    }];
+    let settings = Settings {
+        interface_language: "bul".to_owned(),
+        ..Default::default()
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![
+                        localized_addon,
+                        addon(
+                            "https://default.com/manifest.json",
+                            "movie",
+                            META_RESOURCE_NAME,
+                        ),
+                    ],
+                    settings,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            meta_details: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::MetaDetails(Selected {
+                meta_path: ResourcePath::without_extra(META_RESOURCE_NAME, "movie", "tt1"),
+                stream_path: None,
+                guess_stream: false,
+            })),
+        })
+    });
+    assert_eq!(
+        REQUESTS
+            .read()
+            .unwrap()
+            .iter()
+            .map(|request| request.url.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "https://localized.com/meta/movie/tt1.json",
+            "https://default.com/meta/movie/tt1.json",
+        ],
+        "The language declared by the catalogs is not passed to the meta resource"
+    );
+    let model = runtime.model().unwrap();
+    let preview = &model
+        .meta_details
+        .merged_meta_item
+        .as_ref()
+        .expect("Merged meta item")
+        .preview;
+    assert_eq!(preview.name, "Movie");
+    assert_eq!(
+        preview.description.as_deref(),
+        Some("Description"),
+        "The missing description falls back to the other addons"
+    );
+    assert_eq!(preview.release_info.as_deref(), Some("2020"));
+}
@@ -0,0 +1,129 @@
+use serde_json::json;
+use url::Url;
+
+use super::{addon, fetch_handler, TestModel};
+use crate::constants::META_RESOURCE_NAME;
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::Selected;
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::addon::ResourcePath;
+use crate::types::profile::Profile;
+use crate::types::resource::Link;
+use crate::unit_tests::{TestEnv, FETCH_HANDLER};
+
+#[test]
+fn merged_meta_item() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = fetch_handler(vec![
+        (
+            "https://first.com/meta/series/tt1.json",
+            json!({
+                "id": "tt1",
+                "type": "series",
+                "name": "Series",
+                "poster": "https://first.com/poster.jpg",
+                "links": [{ "name": "Drama", "category": "Genres", "url": "stremio:///drama" }],
+                "videos": [
+                    { "id": "tt1:1:1", "title": "Pilot", "season": 1, "episode": 1 },
+                ],
+            }),
+        ),
+        (
+            "https://second.com/meta/series/tt1.json",
+            json!({
+                "id": "tt1",
+                "type": "series",
+                "name": "Other name",
+                "poster": "https://second.com/poster.jpg",
+                "logo": "https://second.com/logo.png",
+                "description": "Description",
+                "links": [
+                    { "name": "Drama", "category": "Genres", "url": "stremio:///drama" },
+                    { "name": "Crime", "category": "Genres", "url": "stremio:///crime" },
+                ],
+                "trailers": [{ "source": "trailer", "type": "Trailer" }],
+                "videos": [
+                    { "id": "other:1", "title": "Pilot", "season": 1, "episode": 1, "thumbnail": "https://second.com/1.jpg" },
+                    { "id": "other:0", "title": "Special", "season": 0, "episode": 1 },
+                    { "id": "other:2", "title": "Second", "season": 1, "episode": 2 },
+                ],
+            }),
+        ),
+    ]);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![
+                        addon(
+                            "https://first.com/manifest.json",
+                            "series",
+                            META_RESOURCE_NAME,
+                        ),
+                        addon(
+                            "https://second.com/manifest.json",
+                            "series",
+                            META_RESOURCE_NAME,
+                        ),
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            meta_details: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::MetaDetails(Selected {
+                meta_path: ResourcePath::without_extra(META_RESOURCE_NAME, "series", "tt1"),
+                stream_path: None,
+                guess_stream: false,
+            })),
+        })
+    });
+    let model = runtime.model().unwrap();
+    let merged_meta_item = model
+        .meta_details
+        .merged_meta_item
+        .as_ref()
+        .expect("Merged meta item");
+    let preview = &merged_meta_item.preview;
+    assert_eq!(preview.name, "Series");
+    assert_eq!(
+        preview.poster.as_ref().map(Url::as_str),
+        Some("https://first.com/poster.jpg"),
+        "The fields of the first addon are kept"
+    );
+    assert_eq!(
+        preview.logo.as_ref().map(Url::as_str),
+        Some("https://second.com/logo.png")
+    );
+    assert_eq!(preview.description.as_deref(), Some("Description"));
+    assert_eq!(
+        preview
+            .links
+            .iter()
+            .map(|link: &Link| link.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Drama", "Crime"]
+    );
+    assert_eq!(preview.trailer_streams.len(), 1);
+    assert_eq!(
+        merged_meta_item
+            .videos
+            .iter()
+            .map(|video| (video.id.as_str(), video.thumbnail.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("tt1:1:1", Some("https://second.com/1.jpg")),
+            ("other:2", None),
+            ("other:0", None),
+        ],
+        "The videos are matched by series info and sorted with the specials last"
+    );
+}
@@ -1 +1,58 @@
+use std::any::Any;
+
+use futures::future;
+use stremio_derive::Model;
+use url::Url;
+
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::MetaDetails;
+use crate::runtime::EnvFutureExt;
+use crate::types::addon::{Descriptor, Manifest, ManifestResource, ResourceResponse};
+use crate::types::resource::MetaItem;
+use crate::unit_tests::{default_fetch_handler, FetchHandler, TestEnv};
+
+mod addon_priorities;
+mod localization;
+mod merge;
 mod override_selected;
+
+#[derive(Model, Default, Clone, Debug)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    meta_details: MetaDetails,
+}
+
+/// An addon which provides the resource for the type
+fn addon(transport_url: &str, r#type: &str, resource: &str) -> Descriptor {
+    Descriptor {
+        manifest: Manifest {
+            types: vec![r#type.to_owned()],
+            resources: vec![ManifestResource::Short(resource.to_owned())],
+            ..Default::default()
+        },
+        transport_url: Url::parse(transport_url).unwrap(),
+        flags: Default::default(),
+    }
+}
+
+fn meta_item(meta_item: serde_json::Value) -> Box<dyn Any + Send> {
+    Box::new(ResourceResponse::Meta {
+        meta: serde_json::from_value::<MetaItem>(meta_item).unwrap(),
+    })
+}
+
+/// Responds with the meta items by the url of the request and with no streams to the stream requests
+fn fetch_handler(meta_items: Vec<(&'static str, serde_json::Value)>) -> FetchHandler {
+    Box::new(
+        move |request| match meta_items.iter().find(|(url, _)| *url == request.url) {
+            Some((_, meta_item)) => future::ok(self::meta_item(meta_item.to_owned())).boxed_env(),
+            None if request.url.contains("/stream/") => future::ok(Box::new(
+                ResourceResponse::Streams { streams: vec![] },
+            )
+                as Box<dyn Any + Send>)
+            .boxed_env(),
+            None => default_fetch_handler(request),
+        },
+    )
+}
@@ -1,12 +1,23 @@
 mod env;
 pub use env::*;
 
+mod watch_party_loopback_transport;
+pub use watch_party_loopback_transport::*;
+
+mod addon_configure;
+mod calendar;
+mod cast_session;
 mod catalog_with_filters;
+mod catalogs_with_extra;
 mod ctx;
 mod data_export;
 mod deep_links;
+mod installed_addons_with_filters;
 mod link;
 mod meta_details;
 mod player;
 mod serde;
+mod stats;
 mod streaming_server;
+mod trailer_player;
+mod watch_party;
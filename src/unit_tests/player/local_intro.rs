@@ -0,0 +1,192 @@
+use chrono::{TimeZone, Utc};
+use stremio_derive::Model;
+
+use crate::{
+    constants::INTROS_STORAGE_KEY,
+    models::{
+        ctx::Ctx,
+        player::{Player, Selected},
+    },
+    runtime::{
+        msg::{Action, ActionPlayer},
+        Runtime, RuntimeAction,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest},
+        api::SeekLog,
+        intros::{IntroSample, IntrosBucket, SeriesIntros},
+        library::{LibraryItem, LibraryItemState},
+        player::IntroData,
+        resource::{SeriesInfo, Stream, StreamSource},
+    },
+    unit_tests::{TestEnv, STORAGE},
+};
+
+fn player(duration: u64) -> Player {
+    Player {
+        selected: Some(Selected {
+            stream: Stream {
+                source: StreamSource::Url {
+                    url: "https://source_url".parse().unwrap(),
+                },
+                name: None,
+                description: None,
+                thumbnail: None,
+                subtitles: vec![],
+                behavior_hints: Default::default(),
+            },
+            stream_request: Some(ResourceRequest {
+                base: "https://transport_url/manifest.json".parse().unwrap(),
+                path: ResourcePath::without_extra("stream", "series", "tt123456:1:2"),
+            }),
+            meta_request: Some(ResourceRequest {
+                base: "https://transport_url/manifest.json".parse().unwrap(),
+                path: ResourcePath::without_extra("meta", "series", "tt123456"),
+            }),
+            subtitles_path: None,
+        }),
+        series_info: Some(SeriesInfo {
+            season: 1,
+            episode: 2,
+        }),
+        library_item: Some(LibraryItem {
+            id: "tt123456".to_owned(),
+            name: "name".to_owned(),
+            r#type: "series".to_owned(),
+            poster: None,
+            poster_shape: Default::default(),
+            removed: false,
+            temp: false,
+            ctime: Some(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()),
+            mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+            state: LibraryItemState {
+                video_id: Some("tt123456:1:2".to_owned()),
+                time_offset: 600_000,
+                duration,
+                ..Default::default()
+            },
+            behavior_hints: Default::default(),
+        }),
+        seek_history: vec![SeekLog {
+            from: 60_000,
+            to: 150_000,
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn learn_intro_from_seek_history() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        player: Player,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let player = player(2_400_000);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::default(),
+            player,
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Unload,
+        })
+    });
+    let expected_samples = vec![IntroSample {
+        season: 1,
+        episode: 2,
+        duration: 2_400_000,
+        from: 60_000,
+        to: 150_000,
+    }];
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .intros
+            .items
+            .get("tt123456")
+            .map(|series_intros| &series_intros.samples),
+        Some(&expected_samples),
+        "Intro learned from the seek history"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(INTROS_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<IntrosBucket>(data).unwrap())
+            .is_some_and(|intros| intros.items.contains_key("tt123456")),
+        "Intros persisted"
+    );
+}
+
+#[test]
+fn detect_local_intro_on_duration_change() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        player: Player,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let sample = |episode| IntroSample {
+        season: 1,
+        episode,
+        duration: 2_400_000,
+        from: 60_000,
+        to: 150_000,
+    };
+    let mut ctx = Ctx::default();
+    ctx.intros.items.insert(
+        "tt123456".to_owned(),
+        SeriesIntros {
+            samples: vec![sample(1), sample(3)],
+            mtime: Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+        },
+    );
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx,
+            // the duration of the previous episode, which is much longer
+            player: player(5_400_000),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Player(ActionPlayer::TimeChanged {
+                time: 601_000,
+                duration: 2_400_000,
+                device: "device".to_owned(),
+            }),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .intro_outro
+            .as_ref()
+            .and_then(|intro_outro| intro_outro.intro.as_ref()),
+        Some(&IntroData {
+            from: 60_000,
+            to: 150_000,
+            duration: None,
+        }),
+        "The local intro is detected once the duration of the video is known"
+    );
+}
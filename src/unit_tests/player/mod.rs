@@ -1 +1,4 @@
+mod local_intro;
 mod next_stream;
+mod still_watching;
+mod up_next;
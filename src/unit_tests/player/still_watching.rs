@@ -0,0 +1,273 @@
+use std::any::Any;
+use std::iter;
+
+use chrono::Duration;
+use futures::{channel::mpsc::Receiver, future};
+use stremio_derive::Model;
+
+use crate::{
+    constants::STILL_WATCHING_AFTER_EPISODES,
+    models::{
+        ctx::Ctx,
+        player::{Player, Selected, SleepTimer},
+    },
+    runtime::{
+        msg::{Action, ActionLoad, ActionPlayer, Event},
+        Env, EnvError, EnvFutureExt, Runtime, RuntimeAction, RuntimeEvent, TryEnvFuture,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest, ResourceResponse},
+        resource::{MetaItem, MetaItemPreview, SeriesInfo, Stream, StreamSource, Video},
+    },
+    unit_tests::{Request, TestEnv, FETCH_HANDLER, NOW},
+};
+
+#[derive(Model, Default, Clone, Debug)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    player: Player,
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    match request {
+        Request { url, .. } if url == "https://transport_url/meta/series/tt123456.json" => {
+            future::ok(Box::new(ResourceResponse::Meta {
+                meta: MetaItem {
+                    preview: MetaItemPreview {
+                        id: "tt123456".to_owned(),
+                        r#type: "series".to_owned(),
+                        ..Default::default()
+                    },
+                    videos: (1..=6)
+                        .map(|episode| Video {
+                            id: format!("tt123456:1:{episode}"),
+                            series_info: Some(SeriesInfo { season: 1, episode }),
+                            ..Default::default()
+                        })
+                        .collect(),
+                },
+            }) as Box<dyn Any + Send>)
+            .boxed_env()
+        }
+        _ => future::err(EnvError::Fetch("Unhandled fetch request".to_owned())).boxed_env(),
+    }
+}
+
+fn create_selected(episode: u32) -> Selected {
+    Selected {
+        stream: Stream {
+            source: StreamSource::Url {
+                url: "https://source_url".parse().unwrap(),
+            },
+            name: None,
+            description: None,
+            thumbnail: None,
+            subtitles: vec![],
+            behavior_hints: Default::default(),
+        },
+        stream_request: Some(ResourceRequest {
+            base: "https://transport_url/manifest.json".parse().unwrap(),
+            path: ResourcePath::without_extra("stream", "series", &format!("tt123456:1:{episode}")),
+        }),
+        meta_request: Some(ResourceRequest {
+            base: "https://transport_url/manifest.json".parse().unwrap(),
+            path: ResourcePath::without_extra("meta", "series", "tt123456"),
+        }),
+        subtitles_path: None,
+    }
+}
+
+fn dispatch(runtime: &Runtime<TestEnv, TestModel>, action: Action) {
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action,
+        })
+    });
+}
+
+fn time_changed(time: u64) -> Action {
+    Action::Player(ActionPlayer::TimeChanged {
+        time,
+        duration: 2_400_000,
+        device: "web".to_owned(),
+    })
+}
+
+fn events(rx: &mut Receiver<RuntimeEvent<TestEnv, TestModel>>) -> Vec<Event> {
+    iter::from_fn(|| rx.try_next().ok().flatten())
+        .filter_map(|event| match event {
+            RuntimeEvent::CoreEvent(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_playing_next_video(events: &[Event]) -> Option<bool> {
+    events.iter().find_map(|event| match event {
+        Event::PlayerEnded {
+            is_playing_next_video,
+            ..
+        } => Some(*is_playing_next_video),
+        _ => None,
+    })
+}
+
+#[test]
+fn sleep_timer() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, mut rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    dispatch(
+        &runtime,
+        Action::Load(ActionLoad::Player(Box::new(create_selected(1)))),
+    );
+    dispatch(
+        &runtime,
+        Action::Player(ActionPlayer::StartSleepTimer { minutes: Some(30) }),
+    );
+    assert_eq!(
+        runtime.model().unwrap().player.sleep_timer,
+        Some(SleepTimer::Until {
+            time: TestEnv::now() + Duration::minutes(30)
+        }),
+        "Sleep timer started"
+    );
+    dispatch(&runtime, time_changed(60_000));
+    events(&mut rx);
+
+    *NOW.write().unwrap() = TestEnv::now() + Duration::minutes(31);
+    dispatch(&runtime, time_changed(120_000));
+    assert!(
+        events(&mut rx)
+            .iter()
+            .any(|event| matches!(event, Event::PlayerSleepTimerElapsed { .. })),
+        "Sleep timer elapsed"
+    );
+    assert_eq!(runtime.model().unwrap().player.sleep_timer, None);
+
+    dispatch(
+        &runtime,
+        Action::Player(ActionPlayer::StartSleepTimer { minutes: None }),
+    );
+    dispatch(&runtime, Action::Player(ActionPlayer::Ended));
+    let events = events(&mut rx);
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, Event::PlayerSleepTimerElapsed { .. })),
+        "Sleep timer elapsed at the end of the video"
+    );
+    assert_eq!(
+        is_playing_next_video(&events),
+        Some(false),
+        "The next video is not played"
+    );
+    assert_eq!(runtime.model().unwrap().player.sleep_timer, None);
+}
+
+#[test]
+fn still_watching() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, mut rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    dispatch(
+        &runtime,
+        Action::Load(ActionLoad::Player(Box::new(create_selected(1)))),
+    );
+    for episode in 2..=STILL_WATCHING_AFTER_EPISODES + 1 {
+        dispatch(&runtime, Action::Player(ActionPlayer::Ended));
+        assert_eq!(
+            is_playing_next_video(&events(&mut rx)),
+            Some(true),
+            "Binge watching plays the next video"
+        );
+        dispatch(
+            &runtime,
+            Action::Load(ActionLoad::Player(Box::new(create_selected(episode)))),
+        );
+        let requested = events(&mut rx)
+            .iter()
+            .any(|event| matches!(event, Event::PlayerStillWatchingRequested { .. }));
+        assert_eq!(
+            requested,
+            episode > STILL_WATCHING_AFTER_EPISODES,
+            "User is asked if they're still watching only after many auto-advanced episodes"
+        );
+        assert_eq!(
+            runtime.model().unwrap().player.still_watching_requested,
+            requested
+        );
+    }
+
+    dispatch(&runtime, time_changed(60_000));
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .library_item
+            .as_ref()
+            .map(|library_item| library_item.state.time_offset),
+        Some(0),
+        "Progress is not updated while unconfirmed"
+    );
+    dispatch(&runtime, Action::Player(ActionPlayer::Ended));
+    assert_eq!(
+        is_playing_next_video(&events(&mut rx)),
+        Some(false),
+        "The next video is not played while unconfirmed"
+    );
+
+    dispatch(&runtime, Action::Player(ActionPlayer::ConfirmStillWatching));
+    assert!(!runtime.model().unwrap().player.still_watching_requested);
+    dispatch(&runtime, time_changed(60_000));
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .library_item
+            .as_ref()
+            .map(|library_item| library_item.state.time_offset),
+        Some(60_000),
+        "Progress is updated once confirmed"
+    );
+}
+
+#[test]
+fn still_watching_closed_after_ended() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel::default(), vec![], 1000);
+    dispatch(
+        &runtime,
+        Action::Load(ActionLoad::Player(Box::new(create_selected(1)))),
+    );
+    dispatch(&runtime, Action::Player(ActionPlayer::Ended));
+    dispatch(&runtime, Action::Player(ActionPlayer::NextVideo));
+    dispatch(&runtime, Action::Unload);
+    dispatch(
+        &runtime,
+        Action::Load(ActionLoad::Player(Box::new(create_selected(2)))),
+    );
+    assert_eq!(
+        runtime.model().unwrap().player.auto_advanced_count,
+        1,
+        "The requested next video is auto-advanced to after unloading the player"
+    );
+
+    dispatch(&runtime, Action::Player(ActionPlayer::Ended));
+    dispatch(&runtime, Action::Unload);
+    assert!(!runtime.model().unwrap().player.auto_advancing);
+    dispatch(
+        &runtime,
+        Action::Load(ActionLoad::Player(Box::new(create_selected(4)))),
+    );
+    assert_eq!(
+        runtime.model().unwrap().player.auto_advanced_count,
+        0,
+        "The video loaded after closing the ended one is not auto-advanced to"
+    );
+}
@@ -0,0 +1,183 @@
+use std::any::Any;
+
+use futures::future;
+use stremio_derive::Model;
+
+use crate::{
+    constants::PLAY_QUEUE_STORAGE_KEY,
+    models::{
+        ctx::Ctx,
+        player::{Player, Selected},
+    },
+    runtime::{
+        msg::{Action, ActionCtx, ActionLoad},
+        EnvError, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest, ResourceResponse},
+        play_queue::{PlayQueueBucket, PlayQueueItem},
+        profile::{Profile, Settings},
+        resource::{
+            MetaItem, MetaItemPreview, SeriesInfo, Stream, StreamBehaviorHints, StreamSource, Video,
+        },
+    },
+    unit_tests::{Request, TestEnv, FETCH_HANDLER, STORAGE},
+};
+
+fn create_stream(binge_group: &str) -> Stream {
+    Stream {
+        source: StreamSource::Url {
+            url: "https://source_url".parse().unwrap(),
+        },
+        name: None,
+        description: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: StreamBehaviorHints {
+            binge_group: Some(binge_group.to_owned()),
+            ..Default::default()
+        },
+    }
+}
+
+fn create_selected(r#type: &str, meta_id: &str, video_id: &str) -> Selected {
+    Selected {
+        stream: create_stream("binge_group"),
+        stream_request: Some(ResourceRequest {
+            base: "https://transport_url/manifest.json".parse().unwrap(),
+            path: ResourcePath::without_extra("stream", r#type, video_id),
+        }),
+        meta_request: Some(ResourceRequest {
+            base: "https://transport_url/manifest.json".parse().unwrap(),
+            path: ResourcePath::without_extra("meta", r#type, meta_id),
+        }),
+        subtitles_path: None,
+    }
+}
+
+#[test]
+fn up_next() {
+    #[derive(Model, Default, Clone, Debug)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        player: Player,
+    }
+
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+        match request {
+            Request { url, .. } if url == "https://transport_url/meta/series/tt123456.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        preview: MetaItemPreview {
+                            id: "tt123456".to_owned(),
+                            r#type: "series".to_owned(),
+                            ..Default::default()
+                        },
+                        videos: vec![Video {
+                            id: "tt123456:1:1".to_owned(),
+                            series_info: Some(SeriesInfo {
+                                season: 1,
+                                episode: 1,
+                            }),
+                            ..Default::default()
+                        }],
+                    },
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            Request { url, .. } if url == "https://transport_url/stream/movie/tt654321.json" => {
+                future::ok(Box::new(ResourceResponse::Streams {
+                    streams: vec![create_stream("binge_group_1"), create_stream("binge_group")],
+                }) as Box<dyn Any + Send>)
+                .boxed_env()
+            }
+            _ => future::err(EnvError::Fetch("Unhandled fetch request".to_owned())).boxed_env(),
+        }
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+
+    let queued_movie = PlayQueueItem {
+        r#type: "movie".to_owned(),
+        meta_id: "tt654321".to_owned(),
+        video_id: "tt654321".to_owned(),
+        meta_transport_url: "https://transport_url/manifest.json".parse().unwrap(),
+        name: "Movie".to_owned(),
+    };
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    settings: Settings {
+                        binge_watching: true,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            player: Player::default(),
+        },
+        vec![],
+        1000,
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::AddToPlayQueue(queued_movie.to_owned())),
+        });
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Box::new(create_selected(
+                "series",
+                "tt123456",
+                "tt123456:1:1",
+            )))),
+        });
+    });
+    {
+        let model = runtime.model().unwrap();
+        assert_eq!(
+            model.player.next_video, None,
+            "The last episode has no next video"
+        );
+        assert_eq!(
+            model.player.up_next,
+            Some(queued_movie.to_owned()),
+            "The head of the play queue is up next"
+        );
+        assert_eq!(
+            model.player.up_next_stream,
+            Some(create_stream("binge_group")),
+            "The stream from the same binge group is picked"
+        );
+    }
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Box::new(create_selected(
+                "movie", "tt654321", "tt654321",
+            )))),
+        });
+    });
+    let model = runtime.model().unwrap();
+    assert!(
+        model.ctx.play_queue.items.is_empty(),
+        "The played video is dequeued"
+    );
+    assert_eq!(model.player.up_next, None);
+    assert_eq!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(PLAY_QUEUE_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<PlayQueueBucket>(data).unwrap())
+            .map(|bucket| bucket.items),
+        Some(vec![]),
+        "The play queue is persisted"
+    );
+}
@@ -1,5 +1,5 @@
 use crate::types::api::{APIRequest, AuthRequest};
-use crate::types::profile::AuthKey;
+use crate::types::profile::{AuthKey, BoardLayout};
 use crate::unit_tests::serde::default_tokens_ext::DefaultTokens;
 use serde_test::{assert_ser_tokens, Token};
 
@@ -18,6 +18,7 @@ fn api_request() {
             APIRequest::AddonCollectionSet {
                 auth_key: AuthKey::default(),
                 addons: vec![],
+                board_layout: BoardLayout::default(),
             },
         ],
         &[
@@ -50,7 +51,7 @@ fn api_request() {
                 Token::StructEnd,
                 Token::Struct {
                     name: "APIRequest",
-                    len: 3,
+                    len: 4,
                 },
                 Token::Str("type"),
                 Token::Str("AddonCollectionSet"),
@@ -61,9 +62,10 @@ fn api_request() {
                 Token::Str("addons"),
                 Token::Seq { len: Some(0) },
                 Token::SeqEnd,
-                Token::StructEnd,
-                Token::SeqEnd,
+                Token::Str("boardLayout"),
             ],
+            BoardLayout::default_tokens(),
+            vec![Token::StructEnd, Token::SeqEnd],
         ]
         .concat(),
     );
@@ -13,6 +13,7 @@ mod cft_test {
             &CollectionResponse {
                 addons: vec![],
                 last_modified,
+                board_layout: None,
             },
             &[
                 Token::Struct {
@@ -3,7 +3,7 @@ use crate::types::addon::{
 };
 use crate::types::api::{APIError, AuthRequest};
 use crate::types::library::LibraryItemState;
-use crate::types::profile::{Auth, AuthKey, GDPRConsent, Settings, User};
+use crate::types::profile::{Auth, AuthKey, BoardLayout, GDPRConsent, Settings, User};
 use crate::types::resource::{
     MetaItem, MetaItemBehaviorHints, PosterShape, SeriesInfo, StreamBehaviorHints, StreamSource,
     Subtitles,
@@ -367,12 +367,30 @@ impl DefaultTokens for Auth {
     }
 }
 
+impl DefaultTokens for BoardLayout {
+    fn default_tokens() -> Vec<Token> {
+        vec![
+            Token::Struct {
+                name: "BoardLayout",
+                len: 2,
+            },
+            Token::Str("order"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("hidden"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+        ]
+    }
+}
+
 impl DefaultTokens for Settings {
     fn default_tokens() -> Vec<Token> {
         vec![
             Token::Struct {
                 name: "Settings",
-                len: 27,
+                len: 31,
             },
             Token::Str("interfaceLanguage"),
             Token::Str("eng"),
@@ -433,6 +451,29 @@ impl DefaultTokens for Settings {
             Token::Bool(false),
             Token::Str("streamingServerWarningDismissed"),
             Token::None,
+            Token::Str("notificationRules"),
+            Token::Struct {
+                name: "NotificationRules",
+                len: 5,
+            },
+            Token::Str("watchedWithinDays"),
+            Token::None,
+            Token::Str("onlyCaughtUp"),
+            Token::Bool(false),
+            Token::Str("maxVideoAgeDays"),
+            Token::None,
+            Token::Str("quietHours"),
+            Token::None,
+            Token::Str("checkTtlHours"),
+            Token::U32(6),
+            Token::StructEnd,
+            Token::Str("autoUpgradeAddons"),
+            Token::Bool(false),
+            Token::Str("addonPriorities"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("trailerAutoplay"),
+            Token::Bool(false),
             Token::StructEnd,
         ]
     }
@@ -1,4 +1,4 @@
-use crate::types::addon::{Manifest, ManifestBehaviorHints};
+use crate::types::addon::{Manifest, ManifestBehaviorHints, ManifestConfig, ManifestConfigType};
 use crate::unit_tests::serde::default_tokens_ext::DefaultTokens;
 use semver::Version;
 use serde_test::{assert_de_tokens, assert_ser_tokens, Configure, Token};
@@ -22,6 +22,7 @@ fn manifest() {
                 catalogs: vec![],
                 addon_catalogs: vec![],
                 behavior_hints: ManifestBehaviorHints::default(),
+                config: vec![],
             },
             Manifest {
                 id: "id".to_owned(),
@@ -37,6 +38,7 @@ fn manifest() {
                 catalogs: vec![],
                 addon_catalogs: vec![],
                 behavior_hints: ManifestBehaviorHints::default(),
+                config: vec![],
             },
         ]
         .readable(),
@@ -143,6 +145,7 @@ fn manifest() {
                 catalogs: vec![],
                 addon_catalogs: vec![],
                 behavior_hints: ManifestBehaviorHints::default(),
+                config: vec![],
             },
             Manifest {
                 id: "id".to_owned(),
@@ -158,6 +161,7 @@ fn manifest() {
                 catalogs: vec![],
                 addon_catalogs: vec![],
                 behavior_hints: ManifestBehaviorHints::default(),
+                config: vec![],
             },
         ]
         .readable(),
@@ -230,3 +234,40 @@ fn manifest() {
         .concat(),
     );
 }
+
+#[test]
+fn manifest_config_de_invalid() {
+    let manifest = |config: serde_json::Value| {
+        serde_json::from_value::<Manifest>(serde_json::json!({
+            "id": "id",
+            "version": "0.0.1",
+            "name": "name",
+            "types": [],
+            "resources": [],
+            "config": config,
+        }))
+        .expect("Manifest should deserialize")
+        .config
+    };
+    assert_eq!(
+        manifest(serde_json::json!([
+            { "key": "token", "type": "password" },
+            { "key": "unknown", "type": "unknown" },
+            { "type": "text" },
+        ])),
+        vec![ManifestConfig {
+            key: "token".to_owned(),
+            r#type: ManifestConfigType::Password,
+            title: None,
+            options: vec![],
+            default: None,
+            required: false,
+        }],
+        "Invalid settings are skipped"
+    );
+    assert_eq!(
+        manifest(serde_json::json!({ "key": "token" })),
+        vec![],
+        "Invalid config is ignored"
+    );
+}
@@ -1,4 +1,4 @@
-use crate::types::profile::{Auth, Profile, Settings};
+use crate::types::profile::{Auth, BoardLayout, Profile, Settings};
 use crate::unit_tests::serde::default_tokens_ext::DefaultTokens;
 use serde_test::{assert_de_tokens, assert_tokens, Configure, Token};
 
@@ -11,12 +11,14 @@ fn profile() {
                 addons: vec![],
                 addons_locked: false,
                 settings: Settings::default(),
+                board_layout: BoardLayout::default(),
             },
             Profile {
                 auth: None,
                 addons: vec![],
                 addons_locked: false,
                 settings: Settings::default(),
+                board_layout: BoardLayout::default(),
             },
         ]
         .readable(),
@@ -25,7 +27,7 @@ fn profile() {
                 Token::Seq { len: Some(2) },
                 Token::Struct {
                     name: "Profile",
-                    len: 4,
+                    len: 5,
                 },
                 Token::Str("auth"),
                 Token::Some,
@@ -40,11 +42,13 @@ fn profile() {
                 Token::Str("settings"),
             ],
             Settings::default_tokens(),
+            vec![Token::Str("boardLayout")],
+            BoardLayout::default_tokens(),
             vec![
                 Token::StructEnd,
                 Token::Struct {
                     name: "Profile",
-                    len: 4,
+                    len: 5,
                 },
                 Token::Str("auth"),
                 Token::None,
@@ -56,6 +60,8 @@ fn profile() {
                 Token::Str("settings"),
             ],
             Settings::default_tokens(),
+            vec![Token::Str("boardLayout")],
+            BoardLayout::default_tokens(),
             vec![Token::StructEnd, Token::SeqEnd],
         ]
         .concat(),
@@ -66,6 +72,7 @@ fn profile() {
             addons: vec![],
             addons_locked: false,
             settings: Settings::default(),
+            board_layout: BoardLayout::default(),
         }
         .readable(),
         &[
@@ -1,6 +1,9 @@
-use crate::types::profile::{FrameRateMatchingStrategy, Settings};
+use crate::types::profile::{
+    AddonResourcePriority, FrameRateMatchingStrategy, NotificationRules, QuietHours, Settings,
+};
 use chrono::{TimeZone, Utc};
 use serde_test::{assert_de_tokens, assert_tokens, Token};
+use std::collections::HashMap;
 use url::Url;
 
 #[test]
@@ -36,11 +39,27 @@ fn settings() {
             streaming_server_warning_dismissed: Some(
                 Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
             ),
+            notification_rules: NotificationRules {
+                watched_within_days: Some(30),
+                only_caught_up: true,
+                max_video_age_days: Some(7),
+                quiet_hours: Some(QuietHours { start: 22, end: 7 }),
+                check_ttl_hours: 12,
+            },
+            auto_upgrade_addons: true,
+            addon_priorities: HashMap::from([(
+                "stream".to_owned(),
+                AddonResourcePriority {
+                    order: vec![Url::parse("https://addon.com/manifest.json").unwrap()],
+                    disabled: vec![],
+                },
+            )]),
+            trailer_autoplay: true,
         },
         &[
             Token::Struct {
                 name: "Settings",
-                len: 27,
+                len: 31,
             },
             Token::Str("interfaceLanguage"),
             Token::Str("interface_language"),
@@ -105,6 +124,53 @@ fn settings() {
             Token::Str("streamingServerWarningDismissed"),
             Token::Some,
             Token::Str("2021-01-01T00:00:00Z"),
+            Token::Str("notificationRules"),
+            Token::Struct {
+                name: "NotificationRules",
+                len: 5,
+            },
+            Token::Str("watchedWithinDays"),
+            Token::Some,
+            Token::U32(30),
+            Token::Str("onlyCaughtUp"),
+            Token::Bool(true),
+            Token::Str("maxVideoAgeDays"),
+            Token::Some,
+            Token::U32(7),
+            Token::Str("quietHours"),
+            Token::Some,
+            Token::Struct {
+                name: "QuietHours",
+                len: 2,
+            },
+            Token::Str("start"),
+            Token::U8(22),
+            Token::Str("end"),
+            Token::U8(7),
+            Token::StructEnd,
+            Token::Str("checkTtlHours"),
+            Token::U32(12),
+            Token::StructEnd,
+            Token::Str("autoUpgradeAddons"),
+            Token::Bool(true),
+            Token::Str("addonPriorities"),
+            Token::Map { len: Some(1) },
+            Token::Str("stream"),
+            Token::Struct {
+                name: "AddonResourcePriority",
+                len: 2,
+            },
+            Token::Str("order"),
+            Token::Seq { len: Some(1) },
+            Token::Str("https://addon.com/manifest.json"),
+            Token::SeqEnd,
+            Token::Str("disabled"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+            Token::MapEnd,
+            Token::Str("trailerAutoplay"),
+            Token::Bool(true),
             Token::StructEnd,
         ],
     );
@@ -117,7 +183,7 @@ fn settings_de() {
         &[
             Token::Struct {
                 name: "Settings",
-                len: 22,
+                len: 23,
             },
             Token::Str("interfaceLanguage"),
             Token::Str("eng"),
@@ -174,6 +240,29 @@ fn settings_de() {
             Token::Bool(false),
             Token::Str("streamingServerWarningDismissed"),
             Token::None,
+            Token::Str("notificationRules"),
+            Token::Struct {
+                name: "NotificationRules",
+                len: 5,
+            },
+            Token::Str("watchedWithinDays"),
+            Token::None,
+            Token::Str("onlyCaughtUp"),
+            Token::Bool(false),
+            Token::Str("maxVideoAgeDays"),
+            Token::None,
+            Token::Str("quietHours"),
+            Token::None,
+            Token::Str("checkTtlHours"),
+            Token::U32(6),
+            Token::StructEnd,
+            Token::Str("autoUpgradeAddons"),
+            Token::Bool(false),
+            Token::Str("addonPriorities"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("trailerAutoplay"),
+            Token::Bool(false),
             Token::StructEnd,
         ],
     );
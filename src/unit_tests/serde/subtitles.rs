@@ -5,11 +5,22 @@ use url::Url;
 #[test]
 fn subtitles() {
     assert_tokens(
-        &Subtitles {
-            lang: "lang".to_owned(),
-            url: Url::parse("https://url").unwrap(),
-        },
+        &vec![
+            Subtitles {
+                lang: "lang".to_owned(),
+                url: Url::parse("https://url").unwrap(),
+                video_hash: None,
+                video_size: None,
+            },
+            Subtitles {
+                lang: "lang".to_owned(),
+                url: Url::parse("https://url").unwrap(),
+                video_hash: Some("video_hash".to_owned()),
+                video_size: Some(1),
+            },
+        ],
         &[
+            Token::Seq { len: Some(2) },
             Token::Struct {
                 name: "Subtitles",
                 len: 2,
@@ -19,6 +30,22 @@ fn subtitles() {
             Token::Str("url"),
             Token::Str("https://url/"),
             Token::StructEnd,
+            Token::Struct {
+                name: "Subtitles",
+                len: 4,
+            },
+            Token::Str("lang"),
+            Token::Str("lang"),
+            Token::Str("url"),
+            Token::Str("https://url/"),
+            Token::Str("videoHash"),
+            Token::Some,
+            Token::Str("video_hash"),
+            Token::Str("videoSize"),
+            Token::Some,
+            Token::U64(1),
+            Token::StructEnd,
+            Token::SeqEnd,
         ],
     );
 }
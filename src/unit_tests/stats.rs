@@ -0,0 +1,270 @@
+use std::any::Any;
+
+use chrono::{Duration, TimeZone, Utc};
+use futures::future;
+use semver::Version;
+use stremio_derive::Model;
+use stremio_watched_bitfield::WatchedBitField;
+use url::Url;
+
+use crate::{
+    constants::{GENRES_LINK_CATEGORY, META_RESOURCE_NAME},
+    models::{
+        ctx::Ctx,
+        stats::{Genre, Period, Stats, Streak},
+    },
+    runtime::{
+        msg::{Action, ActionCtx, ActionLoad},
+        Env, EnvError, EnvFutureExt, Runtime, RuntimeAction, TryEnvFuture,
+    },
+    types::{
+        addon::{Descriptor, Manifest, ResourceResponse},
+        library::{LibraryBucket, LibraryItem, LibraryItemState},
+        profile::Profile,
+        resource::{Link, MetaItem, MetaItemPreview, SeriesInfo, StreamSource, Video},
+        watch_history::{WatchHistoryBucket, WatchHistoryItem},
+    },
+    unit_tests::{Request, TestEnv, FETCH_HANDLER, NOW},
+};
+
+fn videos() -> Vec<Video> {
+    (1..=2)
+        .map(|episode| Video {
+            id: format!("tt1:1:{episode}"),
+            series_info: Some(SeriesInfo { season: 1, episode }),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn meta_item(id: &str, r#type: &str, genres: &[&str], videos: Vec<Video>) -> MetaItem {
+    MetaItem {
+        preview: MetaItemPreview {
+            id: id.to_owned(),
+            r#type: r#type.to_owned(),
+            links: genres
+                .iter()
+                .map(|genre| Link {
+                    name: genre.to_string(),
+                    category: GENRES_LINK_CATEGORY.to_owned(),
+                    url: "stremio:///discover".parse().unwrap(),
+                })
+                .collect(),
+            ..Default::default()
+        },
+        videos,
+    }
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any + Send>> {
+    let meta = match request.url.as_str() {
+        "https://addon.com/meta/series/tt1.json" => {
+            meta_item("tt1", "series", &["Drama"], videos())
+        }
+        "https://addon.com/meta/movie/tt2.json" => {
+            meta_item("tt2", "movie", &["Drama", "Comedy"], vec![])
+        }
+        _ => return future::err(EnvError::Fetch("Unhandled fetch request".to_owned())).boxed_env(),
+    };
+    future::ok(Box::new(ResourceResponse::Meta { meta }) as Box<dyn Any + Send>).boxed_env()
+}
+
+fn library_item(id: &str, r#type: &str, state: LibraryItemState) -> LibraryItem {
+    LibraryItem {
+        id: id.to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()),
+        mtime: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        state,
+        name: id.to_owned(),
+        r#type: r#type.to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    }
+}
+
+fn session(id: &str, days_ago: i64, time_watched: u64) -> WatchHistoryItem {
+    let start = TestEnv::now() - Duration::days(days_ago);
+    WatchHistoryItem {
+        id: id.to_owned(),
+        meta_id: "tt1".to_owned(),
+        r#type: "series".to_owned(),
+        name: "tt1".to_owned(),
+        poster: None,
+        video_id: "tt1:1:1".to_owned(),
+        stream_source: StreamSource::Url {
+            url: "https://source_url".parse().unwrap(),
+        },
+        start,
+        end: start + Duration::hours(1),
+        from: 0,
+        to: time_watched,
+        time_watched,
+        duration: 3_600_000,
+        device: "web".to_owned(),
+    }
+}
+
+#[test]
+fn stats() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        stats: Stats,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    // a Wednesday
+    *NOW.write().unwrap() = Utc.with_ymd_and_hms(2024, 5, 15, 12, 0, 0).unwrap();
+    let addon = Descriptor {
+        manifest: Manifest {
+            id: "addon".to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "Addon".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["movie".to_owned(), "series".to_owned()],
+            resources: vec![META_RESOURCE_NAME.into()],
+            id_prefixes: Some(vec!["tt".to_owned()]),
+            catalogs: vec![],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+            config: vec![],
+        },
+        transport_url: Url::parse("https://addon.com/manifest.json").unwrap(),
+        flags: Default::default(),
+    };
+    let series = library_item(
+        "tt1",
+        "series",
+        LibraryItemState {
+            overall_time_watched: 7_200_000,
+            times_watched: 2,
+            last_watched: Some(TestEnv::now()),
+            watched: Some(
+                WatchedBitField::construct_from_array(
+                    vec![true, true],
+                    videos().into_iter().map(|video| video.id).collect(),
+                )
+                .into(),
+            ),
+            ..Default::default()
+        },
+    );
+    let movie = library_item(
+        "tt2",
+        "movie",
+        LibraryItemState {
+            overall_time_watched: 5_400_000,
+            times_watched: 1,
+            flagged_watched: 1,
+            ..Default::default()
+        },
+    );
+    let unwatched = library_item("tt3", "movie", LibraryItemState::default());
+    let mut watch_history = WatchHistoryBucket::default();
+    for (id, days_ago) in [("1", 0), ("2", 1), ("3", 2), ("4", 5), ("5", 9)] {
+        watch_history.add(session(id, days_ago, 600_000));
+    }
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![addon],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![series, movie, unwatched]),
+                watch_history,
+                ..Default::default()
+            },
+            stats: Stats::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Stats),
+        })
+    });
+    {
+        let stats = &runtime.model().unwrap().stats;
+        assert_eq!(stats.totals.time_watched, 12_600_000);
+        assert_eq!(stats.totals.times_watched, 3);
+        assert_eq!(
+            stats.totals.movies_watched, 1,
+            "Unwatched items are ignored"
+        );
+        assert_eq!(stats.totals.series_watched, 1);
+        assert_eq!(stats.totals.videos_watched, 2);
+        assert_eq!(
+            stats.top_genres,
+            vec![
+                Genre {
+                    name: "Drama".to_owned(),
+                    time_watched: 12_600_000,
+                    items: 2,
+                },
+                Genre {
+                    name: "Comedy".to_owned(),
+                    time_watched: 5_400_000,
+                    items: 1,
+                },
+            ]
+        );
+        assert_eq!(stats.completed_series, vec!["tt1".to_owned()]);
+        assert_eq!(
+            stats.weeks,
+            vec![
+                Period {
+                    start: Utc
+                        .with_ymd_and_hms(2024, 5, 13, 0, 0, 0)
+                        .unwrap()
+                        .date_naive(),
+                    time_watched: 1_800_000,
+                    sessions: 3,
+                },
+                Period {
+                    start: Utc
+                        .with_ymd_and_hms(2024, 5, 6, 0, 0, 0)
+                        .unwrap()
+                        .date_naive(),
+                    time_watched: 1_200_000,
+                    sessions: 2,
+                },
+            ],
+            "Sessions are totaled per week"
+        );
+        assert_eq!(stats.years.len(), 1);
+        assert_eq!(stats.years[0].time_watched, 3_000_000);
+        assert_eq!(
+            stats.streak,
+            Streak {
+                current: 3,
+                longest: 3
+            }
+        );
+    }
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::LibraryItemMarkAsWatched {
+                id: "tt1".to_owned(),
+                is_watched: false,
+            }),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().stats.totals.times_watched,
+        1,
+        "Stats of the changed item are recomputed"
+    );
+}
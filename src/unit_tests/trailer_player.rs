@@ -0,0 +1,112 @@
+use stremio_derive::Model;
+use url::Url;
+
+use crate::constants::PROFILE_STORAGE_KEY;
+
+use crate::models::ctx::Ctx;
+use crate::models::trailer_player::{Selected, TrailerPlayer};
+use crate::runtime::msg::{Action, ActionCtx, ActionLoad};
+use crate::runtime::{Runtime, RuntimeAction};
+use crate::types::profile::{Profile, Settings};
+use crate::types::resource::{Stream, StreamSource};
+use crate::unit_tests::{TestEnv, REQUESTS, STORAGE};
+
+fn youtube_trailer() -> Stream {
+    Stream {
+        source: StreamSource::YouTube {
+            yt_id: "aqz-KE-bpKQ".to_owned(),
+        },
+        name: None,
+        description: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: Default::default(),
+    }
+}
+
+#[test]
+fn trailer_player() {
+    #[derive(Model, Clone, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        trailer_player: TrailerPlayer,
+    }
+
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile::default(),
+                ..Default::default()
+            },
+            trailer_player: Default::default(),
+        },
+        vec![],
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::TrailerPlayer(Selected {
+                stream: youtube_trailer(),
+                autoplay: true,
+            })),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().trailer_player.stream_url,
+        None,
+        "The trailer autoplay is disabled by default"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UpdateSettings(Settings {
+                trailer_autoplay: true,
+                ..Default::default()
+            })),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().trailer_player.stream_url,
+        Some(Url::parse("http://127.0.0.1:11470/yt/aqz-KE-bpKQ").unwrap()),
+        "YouTube trailers are played through the streaming server"
+    );
+
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::TrailerPlayer(Selected {
+                stream: Stream {
+                    source: StreamSource::Url {
+                        url: Url::parse("https://trailers.com/trailer.mp4").unwrap(),
+                    },
+                    ..youtube_trailer()
+                },
+                autoplay: false,
+            })),
+        });
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Unload,
+        });
+    });
+    let model = runtime.model().unwrap();
+    assert!(model.trailer_player.selected.is_none());
+    assert!(model.trailer_player.stream_url.is_none());
+    assert!(
+        model.ctx.library.items.is_empty() && model.ctx.streams.items.is_empty(),
+        "The playback of trailers is not stored"
+    );
+    assert!(REQUESTS.read().unwrap().is_empty());
+    assert!(
+        !STORAGE
+            .read()
+            .unwrap()
+            .keys()
+            .any(|key| key != PROFILE_STORAGE_KEY),
+        "Only the settings are persisted"
+    );
+}
@@ -0,0 +1,290 @@
+use std::iter;
+
+use chrono::Duration;
+use futures::channel::mpsc::Receiver;
+use stremio_derive::Model;
+
+use crate::{
+    models::{
+        ctx::Ctx,
+        player::{Player, Selected as PlayerSelected},
+        watch_party::{Selected, WatchParty},
+    },
+    runtime::Env,
+    runtime::{
+        msg::{Action, ActionLoad, ActionPlayer, ActionWatchParty, Event},
+        Runtime, RuntimeAction, RuntimeEvent,
+    },
+    types::{
+        addon::{ResourcePath, ResourceRequest},
+        resource::{Stream, StreamSource},
+        watch_party::WatchPartyCommand,
+    },
+    unit_tests::{TestEnv, NOW, PARTIES},
+};
+
+#[derive(Model, Clone, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    player: Player,
+    watch_party: WatchParty,
+}
+
+fn create_runtime() -> (
+    Runtime<TestEnv, TestModel>,
+    Receiver<RuntimeEvent<TestEnv, TestModel>>,
+) {
+    Runtime::<TestEnv, _>::new(
+        TestModel {
+            player: Player {
+                selected: Some(PlayerSelected {
+                    stream: Stream {
+                        source: StreamSource::Url {
+                            url: "https://source_url".parse().unwrap(),
+                        },
+                        name: None,
+                        description: None,
+                        thumbnail: None,
+                        subtitles: vec![],
+                        behavior_hints: Default::default(),
+                    },
+                    stream_request: Some(ResourceRequest {
+                        base: "https://transport_url/manifest.json".parse().unwrap(),
+                        path: ResourcePath::without_extra("stream", "movie", "tt123456"),
+                    }),
+                    meta_request: None,
+                    subtitles_path: None,
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        vec![],
+        1000,
+    )
+}
+
+fn dispatch(runtime: &Runtime<TestEnv, TestModel>, action: Action) {
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action,
+        })
+    });
+}
+
+fn received_commands(
+    rx: &mut Receiver<RuntimeEvent<TestEnv, TestModel>>,
+) -> Vec<(String, WatchPartyCommand)> {
+    iter::from_fn(|| rx.try_next().ok().flatten())
+        .filter_map(|event| match event {
+            RuntimeEvent::CoreEvent(Event::WatchPartyCommandReceived { member_id, command }) => {
+                Some((member_id, command))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn watch_party_sync() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let party_url = "loopback://party/watch_party_sync"
+        .parse::<url::Url>()
+        .unwrap();
+    let (runtime_a, mut rx_a) = create_runtime();
+    let (runtime_b, mut rx_b) = create_runtime();
+    for (runtime, member_id) in [(&runtime_a, "a"), (&runtime_b, "b")] {
+        dispatch(
+            runtime,
+            Action::Load(ActionLoad::WatchParty(Selected {
+                party_url: party_url.to_owned(),
+                member_id: member_id.to_owned(),
+            })),
+        );
+    }
+    {
+        let model = runtime_b.model().unwrap();
+        assert_eq!(
+            model
+                .watch_party
+                .members
+                .iter()
+                .map(|member| member.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"],
+            "Both members joined"
+        );
+        assert_eq!(
+            model.watch_party.leader.as_deref(),
+            Some("a"),
+            "The first member leads the party"
+        );
+    }
+
+    dispatch(
+        &runtime_b,
+        Action::Player(ActionPlayer::TimeChanged {
+            time: 10_000,
+            duration: 6_000_000,
+            device: "web".to_owned(),
+        }),
+    );
+    dispatch(
+        &runtime_a,
+        Action::Player(ActionPlayer::PausedChanged { paused: false }),
+    );
+    dispatch(
+        &runtime_a,
+        Action::Player(ActionPlayer::TimeChanged {
+            time: 60_000,
+            duration: 6_000_000,
+            device: "web".to_owned(),
+        }),
+    );
+    dispatch(&runtime_b, Action::WatchParty(ActionWatchParty::Sync));
+    assert_eq!(
+        received_commands(&mut rx_b),
+        vec![("a".to_owned(), WatchPartyCommand::Seek { time: 60_000 })],
+        "The drift from the leader is corrected"
+    );
+
+    let sent_messages = || {
+        PARTIES
+            .read()
+            .unwrap()
+            .get(&party_url)
+            .map_or(0, |envelopes| envelopes.len())
+    };
+    let sent_messages_before = sent_messages();
+    dispatch(
+        &runtime_b,
+        Action::Player(ActionPlayer::TimeChanged {
+            time: 60_000,
+            duration: 6_000_000,
+            device: "web".to_owned(),
+        }),
+    );
+    assert!(
+        runtime_b.model().unwrap().watch_party.pending.is_empty(),
+        "The applied command is no longer pending"
+    );
+    dispatch(
+        &runtime_b,
+        Action::Player(ActionPlayer::TimeChanged {
+            time: 61_000,
+            duration: 6_000_000,
+            device: "web".to_owned(),
+        }),
+    );
+    assert_eq!(
+        sent_messages(),
+        sent_messages_before,
+        "The playback is reported only once in a few seconds"
+    );
+    dispatch(
+        &runtime_a,
+        Action::Player(ActionPlayer::TimeChanged {
+            time: 120_000,
+            duration: 6_000_000,
+            device: "web".to_owned(),
+        }),
+    );
+    dispatch(
+        &runtime_a,
+        Action::Player(ActionPlayer::PausedChanged { paused: true }),
+    );
+    dispatch(&runtime_a, Action::WatchParty(ActionWatchParty::Sync));
+    assert_eq!(
+        received_commands(&mut rx_a),
+        vec![],
+        "Applying a received command is not sent back to the party"
+    );
+    dispatch(&runtime_b, Action::WatchParty(ActionWatchParty::Sync));
+    assert_eq!(
+        received_commands(&mut rx_b),
+        vec![
+            ("a".to_owned(), WatchPartyCommand::Seek { time: 120_000 }),
+            ("a".to_owned(), WatchPartyCommand::Pause { time: 120_000 }),
+        ],
+        "Seeking and pausing is sent to the party"
+    );
+
+    dispatch(&runtime_a, Action::Unload);
+    dispatch(&runtime_b, Action::WatchParty(ActionWatchParty::Sync));
+    let model = runtime_b.model().unwrap();
+    assert_eq!(
+        model
+            .watch_party
+            .members
+            .iter()
+            .map(|member| member.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["b"],
+        "The leader left the party"
+    );
+    assert_eq!(
+        model.watch_party.leader.as_deref(),
+        Some("b"),
+        "A new leader is elected"
+    );
+}
+
+#[test]
+fn watch_party_dropped_command() {
+    let _env_mutex = TestEnv::reset().expect("Should have exclusive lock to TestEnv");
+    let party_url = "loopback://party/watch_party_dropped_command"
+        .parse::<url::Url>()
+        .unwrap();
+    let (runtime_a, mut rx_a) = create_runtime();
+    let (runtime_b, mut rx_b) = create_runtime();
+    for (runtime, member_id) in [(&runtime_a, "a"), (&runtime_b, "b")] {
+        dispatch(
+            runtime,
+            Action::Load(ActionLoad::WatchParty(Selected {
+                party_url: party_url.to_owned(),
+                member_id: member_id.to_owned(),
+            })),
+        );
+    }
+    for runtime in [&runtime_a, &runtime_b] {
+        dispatch(
+            runtime,
+            Action::Player(ActionPlayer::PausedChanged { paused: false }),
+        );
+        dispatch(
+            runtime,
+            Action::Player(ActionPlayer::TimeChanged {
+                time: 10_000,
+                duration: 6_000_000,
+                device: "web".to_owned(),
+            }),
+        );
+    }
+    dispatch(
+        &runtime_a,
+        Action::Player(ActionPlayer::PausedChanged { paused: true }),
+    );
+    dispatch(&runtime_b, Action::WatchParty(ActionWatchParty::Sync));
+    assert_eq!(
+        received_commands(&mut rx_b),
+        vec![("a".to_owned(), WatchPartyCommand::Pause { time: 10_000 })]
+    );
+
+    // the player of b never applies the received command
+    *NOW.write().unwrap() = TestEnv::now() + Duration::seconds(30);
+    dispatch(
+        &runtime_b,
+        Action::Player(ActionPlayer::PausedChanged { paused: true }),
+    );
+    dispatch(&runtime_a, Action::WatchParty(ActionWatchParty::Sync));
+    assert!(
+        received_commands(&mut rx_a)
+            .iter()
+            .any(|(member_id, command)| member_id == "b"
+                && matches!(command, WatchPartyCommand::Pause { .. })),
+        "The action of the user is sent to the party once the dropped command expires"
+    );
+    assert!(runtime_b.model().unwrap().watch_party.pending.is_empty());
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use futures::future;
+use once_cell::sync::Lazy;
+use url::Url;
+
+use crate::runtime::{EnvFutureExt, TryEnvFuture};
+use crate::types::watch_party::{WatchPartyEnvelope, WatchPartyMessage};
+use crate::watch_party_transport::WatchPartyTransport;
+
+pub static PARTIES: Lazy<RwLock<HashMap<Url, Vec<WatchPartyEnvelope>>>> =
+    Lazy::new(Default::default);
+
+/// Exchanges the messages between the runtimes of the test,
+/// used by the [`TestEnv`](crate::unit_tests::TestEnv) for all parties.
+pub struct WatchPartyLoopbackTransport {
+    party_url: Url,
+}
+
+impl WatchPartyLoopbackTransport {
+    pub fn new(party_url: Url) -> Self {
+        WatchPartyLoopbackTransport { party_url }
+    }
+}
+
+impl WatchPartyTransport for WatchPartyLoopbackTransport {
+    fn send(&self, message: &WatchPartyMessage) -> TryEnvFuture<()> {
+        let mut parties = PARTIES.write().expect("parties write failed");
+        let envelopes = parties.entry(self.party_url.to_owned()).or_default();
+        envelopes.push(WatchPartyEnvelope {
+            seq: envelopes.len() as u64,
+            message: message.to_owned(),
+        });
+        future::ok(()).boxed_env()
+    }
+    fn receive(&self, after: Option<u64>) -> TryEnvFuture<Vec<WatchPartyEnvelope>> {
+        let parties = PARTIES.read().expect("parties read failed");
+        let envelopes = parties
+            .get(&self.party_url)
+            .map(|envelopes| {
+                envelopes
+                    .iter()
+                    .filter(|envelope| after.map_or(true, |after| envelope.seq > after))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        future::ok(envelopes).boxed_env()
+    }
+}
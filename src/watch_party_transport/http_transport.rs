@@ -0,0 +1,49 @@
+use std::marker::PhantomData;
+
+use futures::TryFutureExt;
+use http::Request;
+use url::Url;
+
+use crate::runtime::{Env, EnvFutureExt, TryEnvFuture};
+use crate::types::api::SuccessResponse;
+use crate::types::watch_party::{WatchPartyEnvelope, WatchPartyMessage};
+use crate::watch_party_transport::WatchPartyTransport;
+
+/// Exchanges the messages through a relay server, which orders them
+/// and serves them at `{party_url}/messages`.
+pub struct WatchPartyHTTPTransport<E: Env> {
+    party_url: Url,
+    env: PhantomData<E>,
+}
+
+impl<E: Env> WatchPartyHTTPTransport<E> {
+    pub fn new(party_url: Url) -> Self {
+        WatchPartyHTTPTransport {
+            party_url,
+            env: PhantomData,
+        }
+    }
+    fn messages_url(&self) -> String {
+        format!("{}/messages", self.party_url.as_str().trim_end_matches('/'))
+    }
+}
+
+impl<E: Env + 'static> WatchPartyTransport for WatchPartyHTTPTransport<E> {
+    fn send(&self, message: &WatchPartyMessage) -> TryEnvFuture<()> {
+        let request = Request::post(self.messages_url())
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(message.to_owned())
+            .expect("request builder failed");
+        E::fetch::<_, SuccessResponse>(request)
+            .map_ok(|_| ())
+            .boxed_env()
+    }
+    fn receive(&self, after: Option<u64>) -> TryEnvFuture<Vec<WatchPartyEnvelope>> {
+        let url = match after {
+            Some(after) => format!("{}?after={after}", self.messages_url()),
+            None => self.messages_url(),
+        };
+        let request = Request::get(url).body(()).expect("request builder failed");
+        E::fetch(request)
+    }
+}
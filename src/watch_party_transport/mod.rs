@@ -0,0 +1,8 @@
+mod watch_party_transport;
+pub use watch_party_transport::*;
+
+mod http_transport;
+pub use http_transport::*;
+
+mod unsupported_transport;
+pub use unsupported_transport::*;
@@ -0,0 +1,32 @@
+use futures::future;
+use url::Url;
+
+use crate::runtime::{ConditionalSend, EnvError, EnvFutureExt, TryEnvFuture};
+use crate::types::watch_party::{WatchPartyEnvelope, WatchPartyMessage};
+use crate::watch_party_transport::WatchPartyTransport;
+
+pub struct UnsupportedWatchPartyTransport {
+    party_url: Url,
+}
+
+impl UnsupportedWatchPartyTransport {
+    pub fn new(party_url: Url) -> Self {
+        UnsupportedWatchPartyTransport { party_url }
+    }
+    fn result<T: Sized + ConditionalSend + 'static>(&self) -> TryEnvFuture<T> {
+        future::err(EnvError::Other(format!(
+            "Unsupported watch party transport: {}",
+            self.party_url.scheme()
+        )))
+        .boxed_env()
+    }
+}
+
+impl WatchPartyTransport for UnsupportedWatchPartyTransport {
+    fn send(&self, _message: &WatchPartyMessage) -> TryEnvFuture<()> {
+        self.result::<()>()
+    }
+    fn receive(&self, _after: Option<u64>) -> TryEnvFuture<Vec<WatchPartyEnvelope>> {
+        self.result::<Vec<WatchPartyEnvelope>>()
+    }
+}
@@ -0,0 +1,9 @@
+use crate::runtime::TryEnvFuture;
+use crate::types::watch_party::{WatchPartyEnvelope, WatchPartyMessage};
+
+pub trait WatchPartyTransport {
+    /// Publishes the message to all the members of the party, including the sender.
+    fn send(&self, message: &WatchPartyMessage) -> TryEnvFuture<()>;
+    /// Returns the messages published after the given sequence number, in order.
+    fn receive(&self, after: Option<u64>) -> TryEnvFuture<Vec<WatchPartyEnvelope>>;
+}
@@ -265,33 +265,7 @@ impl Env for WebEnv {
         IN: Serialize,
         for<'de> OUT: Deserialize<'de> + 'static,
     {
-        let (parts, body) = request.into_parts();
-        let url = parts.uri.to_string();
-        let method = parts.method.as_str();
-        let headers = {
-            let mut headers = HashMap::new();
-            for (key, value) in parts.headers.iter() {
-                let key = key.as_str().to_owned();
-                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
-                headers.entry(key).or_insert_with(Vec::new).push(value);
-            }
-            <JsValue as JsValueSerdeExt>::from_serde(&headers)
-                .expect("WebEnv::fetch: JsValue from Headers failed to be built")
-        };
-        let body = match serde_json::to_string(&body) {
-            Ok(ref body) if body != "null" && parts.method != Method::GET => {
-                Some(JsValue::from_str(body))
-            }
-            _ => None,
-        };
-        let mut request_options = web_sys::RequestInit::new();
-        request_options
-            .method(method)
-            .headers(&headers)
-            .body(body.as_ref());
-
-        let request = web_sys::Request::new_with_str_and_init(&url, &request_options)
-            .expect("request builder failed");
+        let request = web_request(request);
         let promise = global().fetch_with_request(&request);
         async {
             let resp = JsFuture::from(promise).await.map_err(|error| {
@@ -345,6 +319,44 @@ impl Env for WebEnv {
         .boxed_local()
     }
 
+    fn fetch_bytes(request: Request<()>) -> TryEnvFuture<Vec<u8>> {
+        let promise = global().fetch_with_request(&web_request(request));
+        async {
+            let resp = JsFuture::from(promise).await.map_err(|error| {
+                EnvError::Fetch(
+                    error
+                        .dyn_into::<js_sys::Error>()
+                        .map(|error| String::from(error.message()))
+                        .unwrap_or_else(|_| UNKNOWN_ERROR.to_owned()),
+                )
+            })?;
+            let resp = resp
+                .dyn_into::<web_sys::Response>()
+                .expect("WebEnv::fetch_bytes: Response into web_sys::Response failed to be built");
+            if resp.status() != 200 {
+                return Err(EnvError::Fetch(format!(
+                    "Unexpected HTTP status code {}",
+                    resp.status(),
+                )));
+            };
+            let buffer = JsFuture::from(
+                resp.array_buffer()
+                    .expect("WebEnv::fetch_bytes: Response array buffer failed to be retrieved"),
+            )
+            .await
+            .map_err(|error| {
+                EnvError::Fetch(
+                    error
+                        .dyn_into::<js_sys::Error>()
+                        .map(|error| String::from(error.message()))
+                        .unwrap_or_else(|_| UNKNOWN_ERROR.to_owned()),
+                )
+            })?;
+            Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+        }
+        .boxed_local()
+    }
+
     fn get_storage<T>(key: &str) -> TryEnvFuture<Option<T>>
     where
         for<'de> T: Deserialize<'de> + 'static,
@@ -504,6 +516,34 @@ fn global() -> WorkerGlobalScope {
         .expect("worker global scope is not available")
 }
 
+fn web_request<IN: Serialize>(request: Request<IN>) -> web_sys::Request {
+    let (parts, body) = request.into_parts();
+    let url = parts.uri.to_string();
+    let method = parts.method.as_str();
+    let headers = {
+        let mut headers = HashMap::new();
+        for (key, value) in parts.headers.iter() {
+            let key = key.as_str().to_owned();
+            let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+            headers.entry(key).or_insert_with(Vec::new).push(value);
+        }
+        <JsValue as JsValueSerdeExt>::from_serde(&headers)
+            .expect("WebEnv::fetch: JsValue from Headers failed to be built")
+    };
+    let body = match serde_json::to_string(&body) {
+        Ok(ref body) if body != "null" && parts.method != Method::GET => {
+            Some(JsValue::from_str(body))
+        }
+        _ => None,
+    };
+    let mut request_options = web_sys::RequestInit::new();
+    request_options
+        .method(method)
+        .headers(&headers)
+        .body(body.as_ref());
+    web_sys::Request::new_with_str_and_init(&url, &request_options).expect("request builder failed")
+}
+
 fn response_deserialize<OUT>(response: js_sys::JsString) -> Result<OUT, EnvError>
 where
     for<'de> OUT: Deserialize<'de> + 'static,
@@ -8,7 +8,10 @@ use serde::Serialize;
 
 use stremio_core::{
     models::{
+        addon_configure::AddonConfigure,
         addon_details::AddonDetails,
+        calendar::Calendar,
+        cast_session::CastSession,
         catalog_with_filters::CatalogWithFilters,
         catalogs_with_extra::CatalogsWithExtra,
         continue_watching_preview::ContinueWatchingPreview,
@@ -20,13 +23,20 @@ use stremio_core::{
         local_search::LocalSearch,
         meta_details::MetaDetails,
         player::Player,
+        stats::Stats,
         streaming_server::StreamingServer,
+        trailer_player::TrailerPlayer,
+        watch_history::WatchHistory,
+        watch_party::WatchParty,
     },
     runtime::Effects,
     types::{
-        addon::DescriptorPreview, api::LinkAuthKey, events::DismissedEventsBucket,
-        library::LibraryBucket, notifications::NotificationsBucket, profile::Profile,
+        addon::DescriptorPreview, addons_history::AddonsHistoryBucket, api::LinkAuthKey,
+        events::DismissedEventsBucket, intros::IntrosBucket,
+        language_preferences::LanguagePreferencesBucket, library::LibraryBucket,
+        notifications::NotificationsBucket, play_queue::PlayQueueBucket, profile::Profile,
         resource::MetaItemPreview, search_history::SearchHistoryBucket, streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
     },
     Model,
 };
@@ -53,11 +63,19 @@ pub struct WebModel {
     pub remote_addons: CatalogWithFilters<DescriptorPreview>,
     pub installed_addons: InstalledAddonsWithFilters,
     pub addon_details: AddonDetails,
+    pub addon_configure: AddonConfigure,
     pub streaming_server: StreamingServer,
     pub player: Player,
+    pub trailer_player: TrailerPlayer,
+    pub cast_session: CastSession,
+    pub watch_party: WatchParty,
+    pub watch_history: WatchHistory,
+    pub stats: Stats,
+    pub calendar: Calendar,
 }
 
 impl WebModel {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         profile: Profile,
         library: LibraryBucket,
@@ -65,6 +83,11 @@ impl WebModel {
         notifications: NotificationsBucket,
         search_history: SearchHistoryBucket,
         dismissed_events: DismissedEventsBucket,
+        language_preferences: LanguagePreferencesBucket,
+        intros: IntrosBucket,
+        play_queue: PlayQueueBucket,
+        watch_history: WatchHistoryBucket,
+        addons_history: AddonsHistoryBucket,
     ) -> (WebModel, Effects) {
         let (continue_watching_preview, continue_watching_preview_effects) =
             ContinueWatchingPreview::new(&library, &notifications);
@@ -79,6 +102,7 @@ impl WebModel {
             InstalledAddonsWithFilters::new(&profile);
         let (streaming_server, streaming_server_effects) = StreamingServer::new::<WebEnv>(&profile);
         let (local_search, local_search_effects) = LocalSearch::new::<WebEnv>();
+        let (watch_history_, watch_history_effects) = WatchHistory::new(&watch_history);
         let model = WebModel {
             ctx: Ctx::new(
                 profile,
@@ -87,6 +111,11 @@ impl WebModel {
                 notifications,
                 search_history,
                 dismissed_events,
+                language_preferences,
+                intros,
+                play_queue,
+                watch_history,
+                addons_history,
             ),
             auth_link: Default::default(),
             data_export: Default::default(),
@@ -101,8 +130,15 @@ impl WebModel {
             remote_addons,
             installed_addons,
             addon_details: Default::default(),
+            addon_configure: Default::default(),
             streaming_server,
             player: Default::default(),
+            trailer_player: Default::default(),
+            cast_session: Default::default(),
+            watch_party: Default::default(),
+            watch_history: watch_history_,
+            stats: Default::default(),
+            calendar: Default::default(),
         };
         (
             model,
@@ -113,7 +149,8 @@ impl WebModel {
                 .join(remote_addons_effects)
                 .join(installed_addons_effects)
                 .join(streaming_server_effects)
-                .join(local_search_effects),
+                .join(local_search_effects)
+                .join(watch_history_effects),
         )
     }
     pub fn get_state(&self, field: &WebModelField) -> JsValue {
@@ -173,10 +210,36 @@ impl WebModel {
                 <JsValue as JsValueSerdeExt>::from_serde(&self.addon_details)
                     .expect("JsValue from AddonDetails")
             }
+            WebModelField::AddonConfigure => {
+                <JsValue as JsValueSerdeExt>::from_serde(&self.addon_configure)
+                    .expect("JsValue from AddonConfigure")
+            }
             WebModelField::StreamingServer => serialize_streaming_server(&self.streaming_server),
             WebModelField::Player => {
                 serialize_player::<WebEnv>(&self.player, &self.ctx, &self.streaming_server)
             }
+            WebModelField::TrailerPlayer => {
+                <JsValue as JsValueSerdeExt>::from_serde(&self.trailer_player)
+                    .expect("JsValue from TrailerPlayer")
+            }
+            WebModelField::CastSession => {
+                <JsValue as JsValueSerdeExt>::from_serde(&self.cast_session)
+                    .expect("JsValue from CastSession")
+            }
+            WebModelField::WatchParty => {
+                <JsValue as JsValueSerdeExt>::from_serde(&self.watch_party)
+                    .expect("JsValue from WatchParty")
+            }
+            WebModelField::WatchHistory => {
+                <JsValue as JsValueSerdeExt>::from_serde(&self.watch_history)
+                    .expect("JsValue from WatchHistory")
+            }
+            WebModelField::Stats => {
+                <JsValue as JsValueSerdeExt>::from_serde(&self.stats).expect("JsValue from Stats")
+            }
+            WebModelField::Calendar => {
+                serialize_calendar(&self.calendar, &self.ctx, &self.streaming_server)
+            }
         }
     }
 }
@@ -0,0 +1,80 @@
+#[cfg(feature = "wasm")]
+use gloo_utils::format::JsValueSerdeExt;
+use serde::Serialize;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::JsValue;
+
+use stremio_core::deep_links::VideoDeepLinks;
+#[cfg(feature = "wasm")]
+use stremio_core::models::{calendar::Calendar, ctx::Ctx, streaming_server::StreamingServer};
+
+#[cfg(feature = "wasm")]
+use crate::model::deep_links_ext::DeepLinksExt;
+
+mod model {
+    use super::*;
+
+    use chrono::NaiveDate;
+    use stremio_core::{
+        models::calendar::{Selectable, Selected},
+        types::resource::{MetaItemPreview, Video},
+    };
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Calendar<'a> {
+        pub selected: &'a Option<Selected>,
+        pub selectable: &'a Selectable,
+        pub days: Vec<Day<'a>>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Day<'a> {
+        pub date: &'a NaiveDate,
+        pub items: Vec<Item<'a>>,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Item<'a> {
+        pub meta_item: &'a MetaItemPreview,
+        pub video: &'a Video,
+        pub deep_links: VideoDeepLinks,
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub fn serialize_calendar(
+    calendar: &Calendar,
+    ctx: &Ctx,
+    streaming_server: &StreamingServer,
+) -> JsValue {
+    <JsValue as JsValueSerdeExt>::from_serde(&model::Calendar {
+        selected: &calendar.selected,
+        selectable: &calendar.selectable,
+        days: calendar
+            .days
+            .iter()
+            .map(|day| model::Day {
+                date: &day.date,
+                items: day
+                    .items
+                    .iter()
+                    .map(|item| model::Item {
+                        meta_item: &item.meta_item,
+                        video: &item.video,
+                        deep_links: VideoDeepLinks::from((
+                            &item.video,
+                            &item.request,
+                            &streaming_server.base_url,
+                            &ctx.profile.settings,
+                        ))
+                        .into_web_deep_links(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    })
+    .expect("JsValue from model::Calendar")
+}
@@ -100,6 +100,7 @@ mod model {
         library_item: LibraryItem<'a>,
         /// a count of the total notifications we have for this item
         notifications: usize,
+        season_added: Option<u32>,
     }
 
     impl<'a>
@@ -126,6 +127,7 @@ mod model {
                     settings,
                 )),
                 notifications: item.notifications,
+                season_added: item.season_added,
             }
         }
     }
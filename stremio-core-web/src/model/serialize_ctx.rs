@@ -15,9 +15,10 @@ mod model {
     use itertools::Itertools;
     use serde::Serialize;
 
-    use stremio_core::deep_links::SearchHistoryItemDeepLinks;
+    use stremio_core::deep_links::{MetaItemDeepLinks, SearchHistoryItemDeepLinks};
     use stremio_core::types::{
-        events::Events, notifications::NotificationItem, profile::Profile, resource::MetaItemId,
+        addons_history::AddonsSnapshot, events::Events, notifications::NotificationItem,
+        profile::Profile, resource::MetaItemId,
     };
 
     use crate::model::deep_links_ext::DeepLinksExt;
@@ -29,6 +30,9 @@ mod model {
         pub profile: &'a Profile,
         pub notifications: Notifications<'a>,
         pub search_history: Vec<SearchHistoryItem<'a>>,
+        pub play_queue: Vec<PlayQueueItem<'a>>,
+        /// The snapshots of the installed addons which can be restored, the most recent first
+        pub addons_history: &'a [AddonsSnapshot],
         pub events: &'a Events,
     }
 
@@ -48,6 +52,14 @@ mod model {
         pub deep_links: SearchHistoryItemDeepLinks,
     }
 
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlayQueueItem<'a> {
+        #[serde(flatten)]
+        pub item: &'a stremio_core::types::play_queue::PlayQueueItem,
+        pub deep_links: MetaItemDeepLinks,
+    }
+
     impl<'a> From<&'a stremio_core::models::ctx::Ctx> for Ctx<'a> {
         fn from(ctx: &'a stremio_core::models::ctx::Ctx) -> Self {
             Self {
@@ -74,6 +86,17 @@ mod model {
                         deep_links: SearchHistoryItemDeepLinks::from(query).into_web_deep_links(),
                     })
                     .collect(),
+                play_queue: ctx
+                    .play_queue
+                    .items
+                    .iter()
+                    .map(|item| PlayQueueItem {
+                        item,
+                        deep_links: MetaItemDeepLinks::from(&item.meta_request().path)
+                            .into_web_deep_links(),
+                    })
+                    .collect(),
+                addons_history: &ctx.addons_history.snapshots,
                 events: &ctx.events,
             }
         }
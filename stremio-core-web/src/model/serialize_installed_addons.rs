@@ -9,7 +9,8 @@ mod model {
     use serde::Serialize;
 
     use stremio_core::{
-        deep_links::AddonsDeepLinks, models::installed_addons_with_filters::Selected,
+        deep_links::AddonsDeepLinks,
+        models::installed_addons_with_filters::{AddonUpgrade, Selected},
     };
 
     #[derive(Serialize)]
@@ -45,6 +46,7 @@ mod model {
         pub selected: &'a Option<Selected>,
         pub selectable: Selectable<'a>,
         pub catalog: Vec<DescriptorPreview<'a>>,
+        pub upgrades: &'a [AddonUpgrade],
     }
 }
 
@@ -85,6 +87,7 @@ pub fn serialize_installed_addons(
                 installed: true,
             })
             .collect(),
+        upgrades: &installed_addons.upgrades,
     })
     .expect("JsValue from model::InstalledAddonsWithFilters")
 }
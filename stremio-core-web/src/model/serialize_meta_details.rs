@@ -143,68 +143,74 @@ pub fn serialize_meta_details<E: Env + 'static>(
                     ResourceLoadable {
                         request,
                         content: Some(Loadable::Ready(meta_item)),
-                    } => Loadable::Ready(model::MetaItem {
-                        meta_item,
-                        videos: meta_item
-                            .videos
-                            .iter()
-                            .map(|video| model::Video {
-                                video,
-                                upcoming: meta_item.preview.behavior_hints.has_scheduled_videos
-                                    && video.released > Some(E::now()),
-                                watched: meta_details
-                                    .watched
-                                    .as_ref()
-                                    .map(|watched| watched.get_video(&video.id))
-                                    .unwrap_or_default(),
-                                progress: ctx
-                                    .library
-                                    .items
-                                    .get(&meta_item.preview.id)
-                                    .filter(|library_item| {
-                                        Some(video.id.to_owned()) == library_item.state.video_id
-                                    })
-                                    .map(|library_item| library_item.progress()),
-                                scheduled: meta_item.preview.behavior_hints.has_scheduled_videos,
-                                deep_links: VideoDeepLinks::from((
+                    } => {
+                        let meta_item = meta_details.merged_meta_item.as_ref().unwrap_or(meta_item);
+                        Loadable::Ready(model::MetaItem {
+                            meta_item,
+                            videos: meta_item
+                                .videos
+                                .iter()
+                                .map(|video| model::Video {
                                     video,
-                                    request,
-                                    &streaming_server.base_url,
-                                    &ctx.profile.settings,
-                                ))
-                                .into_web_deep_links(),
-                            })
-                            .collect::<Vec<_>>(),
-                        trailer_streams: meta_item
-                            .preview
-                            .trailer_streams
-                            .iter()
-                            .map(|stream| model::Stream {
-                                stream,
-                                progress: None,
-                                deep_links: StreamDeepLinks::from((
+                                    upcoming: meta_item.preview.behavior_hints.has_scheduled_videos
+                                        && video.released > Some(E::now()),
+                                    watched: meta_details
+                                        .watched
+                                        .as_ref()
+                                        .map(|watched| watched.get_video(&video.id))
+                                        .unwrap_or_default(),
+                                    progress: ctx
+                                        .library
+                                        .items
+                                        .get(&meta_item.preview.id)
+                                        .filter(|library_item| {
+                                            Some(video.id.to_owned()) == library_item.state.video_id
+                                        })
+                                        .map(|library_item| library_item.progress()),
+                                    scheduled: meta_item
+                                        .preview
+                                        .behavior_hints
+                                        .has_scheduled_videos,
+                                    deep_links: VideoDeepLinks::from((
+                                        video,
+                                        request,
+                                        &streaming_server.base_url,
+                                        &ctx.profile.settings,
+                                    ))
+                                    .into_web_deep_links(),
+                                })
+                                .collect::<Vec<_>>(),
+                            trailer_streams: meta_item
+                                .preview
+                                .trailer_streams
+                                .iter()
+                                .map(|stream| model::Stream {
                                     stream,
-                                    &streaming_server.base_url,
-                                    &ctx.profile.settings,
-                                ))
+                                    progress: None,
+                                    deep_links: StreamDeepLinks::from((
+                                        stream,
+                                        &streaming_server.base_url,
+                                        &ctx.profile.settings,
+                                    ))
+                                    .into_web_deep_links(),
+                                })
+                                .collect::<Vec<_>>(),
+                            in_library: ctx
+                                .library
+                                .items
+                                .get(&meta_item.preview.id)
+                                .map(|library_item| !library_item.removed)
+                                .unwrap_or_default(),
+                            watched: ctx
+                                .library
+                                .items
+                                .get(&meta_item.preview.id)
+                                .map(|library_item| library_item.watched())
+                                .unwrap_or_default(),
+                            deep_links: MetaItemDeepLinks::from((meta_item, request))
                                 .into_web_deep_links(),
-                            })
-                            .collect::<Vec<_>>(),
-                        in_library: ctx
-                            .library
-                            .items
-                            .get(&meta_item.preview.id)
-                            .map(|library_item| !library_item.removed)
-                            .unwrap_or_default(),
-                        watched: ctx
-                            .library
-                            .items
-                            .get(&meta_item.preview.id)
-                            .map(|library_item| library_item.watched())
-                            .unwrap_or_default(),
-                        deep_links: MetaItemDeepLinks::from((meta_item, request))
-                            .into_web_deep_links(),
-                    }),
+                        })
+                    }
                     ResourceLoadable {
                         content: Some(Loadable::Loading),
                         ..
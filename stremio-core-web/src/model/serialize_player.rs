@@ -4,13 +4,16 @@ use url::Url;
 #[cfg(feature = "wasm")]
 use {gloo_utils::format::JsValueSerdeExt, wasm_bindgen::JsValue};
 
-use stremio_core::deep_links::{StreamDeepLinks, VideoDeepLinks};
+use stremio_core::deep_links::{MetaItemDeepLinks, StreamDeepLinks, VideoDeepLinks};
 use stremio_core::models::common::{Loadable, ResourceError, ResourceLoadable};
 use stremio_core::models::ctx::Ctx;
 use stremio_core::models::player::Player;
 use stremio_core::models::streaming_server::StreamingServer;
 use stremio_core::types::{
     addon::{ResourcePath, ResourceRequest},
+    language_preferences::LanguagePreference,
+    play_queue::PlayQueueItem,
+    streaming_server::StreamPlayback,
     streams::StreamItemState,
 };
 
@@ -63,10 +66,11 @@ mod model {
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct Subtitles<'a> {
-        #[serde(flatten)]
-        pub subtitles: &'a stremio_core::types::resource::Subtitles,
-        pub id: String,
-        pub origin: &'a String,
+        pub id: &'a String,
+        pub lang: &'a String,
+        pub url: &'a Url,
+        pub origin: &'a str,
+        pub exact_match: bool,
     }
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
@@ -86,22 +90,37 @@ mod model {
     #[serde(rename_all = "camelCase")]
     pub struct Selected<'a> {
         pub stream: Stream<'a>,
+        pub playback: Option<StreamPlayback>,
         pub stream_request: &'a Option<ResourceRequest>,
         pub meta_request: &'a Option<ResourceRequest>,
         pub subtitles_path: &'a Option<ResourcePath>,
     }
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
+    pub struct UpNext<'a> {
+        #[serde(flatten)]
+        pub item: &'a PlayQueueItem,
+        /// The stream from the same binge group as the selected one, if found
+        pub stream: Option<Stream<'a>>,
+        pub deep_links: MetaItemDeepLinks,
+    }
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
     pub struct Player<'a> {
         pub selected: Option<Selected<'a>>,
         pub meta_item: Option<Loadable<model::MetaItem<'a>, &'a ResourceError>>,
         pub subtitles: Vec<model::Subtitles<'a>>,
+        pub selected_subtitles_id: Option<&'a String>,
         pub next_video: Option<Video<'a>>,
+        pub up_next: Option<UpNext<'a>>,
         pub series_info: Option<&'a stremio_core::types::resource::SeriesInfo>,
         pub library_item: Option<LibraryItem<'a>>,
         pub stream_state: Option<&'a StreamItemState>,
+        pub language_preference: Option<&'a LanguagePreference>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub intro_outro: Option<&'a stremio_core::types::player::IntroOutro>,
+        pub sleep_timer: Option<&'a stremio_core::models::player::SleepTimer>,
+        pub still_watching_requested: bool,
         pub title: Option<String>,
         pub addon: Option<model::DescriptorPreview<'a>>,
     }
@@ -124,6 +143,7 @@ pub fn serialize_player<E: stremio_core::runtime::Env + 'static>(
                 ))
                 .into_web_deep_links(),
             },
+            playback: streaming_server.stream_playback(&selected.stream),
             stream_request: &selected.stream_request,
             meta_request: &selected.meta_request,
             subtitles_path: &selected.subtitles_path,
@@ -160,33 +180,43 @@ pub fn serialize_player<E: stremio_core::runtime::Env + 'static>(
                 }
             }),
         subtitles: player
-            .subtitles
+            .subtitles_tracks
             .iter()
-            .filter_map(|subtitles| {
-                ctx.profile
+            .filter_map(|track| {
+                // the subtitles of the stream are labeled with the addon which returned the stream
+                let origin_url = match &track.origin {
+                    Some(origin) => Some(origin),
+                    None => player
+                        .selected
+                        .as_ref()
+                        .and_then(|selected| selected.stream_request.as_ref())
+                        .map(|stream_request| &stream_request.base),
+                };
+                let origin = ctx
+                    .profile
                     .addons
                     .iter()
-                    .find(|addon| addon.transport_url == subtitles.request.base)
-                    .map(|addon| (addon, subtitles))
-            })
-            .filter_map(|(addon, subtitles)| match subtitles {
-                ResourceLoadable {
-                    content: Some(Loadable::Ready(subtitles)),
-                    ..
-                } => Some((addon, subtitles)),
-                _ => None,
-            })
-            .flat_map(|(addon, subtitles)| {
-                subtitles
-                    .iter()
-                    .enumerate()
-                    .map(move |(position, subtitles)| model::Subtitles {
-                        subtitles,
-                        id: format!("{}_{}", addon.transport_url, position),
-                        origin: &addon.manifest.name,
-                    })
+                    .find(|addon| Some(&addon.transport_url) == origin_url)
+                    .map(|addon| addon.manifest.name.as_str());
+                let origin = match (origin, &track.origin) {
+                    (Some(origin), _) => origin,
+                    // local files and streams of uninstalled addons
+                    (None, None) => "",
+                    (None, Some(_)) => return None,
+                };
+                Some(model::Subtitles {
+                    id: &track.id,
+                    lang: &track.lang,
+                    url: &track.url,
+                    origin,
+                    exact_match: track.exact_match,
+                })
             })
             .collect(),
+        selected_subtitles_id: player
+            .selected_subtitles_track
+            .as_ref()
+            .map(|track| &track.id),
         next_video: player
             .selected
             .as_ref()
@@ -236,6 +266,28 @@ pub fn serialize_player<E: stremio_core::runtime::Env + 'static>(
                 ))
                 .into_web_deep_links(),
             }),
+        up_next: player.up_next.as_ref().map(|item| {
+            let meta_request = item.meta_request();
+            model::UpNext {
+                item,
+                stream: player
+                    .up_next_stream
+                    .as_ref()
+                    .zip(player.up_next_streams.as_ref())
+                    .map(|(stream, up_next_streams)| model::Stream {
+                        stream,
+                        deep_links: StreamDeepLinks::from((
+                            stream,
+                            &up_next_streams.request,
+                            &meta_request,
+                            &streaming_server.base_url,
+                            &ctx.profile.settings,
+                        ))
+                        .into_web_deep_links(),
+                    }),
+                deep_links: MetaItemDeepLinks::from(&meta_request.path).into_web_deep_links(),
+            }
+        }),
         series_info: player.series_info.as_ref(),
         library_item: player
             .library_item
@@ -248,7 +300,10 @@ pub fn serialize_player<E: stremio_core::runtime::Env + 'static>(
                 },
             }),
         stream_state: player.stream_state.as_ref(),
+        language_preference: player.language_preference.as_ref(),
         intro_outro: player.intro_outro.as_ref(),
+        sleep_timer: player.sleep_timer.as_ref(),
+        still_watching_requested: player.still_watching_requested,
         title: player.selected.as_ref().and_then(|selected| {
             player
                 .meta_item
@@ -6,7 +6,9 @@ use stremio_core::models::common::Loadable;
 use stremio_core::models::streaming_server::{PlaybackDevice, Selected};
 use stremio_core::runtime::EnvError;
 use stremio_core::types::addon::ResourcePath;
-use stremio_core::types::streaming_server::{DeviceInfo, NetworkInfo, Settings, Statistics};
+use stremio_core::types::streaming_server::{
+    DeviceInfo, NetworkInfo, PlaybackCapabilities, Settings, Statistics,
+};
 use url::Url;
 #[cfg(feature = "wasm")]
 use wasm_bindgen::JsValue;
@@ -26,6 +28,7 @@ mod model {
         pub playback_devices: &'a Loadable<Vec<PlaybackDevice>, EnvError>,
         pub network_info: &'a Loadable<NetworkInfo, EnvError>,
         pub device_info: &'a Loadable<DeviceInfo, EnvError>,
+        pub playback_capabilities: &'a PlaybackCapabilities,
         pub torrent: Option<(&'a InfoHash, TorrentLoadable<'a>)>,
         pub statistics: Option<&'a Loadable<Statistics, EnvError>>,
     }
@@ -44,6 +47,7 @@ pub fn serialize_streaming_server(
         playback_devices: &streaming_server.playback_devices,
         network_info: &streaming_server.network_info,
         device_info: &streaming_server.device_info,
+        playback_capabilities: &streaming_server.playback_capabilities,
         torrent: streaming_server
             .torrent
             .as_ref()
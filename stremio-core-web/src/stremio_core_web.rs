@@ -1,4 +1,4 @@
-use std::sync::RwLock;
+use std::{str::FromStr, sync::RwLock};
 
 use enclose::enclose;
 use futures::{future, try_join, FutureExt, StreamExt};
@@ -10,16 +10,27 @@ use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
 use stremio_core::{
     constants::{
-        DISMISSED_EVENTS_STORAGE_KEY, LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY,
-        NOTIFICATIONS_STORAGE_KEY, PROFILE_STORAGE_KEY, SEARCH_HISTORY_STORAGE_KEY,
-        STREAMS_STORAGE_KEY,
+        ADDONS_HISTORY_STORAGE_KEY, DISMISSED_EVENTS_STORAGE_KEY, INTROS_STORAGE_KEY,
+        LANGUAGE_PREFERENCES_STORAGE_KEY, LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY,
+        NOTIFICATIONS_STORAGE_KEY, PLAY_QUEUE_STORAGE_KEY, PROFILE_STORAGE_KEY,
+        SEARCH_HISTORY_STORAGE_KEY, STREAMS_STORAGE_KEY, WATCH_HISTORY_STORAGE_KEY,
     },
+    deep_links::DeepLinkRoute,
     models::common::Loadable,
     runtime::{msg::Action, Env, EnvError, Runtime, RuntimeAction, RuntimeEvent},
     types::{
-        events::DismissedEventsBucket, library::LibraryBucket, notifications::NotificationsBucket,
-        profile::Profile, resource::Stream, search_history::SearchHistoryBucket,
+        addons_history::AddonsHistoryBucket,
+        events::DismissedEventsBucket,
+        intros::IntrosBucket,
+        language_preferences::LanguagePreferencesBucket,
+        library::LibraryBucket,
+        notifications::{NotificationsBucket, NotificationsExportFormat},
+        play_queue::PlayQueueBucket,
+        profile::Profile,
+        resource::Stream,
+        search_history::SearchHistoryBucket,
         streams::StreamsBucket,
+        watch_history::WatchHistoryBucket,
     },
 };
 
@@ -71,6 +82,11 @@ pub async fn initialize_runtime(emit_to_ui: js_sys::Function) -> Result<(), JsVa
                 WebEnv::get_storage::<NotificationsBucket>(NOTIFICATIONS_STORAGE_KEY),
                 WebEnv::get_storage::<SearchHistoryBucket>(SEARCH_HISTORY_STORAGE_KEY),
                 WebEnv::get_storage::<DismissedEventsBucket>(DISMISSED_EVENTS_STORAGE_KEY),
+                WebEnv::get_storage::<LanguagePreferencesBucket>(LANGUAGE_PREFERENCES_STORAGE_KEY),
+                WebEnv::get_storage::<IntrosBucket>(INTROS_STORAGE_KEY),
+                WebEnv::get_storage::<PlayQueueBucket>(PLAY_QUEUE_STORAGE_KEY),
+                WebEnv::get_storage::<WatchHistoryBucket>(WATCH_HISTORY_STORAGE_KEY),
+                WebEnv::get_storage::<AddonsHistoryBucket>(ADDONS_HISTORY_STORAGE_KEY),
             );
             match storage_result {
                 Ok((
@@ -81,6 +97,11 @@ pub async fn initialize_runtime(emit_to_ui: js_sys::Function) -> Result<(), JsVa
                     notifications_bucket,
                     search_history_bucket,
                     dismissed_events_bucket,
+                    language_preferences_bucket,
+                    intros_bucket,
+                    play_queue_bucket,
+                    watch_history_bucket,
+                    addons_history_bucket,
                 )) => {
                     let profile = profile.unwrap_or_default();
                     let mut library = LibraryBucket::new(profile.uid(), vec![]);
@@ -98,6 +119,15 @@ pub async fn initialize_runtime(emit_to_ui: js_sys::Function) -> Result<(), JsVa
                         search_history_bucket.unwrap_or(SearchHistoryBucket::new(profile.uid()));
                     let dismissed_events_bucket = dismissed_events_bucket
                         .unwrap_or(DismissedEventsBucket::new(profile.uid()));
+                    let language_preferences_bucket = language_preferences_bucket
+                        .unwrap_or(LanguagePreferencesBucket::new(profile.uid()));
+                    let intros_bucket = intros_bucket.unwrap_or(IntrosBucket::new(profile.uid()));
+                    let play_queue_bucket =
+                        play_queue_bucket.unwrap_or(PlayQueueBucket::new(profile.uid()));
+                    let watch_history_bucket =
+                        watch_history_bucket.unwrap_or(WatchHistoryBucket::new(profile.uid()));
+                    let addons_history_bucket =
+                        addons_history_bucket.unwrap_or(AddonsHistoryBucket::new(profile.uid()));
                     let (model, effects) = WebModel::new(
                         profile,
                         library,
@@ -105,6 +135,11 @@ pub async fn initialize_runtime(emit_to_ui: js_sys::Function) -> Result<(), JsVa
                         notifications_bucket,
                         search_history_bucket,
                         dismissed_events_bucket,
+                        language_preferences_bucket,
+                        intros_bucket,
+                        play_queue_bucket,
+                        watch_history_bucket,
+                        addons_history_bucket,
                     );
                     let (runtime, rx) = Runtime::<WebEnv, _>::new(
                         model,
@@ -236,3 +271,31 @@ pub fn decode_stream(stream: JsValue) -> JsValue {
         _ => JsValue::NULL,
     }
 }
+
+/// Parses a `stremio:///` or `#/` deep link into the route and the `Load` action args
+#[wasm_bindgen]
+pub fn parse_deep_link(deep_link: JsValue) -> JsValue {
+    let route = deep_link
+        .as_string()
+        .map(|deep_link| DeepLinkRoute::from_str(&deep_link));
+    match route {
+        Some(Ok(route)) => {
+            <JsValue as JsValueSerdeExt>::from_serde(&route).expect("JsValue from DeepLinkRoute")
+        }
+        _ => JsValue::NULL,
+    }
+}
+
+#[wasm_bindgen]
+pub fn export_notifications(format: JsValue) -> JsValue {
+    let format: NotificationsExportFormat =
+        JsValueSerdeExt::into_serde(&format).expect("export notifications failed");
+    let runtime = RUNTIME.read().expect("runtime read failed");
+    let runtime = runtime
+        .as_ref()
+        .expect("runtime is not ready")
+        .as_ref()
+        .expect("runtime is not ready");
+    let model = runtime.model().expect("model read failed");
+    JsValue::from_str(&model.ctx.notifications.export(&model.ctx.library, format))
+}